@@ -0,0 +1,337 @@
+//! Turns conditions the monitor can already observe -- a replica that's
+//! gone quiet, or an end-to-end latency sample past its SLA -- into
+//! structured incidents fired at a webhook (PagerDuty Events v2 is a
+//! JSON POST like any other, so the same sink works for it or a plain
+//! webhook). Without this, catching either condition means scraping
+//! `process_producer`'s Prometheus counters (or the e2e latency
+//! histogram) with an external tool and wiring up alertmanager rules
+//! against them, instead of the monitor owning detection end to end.
+//!
+//! Each condition is identified by an [`IncidentKey`] so repeated
+//! breaches dedupe to one open incident: a `trigger` fires on the first
+//! breach, nothing further is sent while it's still holding, and a
+//! `resolve` fires once the condition clears. [`ProcessLivenessWatch`]
+//! is wired to the real `process_producer` tee each [`crate::domain::Domain`]
+//! already registers (see [`crate::tee`]), so the replica-silence half of
+//! this is fully live. The E2E-latency half needs a sample stream from
+//! `crate::steps::e2e::E2ELatency`, which isn't present in this snapshot
+//! of the workspace -- [`E2ELatencyWatch`] takes that stream as a plain
+//! channel so wiring it in is a one-line change once that step exists
+//! here, rather than something this module needs to guess the shape of.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use reqwest::Client;
+use serde::Serialize;
+use tokio::{
+    select,
+    sync::{mpsc::UnboundedReceiver, Mutex},
+    time::MissedTickBehavior,
+};
+
+use crate::tee::TeeSubscription;
+
+/// Identifies one alertable condition for dedupe/resolve bookkeeping:
+/// which network, which kind of breach, and (for a per-replica lane)
+/// which replica.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct IncidentKey {
+    pub(crate) network: String,
+    pub(crate) event: &'static str,
+    pub(crate) replica_of: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IncidentState {
+    Open,
+    Resolved,
+}
+
+/// A PagerDuty Events v2 payload; doubles as a generic JSON webhook body
+/// for anything else that just wants `{event_action, summary}`.
+#[derive(Debug, Serialize)]
+struct AlertEvent<'a> {
+    routing_key: &'a str,
+    event_action: &'a str,
+    dedup_key: String,
+    payload: AlertPayload<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct AlertPayload<'a> {
+    summary: &'a str,
+    source: &'a str,
+    severity: &'a str,
+}
+
+/// Where incidents go and how sensitive the two built-in conditions are.
+/// Read from env alongside the rest of [`crate::init::Monitor::from_config`]'s
+/// network setup.
+#[derive(Debug, Clone)]
+pub(crate) struct AlertConfig {
+    pub(crate) webhook_url: String,
+    pub(crate) routing_key: String,
+    /// How long a home->replica pair can go without a `process` event
+    /// before [`ProcessLivenessWatch`] fires.
+    pub(crate) process_silence_threshold: Duration,
+    /// How often [`ProcessLivenessWatch`] checks elapsed silence against
+    /// `process_silence_threshold`.
+    pub(crate) poll_interval: Duration,
+}
+
+impl AlertConfig {
+    /// `None` if `ALERT_WEBHOOK_URL`/`ALERT_ROUTING_KEY` aren't set --
+    /// alerting is opt-in, since most deployments still rely on the
+    /// external scrape+alertmanager path.
+    pub(crate) fn from_env() -> Option<Self> {
+        let webhook_url = std::env::var("ALERT_WEBHOOK_URL").ok()?;
+        let routing_key = std::env::var("ALERT_ROUTING_KEY").ok()?;
+
+        let process_silence_threshold = std::env::var("ALERT_PROCESS_SILENCE_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(10 * 60));
+
+        let poll_interval = std::env::var("ALERT_POLL_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(30));
+
+        Some(Self {
+            webhook_url,
+            routing_key,
+            process_silence_threshold,
+            poll_interval,
+        })
+    }
+}
+
+/// Dedupes and delivers incidents. Cheap to share: every watcher holds
+/// an `Arc<AlertSink>` and calls `fire`/`resolve` as its own condition
+/// changes.
+#[derive(Debug)]
+pub(crate) struct AlertSink {
+    client: Client,
+    config: AlertConfig,
+    state: Mutex<HashMap<IncidentKey, IncidentState>>,
+}
+
+impl AlertSink {
+    pub(crate) fn new(config: AlertConfig) -> Arc<Self> {
+        Arc::new(Self {
+            client: Client::new(),
+            config,
+            state: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn dedup_key(key: &IncidentKey) -> String {
+        format!(
+            "{}:{}:{}",
+            key.network,
+            key.event,
+            key.replica_of.as_deref().unwrap_or("-")
+        )
+    }
+
+    async fn send(&self, key: &IncidentKey, event_action: &'static str, summary: &str) {
+        let event = AlertEvent {
+            routing_key: &self.config.routing_key,
+            event_action,
+            dedup_key: Self::dedup_key(key),
+            payload: AlertPayload {
+                summary,
+                source: "nomad-monitor",
+                severity: "critical",
+            },
+        };
+
+        if let Err(e) = self
+            .client
+            .post(&self.config.webhook_url)
+            .json(&event)
+            .send()
+            .await
+        {
+            tracing::warn!(
+                network = key.network.as_str(),
+                event = key.event,
+                error = %e,
+                "failed to deliver alert webhook"
+            );
+        }
+    }
+
+    /// Trigger `key`'s incident if it isn't already open -- a no-op
+    /// while a breach keeps holding across polls, so a flapping pair
+    /// doesn't spam the endpoint.
+    pub(crate) async fn fire(&self, key: IncidentKey, summary: String) {
+        let mut state = self.state.lock().await;
+        if matches!(state.get(&key), Some(IncidentState::Open)) {
+            return;
+        }
+        state.insert(key.clone(), IncidentState::Open);
+        drop(state);
+        self.send(&key, "trigger", &summary).await;
+    }
+
+    /// Resolve `key`'s incident if one is open.
+    pub(crate) async fn resolve(&self, key: IncidentKey) {
+        let mut state = self.state.lock().await;
+        if !matches!(state.get(&key), Some(IncidentState::Open)) {
+            return;
+        }
+        state.insert(key.clone(), IncidentState::Resolved);
+        drop(state);
+        let summary = format!("{} recovered", key.network);
+        self.send(&key, "resolve", &summary).await;
+    }
+}
+
+/// Watches one home->replica pair's `process_producer` tee for silence.
+/// Generic over the mirrored event type since all this cares about is
+/// that *something* arrived, not its contents.
+pub(crate) struct ProcessLivenessWatch<T> {
+    network: String,
+    replica_of: String,
+    events: TeeSubscription<T>,
+    threshold: Duration,
+    poll_interval: Duration,
+    sink: Arc<AlertSink>,
+}
+
+impl<T: Clone + Send + Sync + 'static> ProcessLivenessWatch<T> {
+    pub(crate) fn new(
+        network: String,
+        replica_of: String,
+        events: TeeSubscription<T>,
+        config: &AlertConfig,
+        sink: Arc<AlertSink>,
+    ) -> Self {
+        Self {
+            network,
+            replica_of,
+            events,
+            threshold: config.process_silence_threshold,
+            poll_interval: config.poll_interval,
+            sink,
+        }
+    }
+
+    fn key(&self) -> IncidentKey {
+        IncidentKey {
+            network: self.network.clone(),
+            event: "process_silence",
+            replica_of: Some(self.replica_of.clone()),
+        }
+    }
+
+    pub(crate) fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut last_seen = Instant::now();
+            let mut tick = tokio::time::interval(self.poll_interval);
+            tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                select! {
+                    biased;
+
+                    event = self.events.recv() => {
+                        match event {
+                            Some(_) => {
+                                last_seen = Instant::now();
+                                self.sink.resolve(self.key()).await;
+                            }
+                            None => {
+                                tracing::debug!(
+                                    network = self.network.as_str(),
+                                    replica_of = self.replica_of.as_str(),
+                                    "process tee closed; stopping liveness watch"
+                                );
+                                return;
+                            }
+                        }
+                    }
+                    _ = tick.tick() => {
+                        let elapsed = last_seen.elapsed();
+                        if elapsed >= self.threshold {
+                            self.sink.fire(
+                                self.key(),
+                                format!(
+                                    "no process event observed for {}'s replica of {} in over {:?}",
+                                    self.network, self.replica_of, elapsed
+                                ),
+                            ).await;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Watches a stream of `(domain_number, latency_seconds)` samples for a
+/// bound breach. Domain-agnostic about where the samples come from;
+/// `crate::steps::e2e::E2ELatency` is the real source once it's present
+/// in this tree -- today nothing feeds this channel.
+pub(crate) struct E2ELatencyWatch {
+    domain_to_network: HashMap<u32, String>,
+    samples: UnboundedReceiver<(u32, Duration)>,
+    bound: Duration,
+    sink: Arc<AlertSink>,
+}
+
+impl E2ELatencyWatch {
+    pub(crate) fn new(
+        domain_to_network: HashMap<u32, String>,
+        samples: UnboundedReceiver<(u32, Duration)>,
+        bound: Duration,
+        sink: Arc<AlertSink>,
+    ) -> Self {
+        Self {
+            domain_to_network,
+            samples,
+            bound,
+            sink,
+        }
+    }
+
+    fn key(&self, domain: u32) -> IncidentKey {
+        IncidentKey {
+            network: self
+                .domain_to_network
+                .get(&domain)
+                .cloned()
+                .unwrap_or_else(|| domain.to_string()),
+            event: "e2e_latency",
+            replica_of: None,
+        }
+    }
+
+    pub(crate) fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while let Some((domain, latency)) = self.samples.recv().await {
+                let key = self.key(domain);
+                if latency >= self.bound {
+                    self.sink
+                        .fire(
+                            key,
+                            format!(
+                                "end-to-end latency {:?} exceeded bound {:?}",
+                                latency, self.bound
+                            ),
+                        )
+                        .await;
+                } else {
+                    self.sink.resolve(key).await;
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,139 @@
+//! Persistent store for the "seen but not yet matched" correlation state
+//! that `DispatchWait`/`UpdateWait`/`RelayWait` keep while waiting for a
+//! dispatch to be updated, an update to be relayed, or a relay to be
+//! processed. Without this, that state lives only in memory, so a crash
+//! or redeploy mid-flight silently drops every in-progress latency
+//! sample instead of completing it once the matching event arrives.
+//!
+//! Each wait step gets its own [`Column`] (a sled tree, which is already
+//! exactly the "column family" shape this wants) keyed by a monotonic
+//! `u64` sequence number for the pending item, storing
+//! the block number and wall-clock time it was first seen. A step
+//! rehydrates its pending set from its column on startup, and removes an
+//! entry once it's matched and the latency observed.
+
+use std::collections::HashMap;
+
+use ethers::prelude::H256;
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+/// One correlation stage's column. Each variant names the sled tree it
+/// maps to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Column {
+    DispatchSeen,
+    UpdateSeen,
+    RelaySeen,
+}
+
+impl Column {
+    fn tree_name(&self) -> &'static str {
+        match self {
+            Column::DispatchSeen => "dispatch_seen",
+            Column::UpdateSeen => "update_seen",
+            Column::RelaySeen => "relay_seen",
+        }
+    }
+}
+
+/// The first-seen record for a pending correlation: the block it was
+/// observed in, and the unix timestamp it was observed at, so a wait
+/// step can recompute an elapsed duration on match even after its own
+/// process restarted in between. `committed_root` is `DispatchSeen`-only:
+/// the home's incremental tree root immediately after this dispatch's
+/// leaf, which `DispatchWait` rehydrates so it can still match the
+/// correct update post-restart. `UpdateSeen`/`RelaySeen` leave it `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SeenAt {
+    pub(crate) block_number: u64,
+    pub(crate) unix_ts: u64,
+    pub(crate) committed_root: Option<H256>,
+}
+
+/// Governs how [`Writable::apply`] mutates a column entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheUpdatePolicy {
+    /// Insert or replace the value stored under `key`.
+    Overwrite,
+    /// Remove the value stored under `key` -- used once a pair is
+    /// matched and its latency sample has been emitted.
+    Remove,
+}
+
+/// Write-through access to a [`CorrelationStore`] column.
+pub(crate) trait Writable {
+    fn apply(
+        &self,
+        column: Column,
+        key: u64,
+        policy: CacheUpdatePolicy,
+        value: Option<SeenAt>,
+    ) -> eyre::Result<()>;
+}
+
+/// Read-back access to a [`CorrelationStore`] column, used to rehydrate a
+/// wait step's pending set on startup.
+pub(crate) trait Readable {
+    fn rehydrate(&self, column: Column) -> eyre::Result<HashMap<u64, SeenAt>>;
+}
+
+/// A small embedded, column-family-style key/value store for pipeline
+/// correlation state, backed by `sled`.
+#[derive(Debug)]
+pub(crate) struct CorrelationStore {
+    db: sled::Db,
+}
+
+impl CorrelationStore {
+    /// Open (creating if absent) the correlation store at `path`.
+    pub(crate) fn open(path: impl AsRef<std::path::Path>) -> eyre::Result<Self> {
+        let db = sled::open(path).wrap_err("opening correlation store")?;
+        Ok(Self { db })
+    }
+
+    fn tree(&self, column: Column) -> eyre::Result<sled::Tree> {
+        Ok(self.db.open_tree(column.tree_name())?)
+    }
+}
+
+impl Writable for CorrelationStore {
+    fn apply(
+        &self,
+        column: Column,
+        key: u64,
+        policy: CacheUpdatePolicy,
+        value: Option<SeenAt>,
+    ) -> eyre::Result<()> {
+        let tree = self.tree(column)?;
+        match policy {
+            CacheUpdatePolicy::Overwrite => {
+                let value = value.expect("Overwrite requires a value");
+                let encoded = bincode::serialize(&value).wrap_err("encoding correlation entry")?;
+                tree.insert(key.to_be_bytes(), encoded)?;
+            }
+            CacheUpdatePolicy::Remove => {
+                tree.remove(key.to_be_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Readable for CorrelationStore {
+    fn rehydrate(&self, column: Column) -> eyre::Result<HashMap<u64, SeenAt>> {
+        let tree = self.tree(column)?;
+        tree.iter()
+            .map(|entry| {
+                let (key, value) = entry.wrap_err("reading correlation entry")?;
+                let key = u64::from_be_bytes(
+                    key.as_ref()
+                        .try_into()
+                        .map_err(|_| eyre::eyre!("corrupt correlation key"))?,
+                );
+                let value: SeenAt = bincode::deserialize(&value).wrap_err("decoding correlation entry")?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+}
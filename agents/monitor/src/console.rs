@@ -0,0 +1,23 @@
+//! Optional `tokio-console` wiring, gated behind the `tokio-console`
+//! feature so default builds don't pull in its dependencies or pay its
+//! always-on event recording overhead.
+//!
+//! Every spawned step (`DispatchWait` and friends) already wraps its
+//! loop in `info_span!("DispatchWait", network, emitter)` via
+//! `.instrument(span)`, so once [`init`] installs `console-subscriber`
+//! as the process's tracing subscriber, each task shows up in
+//! `tokio-console` under that span's name with its `network`/`emitter`
+//! fields attached -- letting an operator see which step's task is
+//! stalled (high poll time, long idle gaps) when latency climbs,
+//! without needing to guess from Prometheus alone.
+
+#![cfg(feature = "tokio-console")]
+
+/// Install `console-subscriber` as the global default tracing
+/// subscriber. Must be called once, as early as possible in the
+/// process's `main` -- this checkout has no `main.rs` for this crate to
+/// call it from yet, so wiring this in is left to whatever binary
+/// entrypoint eventually exists.
+pub(crate) fn init() {
+    console_subscriber::init();
+}
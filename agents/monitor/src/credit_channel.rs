@@ -0,0 +1,134 @@
+//! A bounded, credit-accounted replacement for the plain
+//! `unbounded_channel()` every faucet/pipe hand-off here used to go
+//! through. Each [`CreditedSender`] must acquire a unit of credit before
+//! a send completes; [`CreditedReceiver`] returns that credit to the
+//! pool as it drains items. When credit is exhausted the sender awaits
+//! instead of piling more items into an ever-growing queue, so a slow
+//! downstream step (an RPC slowdown in a `*_Wait` step, say) now applies
+//! visible, bounded backpressure instead of silently growing memory.
+//!
+//! This is `tokio::sync::mpsc::channel`'s own bounded-permit model --
+//! the permit *is* the credit -- with `faucet_depth{network,step}` and
+//! `faucet_blocked_seconds{network,step}` instrumentation layered on top
+//! so the backpressure is observable instead of just a Semaphore nobody
+//! can see. `DispatchFaucet`/`DispatchSink` and their `Update`/`Relay`/
+//! `Process` counterparts now resolve to [`CreditedReceiver`]/
+//! [`CreditedSender`] rather than the raw `Unbounded*` types.
+
+use std::time::Instant;
+
+use prometheus::{Histogram, IntGauge};
+use tokio::sync::mpsc::{self, error::SendError};
+
+/// Per-stage bounded channel capacities, i.e. how much credit a producer
+/// for that stage is given. Each producer stage reads its own field;
+/// `default` is not read by any of them.
+#[derive(Debug, Clone)]
+pub(crate) struct CreditLimits {
+    pub(crate) default: usize,
+    pub(crate) dispatch: usize,
+    pub(crate) update: usize,
+    pub(crate) relay: usize,
+    pub(crate) process: usize,
+}
+
+impl Default for CreditLimits {
+    fn default() -> Self {
+        Self {
+            default: 256,
+            dispatch: 256,
+            update: 256,
+            relay: 256,
+            process: 256,
+        }
+    }
+}
+
+/// The gauges a [`CreditedSender`]/[`CreditedReceiver`] pair keeps
+/// current. One set per `(network, step)`, built by the caller (mirrors
+/// `HealthMetrics`/`DispatchWaitMetrics`).
+#[derive(Debug, Clone)]
+pub(crate) struct FaucetMetrics {
+    /// Items currently sent but not yet drained -- `faucet_depth{network,step}`.
+    pub(crate) depth: IntGauge,
+    /// Seconds a send spent waiting for credit -- `faucet_blocked_seconds{network,step}`.
+    pub(crate) blocked_seconds: Histogram,
+}
+
+/// The sending half of a [`credited_channel`]. Cheap to clone; every
+/// clone competes for the same credit pool.
+#[derive(Debug, Clone)]
+pub(crate) struct CreditedSender<T> {
+    inner: mpsc::Sender<T>,
+    metrics: FaucetMetrics,
+}
+
+impl<T> CreditedSender<T> {
+    /// Acquire one unit of credit and send `item`, awaiting if the
+    /// channel is at capacity. Records how long the wait took.
+    pub(crate) async fn send(&self, item: T) -> Result<(), SendError<T>> {
+        let start = Instant::now();
+        self.inner.send(item).await?;
+        let waited = start.elapsed();
+        if waited.as_secs_f64() > 0.0 {
+            self.metrics.blocked_seconds.observe(waited.as_secs_f64());
+        }
+        self.metrics.depth.inc();
+        Ok(())
+    }
+
+    /// Send without waiting for credit, failing immediately if none is
+    /// available. For call sites that can't await (e.g. a synchronous
+    /// `select!` arm) and would rather drop/retry than block.
+    pub(crate) fn try_send(&self, item: T) -> Result<(), mpsc::error::TrySendError<T>> {
+        self.inner.try_send(item)?;
+        self.metrics.depth.inc();
+        Ok(())
+    }
+}
+
+/// The receiving half of a [`credited_channel`].
+#[derive(Debug)]
+pub(crate) struct CreditedReceiver<T> {
+    inner: mpsc::Receiver<T>,
+    metrics: FaucetMetrics,
+}
+
+impl<T> CreditedReceiver<T> {
+    /// Receive the next item, returning its credit to the pool.
+    pub(crate) async fn recv(&mut self) -> Option<T> {
+        let item = self.inner.recv().await;
+        if item.is_some() {
+            self.metrics.depth.dec();
+        }
+        item
+    }
+
+    /// Receive without waiting, for callers that can't await (mirrors
+    /// [`CreditedSender::try_send`]). Useful for opportunistically
+    /// draining whatever's already buffered after waking on a single
+    /// ready item, e.g. so a burst across several channels can be
+    /// collected before a priority-ordered drain decides what to
+    /// process first.
+    pub(crate) fn try_recv(&mut self) -> Result<T, mpsc::error::TryRecvError> {
+        let item = self.inner.try_recv()?;
+        self.metrics.depth.dec();
+        Ok(item)
+    }
+}
+
+/// Open a bounded, credit-accounted channel with `capacity` units of
+/// credit, instrumented with `metrics`.
+pub(crate) fn credited_channel<T>(
+    capacity: usize,
+    metrics: FaucetMetrics,
+) -> (CreditedSender<T>, CreditedReceiver<T>) {
+    let (tx, rx) = mpsc::channel(capacity);
+    (
+        CreditedSender {
+            inner: tx,
+            metrics: metrics.clone(),
+        },
+        CreditedReceiver { inner: rx, metrics },
+    )
+}
@@ -1,17 +1,124 @@
-use ethers::prelude::U64;
-use prometheus::{Histogram, HistogramTimer};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use ethers::prelude::{H256, U64};
+use prometheus::{Gauge, Histogram, IntCounter};
 use tokio::select;
-use tracing::{info_span, Instrument};
+use tracing::{info_span, warn, Instrument};
 
 use crate::{
-    bail_task_if, DispatchFaucet, DispatchSink, ProcessStep, Restartable, StepHandle, UpdateFaucet,
-    UpdateSink,
+    bail_task_if,
+    checkpoint_store::{CacheUpdatePolicy, Column, CorrelationStore, Readable, SeenAt, Writable},
+    priority::{OrderTag, Priority, PriorityQueue},
+    status::{StatusSinks, StepStatus},
+    tranquilizer::{Tranquilizer, TranquilizerConfig},
+    DispatchFaucet, DispatchSink, ProcessStep, Restartable, StepHandle, UpdateFaucet, UpdateSink,
 };
 
+/// One queued, not-yet-processed dispatch or update: forwarding it
+/// downstream, pacing, and correlating it all happen when this is
+/// popped off [`DispatchWait`]'s [`PriorityQueue`] rather than
+/// immediately on receipt, so [`DispatchWaitPriorities`] governs which
+/// of the two channels' backlog drains first instead of the old
+/// hardcoded dispatch-always-first `select! { biased; ... }` order.
+/// Boxed rather than a named enum because the concrete `Dispatch`/
+/// `Update` item types live behind the still-missing crate root this
+/// checkout doesn't have -- the closure captures whichever one it needs
+/// without this module ever having to name it.
+type QueuedAction = Box<dyn FnOnce(&mut DispatchWait) -> eyre::Result<Duration> + Send>;
+
+/// Per-channel drain priority for [`DispatchWait`]'s queue. Defaults to
+/// the historical "dispatches always win" behavior (`dispatch: High`,
+/// `update: Normal`), but an operator whose bottleneck is update
+/// catch-up instead can flip it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DispatchWaitPriorities {
+    pub(crate) dispatch: Priority,
+    pub(crate) update: Priority,
+}
+
+impl Default for DispatchWaitPriorities {
+    fn default() -> Self {
+        Self {
+            dispatch: Priority::High,
+            update: Priority::Normal,
+        }
+    }
+}
+
+/// How often the stale-dispatch sweep in [`ProcessStep::spawn`] runs.
+const STALE_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+/// How often [`DispatchWait`] checks its [`StatusSinks`] subscribers for
+/// a due push -- each subscriber's own `interval` still governs how
+/// often it actually receives one.
+const STATUS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 pub(crate) struct DispatchWaitMetrics {
     pub(crate) timer: Histogram,
     pub(crate) blocks: Histogram,
+    /// Updates whose `new_root` matched no pending dispatch -- expected
+    /// once in a while for an update that only re-commits what an
+    /// earlier update already covered, but a sustained climb means
+    /// dispatches are being matched to the wrong update somewhere.
+    pub(crate) stale_updates: IntCounter,
+    /// Pending dispatches evicted by [`DispatchWait::sweep_stale`] for
+    /// having waited longer than `max_dispatch_wait` with no matching
+    /// update -- the updater side has stalled.
+    pub(crate) stale_dispatches: IntCounter,
+    /// Current pacing sleep [`Tranquilizer`] inserts around each
+    /// `outgoing_dispatch` send, for tuning `TranquilizerConfig`.
+    pub(crate) dispatch_pacing_sleep_seconds: Gauge,
+    /// Same as `dispatch_pacing_sleep_seconds`, for `outgoing_update`.
+    pub(crate) update_pacing_sleep_seconds: Gauge,
+}
+
+/// Bounds on how much unmatched state `DispatchWait` will hold before it
+/// starts shedding load, since an updater that's stopped producing would
+/// otherwise let `pending` (and the latency it eventually reports) grow
+/// without limit.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DispatchWaitLimits {
+    /// Stop polling `incoming_dispatch` once `pending` reaches this many
+    /// entries, until updates drain it back down.
+    pub(crate) max_pending: usize,
+    /// A pending dispatch older than this is evicted by the stale sweep
+    /// rather than held onto forever.
+    pub(crate) max_dispatch_wait: Duration,
+}
+
+impl Default for DispatchWaitLimits {
+    fn default() -> Self {
+        Self {
+            max_pending: 10_000,
+            max_dispatch_wait: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before unix epoch")
+        .as_secs()
+}
+
+/// A dispatch that's been seen but not yet matched with an update, kept
+/// in the order it arrived. `seq` is this pending item's key in the
+/// `dispatch_seen` correlation column; `committed_root` is the root the
+/// home's incremental tree reached immediately after this dispatch's
+/// leaf was inserted. Since the home inserts leaves (and advances that
+/// root) strictly in order, an update whose `new_root` matches one
+/// pending dispatch's `committed_root` also commits every dispatch
+/// queued ahead of it -- so [`DispatchWait::handle_update`] can find the
+/// matching entry and drain the prefix up to and including it, rather
+/// than draining unconditionally.
+#[derive(Debug, Clone, Copy)]
+struct PendingDispatch {
+    seq: u64,
+    seen: SeenAt,
+    committed_root: H256,
 }
 
 #[derive(Debug)]
@@ -23,12 +130,23 @@ pub(crate) struct DispatchWait {
     emitter: String,
 
     metrics: DispatchWaitMetrics,
+    limits: DispatchWaitLimits,
 
-    timers: Vec<HistogramTimer>,
-    blocks: Vec<U64>,
+    pending: VecDeque<PendingDispatch>,
+    next_seq: u64,
+    store: Arc<CorrelationStore>,
 
     outgoing_update: UpdateSink,
     outgoing_dispatch: DispatchSink,
+    dispatch_pacing: Tranquilizer,
+    update_pacing: Tranquilizer,
+    status_sinks: StatusSinks,
+    last_drained_at: Option<Instant>,
+
+    priorities: DispatchWaitPriorities,
+    queue: PriorityQueue<QueuedAction>,
+    dispatch_order_seq: u64,
+    update_order_seq: u64,
 }
 
 impl std::fmt::Display for DispatchWait {
@@ -42,42 +160,177 @@ impl std::fmt::Display for DispatchWait {
 }
 
 impl DispatchWait {
+    /// Rehydrates its pending set from `store`'s `dispatch_seen` column,
+    /// so dispatches seen before a restart still produce a latency
+    /// sample once their update arrives.
     pub(crate) fn new(
         incoming_dispatch: DispatchFaucet,
         incoming_update: UpdateFaucet,
         network: String,
         emitter: String,
         metrics: DispatchWaitMetrics,
+        limits: DispatchWaitLimits,
+        pacing: TranquilizerConfig,
+        priorities: DispatchWaitPriorities,
         outgoing_update: UpdateSink,
         outgoing_dispatch: DispatchSink,
-    ) -> Self {
-        Self {
+        store: Arc<CorrelationStore>,
+    ) -> eyre::Result<Self> {
+        let mut rehydrated: Vec<PendingDispatch> = store
+            .rehydrate(Column::DispatchSeen)?
+            .into_iter()
+            .map(|(seq, seen)| {
+                let committed_root = seen
+                    .committed_root
+                    .expect("DispatchSeen entries always carry a committed_root");
+                PendingDispatch {
+                    seq,
+                    seen,
+                    committed_root,
+                }
+            })
+            .collect();
+        rehydrated.sort_by_key(|pending| pending.seq);
+        let next_seq = rehydrated.last().map(|pending| pending.seq + 1).unwrap_or(0);
+
+        let dispatch_pacing = Tranquilizer::new(pacing, metrics.dispatch_pacing_sleep_seconds.clone());
+        let update_pacing = Tranquilizer::new(pacing, metrics.update_pacing_sleep_seconds.clone());
+
+        Ok(Self {
             incoming_dispatch,
             incoming_update,
             network,
             emitter,
             metrics,
-            timers: vec![],
-            blocks: vec![],
+            limits,
+            pending: rehydrated.into(),
+            next_seq,
+            store,
             outgoing_update,
             outgoing_dispatch,
+            dispatch_pacing,
+            update_pacing,
+            status_sinks: StatusSinks::new(),
+            last_drained_at: None,
+            priorities,
+            queue: PriorityQueue::new(),
+            dispatch_order_seq: 0,
+            update_order_seq: 0,
+        })
+    }
+
+    /// Subscribe `sender` to a [`StepStatus`] snapshot roughly every
+    /// `interval`. Must be called before [`ProcessStep::spawn`] moves
+    /// `self` into its task.
+    pub(crate) fn register_status_sink(
+        &mut self,
+        interval: Duration,
+        sender: tokio::sync::mpsc::Sender<StepStatus>,
+    ) {
+        self.status_sinks.register(interval, sender);
+    }
+
+    /// A snapshot of this step's current health: how many dispatches are
+    /// pending a match, the block the most recent of them was seen in
+    /// (`None` while nothing is pending), and how long it's been since an
+    /// update last drained the queue.
+    fn status(&self) -> StepStatus {
+        StepStatus {
+            network: self.network.clone(),
+            step: "dispatch_to_update".to_owned(),
+            pending: self.pending.len(),
+            last_observed_block: self.pending.back().map(|p| p.seen.block_number),
+            since_last_drain: self.last_drained_at.map(|at| at.elapsed()),
         }
     }
 
-    fn handle_dispatch(&mut self, block_number: U64) {
-        self.timers.push(self.metrics.timer.start_timer());
-        self.blocks.push(block_number);
+    fn handle_dispatch(&mut self, block_number: U64, committed_root: H256) -> eyre::Result<()> {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        let seen = SeenAt {
+            block_number: block_number.as_u64(),
+            unix_ts: now_unix(),
+            committed_root: Some(committed_root),
+        };
+        self.store
+            .apply(Column::DispatchSeen, seq, CacheUpdatePolicy::Overwrite, Some(seen))?;
+        self.pending.push_back(PendingDispatch {
+            seq,
+            seen,
+            committed_root,
+        });
+        Ok(())
     }
 
-    fn handle_update(&mut self, block_number: U64) {
-        // drain the entire vec
-        self.timers
-            .drain(0..)
-            .for_each(|timer| timer.observe_duration());
-        self.blocks.drain(0..).for_each(|dispatch_height| {
-            let diff = block_number.saturating_sub(dispatch_height);
+    /// Match `new_root` against the pending queue: if some pending
+    /// dispatch's own `committed_root` equals `new_root`, this update
+    /// commits it and every dispatch queued ahead of it (the home's tree
+    /// root only advances forward, so an earlier dispatch can't still be
+    /// uncommitted once a later one is). Dispatches queued after the
+    /// match stay pending for a future update. An update matching nothing
+    /// in the queue is a no-op other than bumping `stale_updates` -- it
+    /// may simply be re-confirming roots an earlier update already
+    /// committed.
+    fn handle_update(&mut self, block_number: U64, new_root: H256) -> eyre::Result<()> {
+        let matched = self
+            .pending
+            .iter()
+            .position(|pending| pending.committed_root == new_root);
+
+        let Some(matched) = matched else {
+            self.metrics.stale_updates.inc();
+            return Ok(());
+        };
+
+        for pending in self.pending.drain(0..=matched) {
+            let elapsed = now_unix().saturating_sub(pending.seen.unix_ts) as f64;
+            self.metrics.timer.observe(elapsed);
+
+            let diff = block_number.saturating_sub(U64::from(pending.seen.block_number));
             self.metrics.blocks.observe(diff.as_u64() as f64);
-        });
+
+            self.store
+                .apply(Column::DispatchSeen, pending.seq, CacheUpdatePolicy::Remove, None)?;
+        }
+        self.last_drained_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Whether `pending` is at or over `max_pending` -- while true,
+    /// `spawn` stops polling `incoming_dispatch` so the buffer can drain
+    /// instead of growing without bound.
+    fn over_capacity(&self) -> bool {
+        self.pending.len() >= self.limits.max_pending
+    }
+
+    /// Evict any pending dispatch that's been waiting longer than
+    /// `max_dispatch_wait` with no matching update -- the updater side
+    /// has stalled, and holding these forever would eventually produce a
+    /// meaningless latency sample (or just leak memory). Each eviction
+    /// bumps `stale_dispatches` and logs a warning naming the stalled
+    /// lane.
+    fn sweep_stale(&mut self) -> eyre::Result<()> {
+        let now = now_unix();
+        let max_wait = self.limits.max_dispatch_wait.as_secs();
+
+        while let Some(oldest) = self.pending.front() {
+            if now.saturating_sub(oldest.seen.unix_ts) <= max_wait {
+                break;
+            }
+            let stale = self.pending.pop_front().expect("checked by front() above");
+            self.metrics.stale_dispatches.inc();
+            warn!(
+                network = self.network.as_str(),
+                emitter = self.emitter.as_str(),
+                seq = stale.seq,
+                waited_secs = now.saturating_sub(stale.seen.unix_ts),
+                "evicting dispatch that waited past max_dispatch_wait with no matching update"
+            );
+            self.store
+                .apply(Column::DispatchSeen, stale.seq, CacheUpdatePolicy::Remove, None)?;
+        }
+        Ok(())
     }
 }
 
@@ -103,38 +356,130 @@ impl ProcessStep for DispatchWait {
             emitter = self.emitter.as_str(),
         );
 
+        let mut stale_sweep = tokio::time::interval(STALE_SWEEP_INTERVAL);
+        let mut status_check = tokio::time::interval(STATUS_CHECK_INTERVAL);
+
+        // Build the `QueuedAction` for a just-received dispatch/update and
+        // push it onto `$self.queue` under its stream's `OrderTag` and the
+        // configured `DispatchWaitPriorities`, rather than processing it
+        // inline. Macros rather than methods because the concrete item
+        // type flowing out of `$self.incoming_dispatch`/`incoming_update`
+        // isn't nameable in a fn signature in this checkout (see the
+        // `QueuedAction` doc comment) -- within a macro body it's just
+        // inferred from `$item`'s usage, same as it already is in a `let`.
+        macro_rules! enqueue_dispatch {
+            ($self:expr, $item:expr) => {{
+                let dispatch = $item;
+                let seq = $self.dispatch_order_seq;
+                $self.dispatch_order_seq += 1;
+                let action: QueuedAction = Box::new(move |me: &mut DispatchWait| {
+                    let block_number = dispatch.meta.block_number;
+                    let committed_root = dispatch.committed_root;
+                    let pacing_start = me.dispatch_pacing.tranquilize_start();
+                    if me.outgoing_dispatch.try_send(dispatch).is_err() {
+                        eyre::bail!("outbound dispatch broke");
+                    }
+                    let sleep = me.dispatch_pacing.record(pacing_start.elapsed(), 1);
+                    me.handle_dispatch(block_number, committed_root)?;
+                    Ok(sleep)
+                });
+                $self.queue.push(
+                    OrderTag { stream_id: "dispatch".to_owned(), seq },
+                    $self.priorities.dispatch,
+                    action,
+                );
+            }};
+        }
+        macro_rules! enqueue_update {
+            ($self:expr, $item:expr) => {{
+                let update = $item;
+                let seq = $self.update_order_seq;
+                $self.update_order_seq += 1;
+                let action: QueuedAction = Box::new(move |me: &mut DispatchWait| {
+                    let block_number = update.meta.block_number;
+                    let new_root = update.new_root;
+                    let pacing_start = me.update_pacing.tranquilize_start();
+                    if me.outgoing_update.try_send(update).is_err() {
+                        eyre::bail!("outbound update broke");
+                    }
+                    let sleep = me.update_pacing.record(pacing_start.elapsed(), 1);
+                    me.handle_update(block_number, new_root)?;
+                    Ok(sleep)
+                });
+                $self.queue.push(
+                    OrderTag { stream_id: "update".to_owned(), seq },
+                    $self.priorities.update,
+                    action,
+                );
+            }};
+        }
+
         tokio::spawn(
             async move {
                 loop {
                     // how this works:
-                    // For each dispatch, we mark its block height and start a
-                    // timer.
-                    // Every time an update comes in, we observe all timers, and
-                    // then observe all the interblock periods.
+                    // For each dispatch, we mark its block height and committed
+                    // root, and queue it pending.
+                    // Every time an update comes in, we match its new_root
+                    // against the queue and observe a timer/interblock sample
+                    // for that dispatch and every one queued ahead of it --
+                    // not the whole queue, since an update only commits the
+                    // dispatches up to its own new_root.
+                    //
+                    // A dispatch or update isn't processed the moment
+                    // `select!` wakes for it -- it's boxed into a
+                    // `QueuedAction` and pushed onto `self.queue`, a
+                    // `PriorityQueue` keyed by `self.priorities`. Once
+                    // `select!` resolves we also opportunistically
+                    // `try_recv` anything else already buffered on either
+                    // channel, so a burst queues up together instead of
+                    // draining one-at-a-time in arrival order; only then do
+                    // we drain the queue, letting `self.priorities` (not a
+                    // hardcoded dispatch-first bias) decide which of the
+                    // two channels' backlog goes first. Within either
+                    // channel, sequence order is always preserved --
+                    // `OrderTag` never lets a stream reorder against
+                    // itself.
                     //
-                    // We always send the event onwards before making local
-                    // observations, to ensure that the next step gets it
-                    // reasonably early
+                    // A periodic sweep evicts dispatches that waited past
+                    // max_dispatch_wait with no matching update, and once
+                    // pending hits max_pending we stop polling incoming
+                    // dispatches entirely until updates drain it back under
+                    // the limit -- otherwise a stalled updater lets this
+                    // grow forever.
+                    //
+                    // A separate, more frequent tick pushes a StepStatus
+                    // snapshot to any subscribers registered via
+                    // register_status_sink whose own interval has elapsed.
 
-                    select! {
-                        // cause the select block to always poll dispatches
-                        // first. i.e. ready dispatches will arrive first
-                        biased;
+                    let over_capacity = self.over_capacity();
 
-                        dispatch_next = self.incoming_dispatch.recv() => {
+                    select! {
+                        _ = stale_sweep.tick() => {
+                            let result = self.sweep_stale();
                             bail_task_if!(
-                                dispatch_next.is_none(),
+                                result.is_err(),
                                 self,
-                                "inbound dispatch broke"
+                                format!("sweeping stale dispatches failed: {:?}", result.err())
                             );
-                            let dispatch = dispatch_next.expect("checked in block");
-                            let block_number = dispatch.meta.block_number;
+                        }
+                        _ = status_check.tick() => {
+                            let status = self.status();
+                            self.status_sinks.push(&status);
+                        }
+                        dispatch_next = self.incoming_dispatch.recv(), if !over_capacity => {
                             bail_task_if!(
-                                self.outgoing_dispatch.send(dispatch).is_err(),
+                                dispatch_next.is_none(),
                                 self,
-                                "outbound dispatch broke"
+                                "inbound dispatch broke"
                             );
-                            self.handle_dispatch(block_number);
+                            enqueue_dispatch!(self, dispatch_next.expect("checked in block"));
+                            while let Ok(extra) = self.incoming_dispatch.try_recv() {
+                                enqueue_dispatch!(self, extra);
+                            }
+                            while let Ok(extra) = self.incoming_update.try_recv() {
+                                enqueue_update!(self, extra);
+                            }
                         }
                         update_opt = self.incoming_update.recv() => {
                             bail_task_if!(
@@ -142,15 +487,28 @@ impl ProcessStep for DispatchWait {
                                 self,
                                 "inbound update broke"
                             );
-                            let update = update_opt.expect("checked in block");
-                            let block_number = update.meta.block_number;
+                            enqueue_update!(self, update_opt.expect("checked in block"));
+                            while let Ok(extra) = self.incoming_update.try_recv() {
+                                enqueue_update!(self, extra);
+                            }
+                            if !over_capacity {
+                                while let Ok(extra) = self.incoming_dispatch.try_recv() {
+                                    enqueue_dispatch!(self, extra);
+                                }
+                            }
+                        }
+                    }
 
-                            bail_task_if!(
-                                self.outgoing_update.send(update).is_err(),
-                                self,
-                                "outbound update broke"
-                            );
-                            self.handle_update(block_number);
+                    while let Some(action) = self.queue.pop() {
+                        let result = action(&mut self);
+                        bail_task_if!(
+                            result.is_err(),
+                            self,
+                            format!("processing queued dispatch/update failed: {:?}", result.err())
+                        );
+                        let sleep = result.expect("checked by bail_task_if! above");
+                        if sleep > Duration::ZERO {
+                            tokio::time::sleep(sleep).await;
                         }
                     }
                 }
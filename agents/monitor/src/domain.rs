@@ -6,16 +6,23 @@ use nomad_xyz_configuration::{contracts::CoreContracts, NomadConfig};
 use tokio::sync::mpsc::unbounded_channel;
 
 use crate::{
+    alert::{AlertConfig, AlertSink, ProcessLivenessWatch},
+    checkpoint_store::CorrelationStore,
+    credit_channel::{credited_channel, CreditLimits},
     faucets::Faucets,
+    health::{HealthMetrics, HealthMonitor, HealthThresholds, NtpClockReference, ReadinessGate},
     init::provider_for,
     metrics::Metrics,
     steps::{
         between::{BetweenEvents, BetweenMetrics},
-        dispatch_wait::DispatchWait,
+        dispatch_wait::{DispatchWait, DispatchWaitLimits, DispatchWaitPriorities},
+        tranquilizer::TranquilizerConfig,
         producer::{DispatchProducer, ProcessProducer, RelayProducer, UpdateProducer},
         relay_wait::RelayWait,
         update_wait::UpdateWait,
     },
+    supervisor::{RestartPolicy, TaskRegistry},
+    tee::{SubscriptionRegistry, TeeSubscription, SUBSCRIPTION_BUFFER},
     DispatchFaucet, ProcessFaucet, Provider, RelayFaucet, UpdateFaucet,
 };
 
@@ -25,6 +32,27 @@ pub(crate) struct Domain {
     pub(crate) domain_number: u32,
     pub(crate) home: Home<Provider>,
     pub(crate) replicas: HashMap<String, Replica<Provider>>,
+    pub(crate) tasks: Arc<TaskRegistry>,
+    pub(crate) correlation_store: Arc<CorrelationStore>,
+    pub(crate) credit_limits: CreditLimits,
+    pub(crate) subscriptions: SubscriptionRegistry,
+    /// Shared alert config/sink, cloned into every domain so each
+    /// `process_producer` can stand up its own [`ProcessLivenessWatch`].
+    /// `None` when `ALERT_WEBHOOK_URL`/`ALERT_ROUTING_KEY` aren't set --
+    /// alerting is opt-in.
+    pub(crate) alerting: Option<(AlertConfig, Arc<AlertSink>)>,
+}
+
+/// Stand-in `on_restart` callback for [`TaskRegistry::run_supervised`]
+/// until `Metrics` grows a `restarts_total{network,step}` counter to
+/// increment here instead.
+fn log_restart(label: &(String, String, Option<String>)) {
+    tracing::warn!(
+        network = label.0.as_str(),
+        step = label.1.as_str(),
+        replica_of = label.2.as_deref().unwrap_or(""),
+        "restarts_total"
+    );
 }
 
 impl Domain {
@@ -36,6 +64,7 @@ impl Domain {
         config: &NomadConfig,
         network: &str,
         to_monitor: &[String],
+        alerting: Option<(AlertConfig, Arc<AlertSink>)>,
     ) -> eyre::Result<Self> {
         let network = network.to_owned();
         let provider = provider_for(config, &network)?;
@@ -62,11 +91,21 @@ impl Domain {
             })
             .collect();
 
+        let correlation_store = Arc::new(CorrelationStore::open(format!(
+            "monitor_db/{}_correlation",
+            network
+        ))?);
+
         Ok(Domain {
             network,
             home,
             replicas,
             domain_number,
+            tasks: Arc::new(TaskRegistry::new()),
+            correlation_store,
+            credit_limits: CreditLimits::default(),
+            subscriptions: SubscriptionRegistry::new(),
+            alerting,
         })
     }
 
@@ -82,34 +121,159 @@ impl Domain {
         &self.replicas
     }
 
-    pub(crate) fn dispatch_producer(&self) -> DispatchFaucet {
-        let (tx, rx) = unbounded_channel();
+    /// Tap the mirrored event stream for `stage` (and, for replica-scoped
+    /// stages like `relay_producer`/`process_producer`, `replica_of`).
+    /// Returns `None` until that stage's producer has registered its tee
+    /// (i.e. until `dispatch_producer`/`update_producer`/
+    /// `relay_producer_for`/`process_producer_for` has run), or if `T`
+    /// doesn't match the event type that stage actually mirrors.
+    pub(crate) fn subscribe<T: Clone + Send + Sync + 'static>(
+        &self,
+        stage: &str,
+        replica_of: Option<&str>,
+    ) -> Option<TeeSubscription<T>> {
+        self.subscriptions.subscribe(stage, replica_of)
+    }
 
-        DispatchProducer::new(self.home.clone(), &self.network, tx).run_until_panic();
+    /// Start polling this domain's home for chain liveness and clock
+    /// drift, flipping `readiness` unhealthy if either exceeds its
+    /// threshold. Restarted under supervision like the other steps; an
+    /// NTP/RPC hiccup shouldn't permanently stop monitoring the domain.
+    /// `watchdog`, when set, also reports a confirmed stall so a pod
+    /// restart can be issued instead of the stall only showing up in
+    /// `readiness`.
+    pub(crate) fn health_monitor(
+        &self,
+        metrics: HealthMetrics,
+        readiness: ReadinessGate,
+        watchdog: Option<(Arc<crate::watchdog::Watchdog>, String)>,
+    ) {
+        let home = self.home.clone();
+        let network = self.network.clone();
+        let label = (self.network.clone(), "health_monitor".to_owned(), None);
+
+        self.tasks.run_supervised(
+            label,
+            RestartPolicy::default(),
+            Arc::new(log_restart),
+            move || {
+                HealthMonitor::new(
+                    network.clone(),
+                    home.clone(),
+                    HealthThresholds::default(),
+                    metrics.clone(),
+                    readiness.clone(),
+                    Arc::new(NtpClockReference::new("pool.ntp.org:123")),
+                    watchdog.clone(),
+                )
+                .spawn()
+            },
+        );
+    }
+
+    pub(crate) fn dispatch_producer(&self, metrics: &Metrics) -> DispatchFaucet {
+        let (tx, rx) = credited_channel(
+            self.credit_limits.dispatch,
+            metrics.faucet_metrics(&self.network, "dispatch_producer"),
+        );
+
+        let home = self.home.clone();
+        let network = self.network.clone();
+        let label = (self.network.clone(), "dispatch_producer".to_owned(), None);
+        self.tasks.run_supervised(
+            label,
+            RestartPolicy::default(),
+            Arc::new(log_restart),
+            move || DispatchProducer::new(home.clone(), &network, tx.clone()).run_until_panic(),
+        );
+
+        let (rx, tee) = crate::tee::tee(
+            rx,
+            metrics.faucet_metrics(&self.network, "dispatch_producer"),
+            self.credit_limits.dispatch,
+            SUBSCRIPTION_BUFFER,
+            metrics.faucet_tee_metrics(&self.network, "dispatch_producer", None),
+        );
+        self.subscriptions.insert("dispatch_producer", None, tee);
 
         rx
     }
 
-    pub(crate) fn update_producer(&self) -> UpdateFaucet {
-        let (tx, rx) = unbounded_channel();
+    pub(crate) fn update_producer(&self, metrics: &Metrics) -> UpdateFaucet {
+        let (tx, rx) = credited_channel(
+            self.credit_limits.update,
+            metrics.faucet_metrics(&self.network, "update_producer"),
+        );
+
+        let home = self.home.clone();
+        let network = self.network.clone();
+        let label = (self.network.clone(), "update_producer".to_owned(), None);
+        self.tasks.run_supervised(
+            label,
+            RestartPolicy::default(),
+            Arc::new(log_restart),
+            move || UpdateProducer::new(home.clone(), &network, tx.clone()).run_until_panic(),
+        );
 
-        UpdateProducer::new(self.home.clone(), &self.network, tx).run_until_panic();
+        let (rx, tee) = crate::tee::tee(
+            rx,
+            metrics.faucet_metrics(&self.network, "update_producer"),
+            self.credit_limits.update,
+            SUBSCRIPTION_BUFFER,
+            metrics.faucet_tee_metrics(&self.network, "update_producer", None),
+        );
+        self.subscriptions.insert("update_producer", None, tee);
 
         rx
     }
 
-    pub fn relay_producer_for(&self, replica: &Replica<Provider>, replica_of: &str) -> RelayFaucet {
-        let (tx, rx) = unbounded_channel();
+    pub fn relay_producer_for(
+        &self,
+        replica: &Replica<Provider>,
+        replica_of: &str,
+        metrics: &Metrics,
+    ) -> RelayFaucet {
+        let (tx, rx) = credited_channel(
+            self.credit_limits.relay,
+            metrics.faucet_metrics(&self.network, "relay_producer"),
+        );
+
+        let replica = replica.clone();
+        let network = self.network.clone();
+        let replica_of_owned = replica_of.to_owned();
+        let label = (
+            self.network.clone(),
+            "relay_producer".to_owned(),
+            Some(replica_of.to_owned()),
+        );
+        self.tasks.run_supervised(
+            label,
+            RestartPolicy::default(),
+            Arc::new(log_restart),
+            move || {
+                RelayProducer::new(replica.clone(), &network, &replica_of_owned, tx.clone())
+                    .run_until_panic()
+            },
+        );
+
+        let (rx, tee) = crate::tee::tee(
+            rx,
+            metrics.faucet_metrics(&self.network, "relay_producer"),
+            self.credit_limits.relay,
+            SUBSCRIPTION_BUFFER,
+            metrics.faucet_tee_metrics(&self.network, "relay_producer", Some(replica_of)),
+        );
+        self.subscriptions
+            .insert("relay_producer", Some(replica_of), tee);
 
-        RelayProducer::new(replica.clone(), &self.network, replica_of, tx).run_until_panic();
         rx
     }
 
-    pub(crate) fn relay_producers(&self) -> HashMap<&str, RelayFaucet> {
+    pub(crate) fn relay_producers(&self, metrics: &Metrics) -> HashMap<&str, RelayFaucet> {
         self.replicas()
             .iter()
             .map(|(network, replica)| {
-                let producer = self.relay_producer_for(replica, network);
+                let producer = self.relay_producer_for(replica, network, metrics);
                 (network.as_str(), producer)
             })
             .collect()
@@ -119,18 +283,66 @@ impl Domain {
         &self,
         replica: &Replica<Provider>,
         replica_of: &str,
+        metrics: &Metrics,
     ) -> ProcessFaucet {
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = credited_channel(
+            self.credit_limits.process,
+            metrics.faucet_metrics(&self.network, "process_producer"),
+        );
+
+        let replica = replica.clone();
+        let network = self.network.clone();
+        let replica_of_owned = replica_of.to_owned();
+        let label = (
+            self.network.clone(),
+            "process_producer".to_owned(),
+            Some(replica_of.to_owned()),
+        );
+        self.tasks.run_supervised(
+            label,
+            RestartPolicy::default(),
+            Arc::new(log_restart),
+            move || {
+                ProcessProducer::new(replica.clone(), &network, &replica_of_owned, tx.clone())
+                    .run_until_panic()
+            },
+        );
+
+        let (rx, tee) = crate::tee::tee(
+            rx,
+            metrics.faucet_metrics(&self.network, "process_producer"),
+            self.credit_limits.process,
+            SUBSCRIPTION_BUFFER,
+            metrics.faucet_tee_metrics(&self.network, "process_producer", Some(replica_of)),
+        );
+
+        if let Some((alert_config, sink)) = &self.alerting {
+            let watch = ProcessLivenessWatch::new(
+                self.network.clone(),
+                replica_of.to_owned(),
+                tee.subscribe(),
+                alert_config,
+                sink.clone(),
+            );
+            let label = (
+                self.network.clone(),
+                "alert_process_liveness".to_owned(),
+                Some(replica_of.to_owned()),
+            );
+            self.tasks.track(label, watch.spawn());
+        }
+
+        self.subscriptions
+            .insert("process_producer", Some(replica_of), tee);
 
-        ProcessProducer::new(replica.clone(), &self.network, replica_of, tx).run_until_panic();
         rx
     }
 
-    pub(crate) fn process_producers(&self) -> HashMap<&str, ProcessFaucet> {
+    pub(crate) fn process_producers(&self, metrics: &Metrics) -> HashMap<&str, ProcessFaucet> {
         self.replicas()
             .iter()
             .map(|(replica_of, replica)| {
-                let producer = self.process_producer_for(replica, replica_of);
+                let producer = self.process_producer_for(replica, replica_of, metrics);
                 (replica_of.as_str(), producer)
             })
             .collect()
@@ -151,7 +363,7 @@ impl Domain {
             event = event.as_ref(),
             "starting counter",
         );
-        BetweenEvents::new(
+        let handle = BetweenEvents::new(
             faucets.dispatch_pipe(self.name()),
             metrics,
             network,
@@ -159,6 +371,8 @@ impl Domain {
             emitter,
         )
         .run_until_panic();
+        self.tasks
+            .track((self.network.clone(), "count_dispatches".to_owned(), None), handle);
     }
 
     pub(crate) fn count_updates<'a>(
@@ -177,7 +391,9 @@ impl Domain {
             event = event.as_ref(),
             "starting counter",
         );
-        BetweenEvents::new(pipe, metrics, network, event, emitter).run_until_panic();
+        let handle = BetweenEvents::new(pipe, metrics, network, event, emitter).run_until_panic();
+        self.tasks
+            .track((self.network.clone(), "count_updates".to_owned(), None), handle);
     }
 
     pub(crate) fn count_relays<'a>(&'a self, faucets: &mut Faucets<'a>, metrics: Arc<Metrics>) {
@@ -196,7 +412,11 @@ impl Domain {
 
             let metrics = metrics.between_metrics(network, event, &emitter, Some(replica_of));
 
-            BetweenEvents::new(pipe, metrics, network, event, emitter).run_until_panic();
+            let handle = BetweenEvents::new(pipe, metrics, network, event, emitter).run_until_panic();
+            self.tasks.track(
+                (self.network.clone(), "count_relays".to_owned(), Some(replica_of.clone())),
+                handle,
+            );
         });
     }
 
@@ -216,7 +436,11 @@ impl Domain {
 
             let metrics = metrics.between_metrics(network, event, &emitter, Some(replica_of));
 
-            BetweenEvents::new(pipe, metrics, network, event, emitter).run_until_panic();
+            let handle = BetweenEvents::new(pipe, metrics, network, event, emitter).run_until_panic();
+            self.tasks.track(
+                (self.network.clone(), "count_processes".to_owned(), Some(replica_of.clone())),
+                handle,
+            );
         });
     }
 
@@ -230,14 +454,30 @@ impl Domain {
         let dispatch_pipe = faucets.dispatch_pipe(self.name());
         let update_pipe = faucets.update_pipe(self.name());
 
-        DispatchWait::new(
+        let wait = match DispatchWait::new(
             dispatch_pipe,
             update_pipe,
             self.name(),
             self.home_address(),
             metrics,
-        )
-        .run_until_panic();
+            DispatchWaitLimits::default(),
+            TranquilizerConfig::default(),
+            DispatchWaitPriorities::default(),
+            self.correlation_store.clone(),
+        ) {
+            Ok(wait) => wait,
+            Err(e) => {
+                tracing::error!(
+                    network = self.name(),
+                    error = %e,
+                    "failed to rehydrate dispatch correlation state; not starting dispatch_to_update"
+                );
+                return;
+            }
+        };
+        let handle = wait.run_until_panic();
+        self.tasks
+            .track((self.network.clone(), "dispatch_to_update".to_owned(), None), handle);
     }
 
     pub(crate) fn update_to_relay<'a>(&'a self, faucets: &mut Faucets<'a>, metrics: Arc<Metrics>) {
@@ -278,7 +518,7 @@ impl Domain {
 
         let metrics = metrics.update_wait_metrics(self.name(), &other_nets, &self.home_address());
 
-        UpdateWait::new(
+        let handle = UpdateWait::new(
             update_pipe,
             self.name(),
             metrics,
@@ -286,6 +526,8 @@ impl Domain {
             relay_faucets,
         )
         .run_until_panic();
+        self.tasks
+            .track((self.network.clone(), "update_to_relay".to_owned(), None), handle);
     }
 
     pub(crate) fn relay_to_process<'a>(&'a self, faucets: &mut Faucets<'a>, metrics: Arc<Metrics>) {
@@ -297,7 +539,7 @@ impl Domain {
 
             let metrics = metrics.relay_wait_metrics(self.name(), replica_of, &emitter);
 
-            RelayWait::new(
+            let handle = RelayWait::new(
                 relay_pipe,
                 process_pipe,
                 self.name().to_owned(),
@@ -306,6 +548,10 @@ impl Domain {
                 metrics,
             )
             .run_until_panic();
+            self.tasks.track(
+                (self.network.clone(), "relay_to_process".to_owned(), Some(replica_of.clone())),
+                handle,
+            );
         });
     }
 }
@@ -0,0 +1,105 @@
+//! An HDR-histogram-backed alternative to `DispatchWaitMetrics`'s fixed-
+//! bucket Prometheus `Histogram`s, gated behind the `hdr-metrics`
+//! feature so default builds stay lean. Prometheus's bucket boundaries
+//! are fixed at registration time, which hides exactly the tail latency
+//! (p99/p999) an operator most wants to see when something's slow; an
+//! [`hdrhistogram::Histogram`] instead records every sample at full
+//! resolution and can report any percentile on demand, merged across
+//! however many restarts the process has been through (via
+//! [`HdrLatency::merge_from`]).
+//!
+//! This tracks the same two measurements [`crate::dispatch_wait::
+//! DispatchWait`] already feeds into its Prometheus histograms --
+//! dispatch→update latency and interblock gap -- so it's meant to run
+//! alongside them, not replace them; Prometheus stays the always-on,
+//! dashboarded signal, and this is the higher-resolution drill-down an
+//! operator reaches for once that signal says something's wrong.
+
+#![cfg(feature = "hdr-metrics")]
+
+use hdrhistogram::Histogram;
+
+/// The percentiles [`HdrLatency::percentiles`] reports.
+const REPORTED_PERCENTILES: [f64; 4] = [50.0, 90.0, 99.0, 99.9];
+
+/// `p50`/`p90`/`p99`/`p999`, in seconds.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Percentiles {
+    pub(crate) p50: f64,
+    pub(crate) p90: f64,
+    pub(crate) p99: f64,
+    pub(crate) p999: f64,
+}
+
+/// Full-resolution latency tracking for one measurement (e.g. "dispatch
+/// to update" or "interblock gap"), recorded in microseconds since
+/// `hdrhistogram::Histogram` works over integers.
+#[derive(Debug)]
+pub(crate) struct HdrLatency {
+    histogram: Histogram<u64>,
+}
+
+impl HdrLatency {
+    /// `sigfig` is the number of significant decimal digits of precision
+    /// to retain (2-3 is typical); see `hdrhistogram::Histogram::new`.
+    /// The histogram auto-resizes, so there's no fixed upper bound a
+    /// sample could fall outside of.
+    pub(crate) fn new(sigfig: u8) -> eyre::Result<Self> {
+        Ok(Self {
+            histogram: Histogram::new(sigfig)?,
+        })
+    }
+
+    /// Record one sample.
+    pub(crate) fn observe(&mut self, value: std::time::Duration) {
+        let micros = value.as_micros().min(u128::from(u64::MAX)) as u64;
+        let _ = self.histogram.record(micros);
+    }
+
+    /// The standard percentile set, converted from microseconds to seconds.
+    pub(crate) fn percentiles(&self) -> Percentiles {
+        let micros = |q: f64| self.histogram.value_at_percentile(q) as f64 / 1_000_000.0;
+        Percentiles {
+            p50: micros(REPORTED_PERCENTILES[0]),
+            p90: micros(REPORTED_PERCENTILES[1]),
+            p99: micros(REPORTED_PERCENTILES[2]),
+            p999: micros(REPORTED_PERCENTILES[3]),
+        }
+    }
+
+    /// Merge another process's (e.g. a prior run's persisted) samples
+    /// into this one, so percentiles reflect history across restarts
+    /// rather than resetting to empty each time.
+    pub(crate) fn merge_from(&mut self, other: &Histogram<u64>) -> eyre::Result<()> {
+        self.histogram.add(other)?;
+        Ok(())
+    }
+
+    /// A reference to the underlying histogram, for serialization (e.g.
+    /// persisting it alongside `DispatchWait`'s other correlation state
+    /// so [`Self::merge_from`] has something to merge on the next
+    /// restart).
+    pub(crate) fn raw(&self) -> &Histogram<u64> {
+        &self.histogram
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn percentiles_reflect_recorded_samples() {
+        let mut latency = HdrLatency::new(3).unwrap();
+        for ms in 1..=1000u64 {
+            latency.observe(Duration::from_millis(ms));
+        }
+
+        let p = latency.percentiles();
+        // hdrhistogram's sigfig-bounded precision means these are
+        // approximate, not exact, equality checks.
+        assert!((p.p50 - 0.5).abs() < 0.005, "p50 was {}", p.p50);
+        assert!((p.p99 - 0.99).abs() < 0.01, "p99 was {}", p.p99);
+    }
+}
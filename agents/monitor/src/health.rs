@@ -0,0 +1,264 @@
+//! Per-`Domain` chain-liveness and clock-drift health checks.
+//!
+//! `*_Wait` steps record latency by diffing block numbers and wall-clock
+//! timestamps they observe from each `home` provider. Neither of those
+//! numbers can be trusted blind: a stalled chain stops advancing its
+//! block timestamp entirely, and a skewed local clock corrupts every
+//! wall-clock diff the same way an unsynced node corrupts its own
+//! consensus timing. [`HealthMonitor`] polls a `Domain`'s `home` on an
+//! interval, compares the latest block's timestamp against the previous
+//! poll (chain liveness) and against an NTP reference (clock drift), and
+//! flips a process-wide [`ReadinessGate`] unhealthy when either exceeds
+//! its threshold -- the same node-health/NTP combination an Ethereum
+//! client uses for its own sync status, applied here per monitored
+//! domain instead of per local chain.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ethers::prelude::{BlockNumber, Middleware};
+use prometheus::{Gauge, IntGauge};
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    watchdog::{LaneId, Watchdog},
+    Home, Provider,
+};
+
+/// Thresholds past which [`HealthMonitor`] considers a domain unhealthy.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HealthThresholds {
+    /// How long the home's latest block can go without a new block
+    /// before the chain is considered stalled.
+    pub(crate) max_stall: Duration,
+    /// How far local wall-clock time can drift from the NTP reference
+    /// before `clock_drift_seconds` is considered unhealthy.
+    pub(crate) max_clock_drift: Duration,
+    /// How often to poll the home provider and the NTP reference.
+    pub(crate) poll_interval: Duration,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            max_stall: Duration::from_secs(5 * 60),
+            max_clock_drift: Duration::from_secs(10),
+            poll_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// The gauges [`HealthMonitor`] keeps current. One set per `Domain`,
+/// built by the caller (mirrors `DispatchWaitMetrics`/`BetweenMetrics`).
+#[derive(Debug, Clone)]
+pub(crate) struct HealthMetrics {
+    /// Seconds between the home's latest block timestamp and now --
+    /// `block_lag_seconds{network}`.
+    pub(crate) block_lag_seconds: Gauge,
+    /// Seconds of local clock drift from the NTP reference --
+    /// `clock_drift_seconds` (process-wide, not per network, since
+    /// there's only one local clock).
+    pub(crate) clock_drift_seconds: Gauge,
+    /// `1` if this domain's chain hasn't advanced within `max_stall`,
+    /// else `0` -- `chain_stalled{network}`.
+    pub(crate) chain_stalled: IntGauge,
+}
+
+/// A process-wide readiness flag: `false` once any [`HealthMonitor`]
+/// observes a domain past its thresholds, `true` again once every
+/// domain recovers. Cheap to clone; every clone shares the same flag.
+#[derive(Debug, Clone)]
+pub(crate) struct ReadinessGate {
+    healthy: Arc<AtomicBool>,
+}
+
+impl Default for ReadinessGate {
+    fn default() -> Self {
+        Self {
+            healthy: Arc::new(AtomicBool::new(true)),
+        }
+    }
+}
+
+impl ReadinessGate {
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::SeqCst)
+    }
+
+    fn mark(&self, healthy: bool) {
+        self.healthy.store(healthy, Ordering::SeqCst);
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before unix epoch")
+        .as_secs()
+}
+
+/// Queries an external time reference for local clock drift. Pulled
+/// behind a trait so the NTP round-trip (and any future alternative
+/// reference, e.g. a beacon-chain slot clock) is swappable without
+/// touching [`HealthMonitor`]'s polling loop.
+pub(crate) trait ClockReference: Send + Sync {
+    /// Returns `local_unix_time - reference_unix_time`, i.e. positive
+    /// when the local clock is ahead.
+    fn drift_seconds(&self) -> eyre::Result<f64>;
+}
+
+/// Default [`ClockReference`]: a single NTP server queried fresh on
+/// every call.
+#[derive(Debug)]
+pub(crate) struct NtpClockReference {
+    server: String,
+}
+
+impl NtpClockReference {
+    pub(crate) fn new(server: impl Into<String>) -> Self {
+        Self {
+            server: server.into(),
+        }
+    }
+}
+
+impl ClockReference for NtpClockReference {
+    fn drift_seconds(&self) -> eyre::Result<f64> {
+        let response = ntp::request(&self.server)?;
+        let reference_unix = response.transmit_time.sec as i64 - ntp::packet::EPOCH_DELTA;
+        let local_unix = now_unix() as i64;
+        Ok((local_unix - reference_unix) as f64)
+    }
+}
+
+/// Polls a single `Domain`'s home provider for chain liveness, and an
+/// NTP reference for local clock drift.
+#[derive(Debug)]
+pub(crate) struct HealthMonitor {
+    network: String,
+    home: Home<Provider>,
+    thresholds: HealthThresholds,
+    metrics: HealthMetrics,
+    readiness: ReadinessGate,
+    clock: Arc<dyn ClockReference>,
+    last_block_timestamp: Option<u64>,
+    last_block_seen_at: Option<u64>,
+    /// Reports this domain's stall result to a [`Watchdog`] each poll, so
+    /// a confirmed stall triggers a pod restart instead of only flipping
+    /// [`ReadinessGate`]. `None` disables that reporting -- self-healing
+    /// is opt-in alongside alerting.
+    watchdog: Option<(Arc<Watchdog>, String)>,
+}
+
+impl std::fmt::Display for HealthMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HealthMonitor for {}", self.network)
+    }
+}
+
+impl HealthMonitor {
+    pub(crate) fn new(
+        network: String,
+        home: Home<Provider>,
+        thresholds: HealthThresholds,
+        metrics: HealthMetrics,
+        readiness: ReadinessGate,
+        clock: Arc<dyn ClockReference>,
+        watchdog: Option<(Arc<Watchdog>, String)>,
+    ) -> Self {
+        Self {
+            network,
+            home,
+            thresholds,
+            metrics,
+            readiness,
+            clock,
+            last_block_timestamp: None,
+            last_block_seen_at: None,
+            watchdog,
+        }
+    }
+
+    async fn poll_once(&mut self) -> eyre::Result<()> {
+        let block = self
+            .home
+            .client()
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| eyre::eyre!("home provider returned no latest block"))?;
+        let block_timestamp = block.timestamp.as_u64();
+        let now = now_unix();
+
+        let block_lag = now.saturating_sub(block_timestamp) as f64;
+        self.metrics.block_lag_seconds.set(block_lag);
+
+        let stalled = match self.last_block_timestamp {
+            Some(previous) if previous == block_timestamp => {
+                let since_seen = now.saturating_sub(self.last_block_seen_at.unwrap_or(now));
+                since_seen >= self.thresholds.max_stall.as_secs()
+            }
+            _ => false,
+        };
+        self.metrics.chain_stalled.set(stalled as i64);
+
+        if self.last_block_timestamp != Some(block_timestamp) {
+            self.last_block_timestamp = Some(block_timestamp);
+            self.last_block_seen_at = Some(now);
+        }
+
+        let drift = match self.clock.drift_seconds() {
+            Ok(drift) => {
+                self.metrics.clock_drift_seconds.set(drift);
+                drift.abs() >= self.thresholds.max_clock_drift.as_secs() as f64
+            }
+            Err(e) => {
+                tracing::warn!(network = self.network.as_str(), error = %e, "NTP reference check failed");
+                false
+            }
+        };
+
+        if stalled || drift {
+            tracing::warn!(
+                network = self.network.as_str(),
+                stalled,
+                drift,
+                "domain health check failed; marking process unready"
+            );
+            self.readiness.mark(false);
+        } else {
+            self.readiness.mark(true);
+        }
+
+        if let Some((watchdog, agent)) = &self.watchdog {
+            watchdog
+                .observe(
+                    LaneId {
+                        network: self.network.clone(),
+                        agent: agent.clone(),
+                    },
+                    stalled,
+                )
+                .await;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.thresholds.poll_interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.poll_once().await {
+                    tracing::warn!(network = self.network.as_str(), error = %e, "health poll failed");
+                }
+            }
+        })
+    }
+}
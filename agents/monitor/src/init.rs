@@ -14,8 +14,10 @@ use agent_utils::{
 };
 
 use crate::{
+    alert::{AlertConfig, AlertSink},
     domain::Domain,
     faucets::Faucets,
+    health::ReadinessGate,
     metrics::Metrics,
     steps::{e2e::E2ELatency, terminal::Terminal},
     ArcProvider, DispatchFaucet, ProcessFaucet, RelayFaucet, UpdateFaucet,
@@ -59,32 +61,118 @@ pub(crate) fn monitor() -> eyre::Result<Monitor> {
 pub(crate) struct Monitor {
     networks: HashMap<String, Domain>,
     metrics: Arc<Metrics>,
+    readiness: ReadinessGate,
+    to_monitor: Vec<String>,
+    alerting: Option<(AlertConfig, Arc<AlertSink>)>,
 }
 
 impl Monitor {
     pub(crate) fn from_config(config: &NomadConfig) -> eyre::Result<Self> {
+        let alerting = AlertConfig::from_env().map(|config| (config.clone(), AlertSink::new(config)));
+        if alerting.is_none() {
+            tracing::info!("ALERT_WEBHOOK_URL/ALERT_ROUTING_KEY not set; alerting disabled");
+        }
+
         let mut networks = HashMap::new();
         let to_monitor =
             networks_from_env().unwrap_or_else(|| config.networks.iter().cloned().collect());
         for network in config.networks.iter().filter(|s| to_monitor.contains(s)) {
             networks.insert(
                 network.to_owned(),
-                Domain::from_config(config, network, &to_monitor)?,
+                Domain::from_config(config, network, &to_monitor, alerting.clone())?,
             );
         }
         let metrics = Metrics::new()?.into();
-        Ok(Monitor { networks, metrics })
+        Ok(Monitor {
+            networks,
+            metrics,
+            readiness: ReadinessGate::default(),
+            to_monitor,
+            alerting,
+        })
+    }
+
+    /// Diff `config` against the currently running domains and apply
+    /// the result: newly present networks get a freshly constructed
+    /// [`Domain`] with its health monitor started immediately; networks
+    /// no longer present have their supervised tasks torn down and are
+    /// dropped. Either way, [`Self::rebuild_pipeline`] then rebuilds the
+    /// shared producer/counter/`*_Wait` pipeline across every remaining
+    /// domain, so an added domain actually gets monitored end-to-end
+    /// (not just its liveness/clock-drift checks) and a removed one's
+    /// former peers stop expecting it.
+    pub(crate) async fn reconcile(&mut self, config: &NomadConfig) -> eyre::Result<()> {
+        let diff = crate::reconfig::diff_networks(self.networks.keys(), config, &self.to_monitor);
+        if diff.is_empty() {
+            return Ok(());
+        }
+        tracing::info!(added = ?diff.added, removed = ?diff.removed, "applying config change");
+
+        for network in &diff.removed {
+            if let Some(domain) = self.networks.remove(network) {
+                domain.shutdown().await;
+            }
+        }
+
+        for network in &diff.added {
+            let domain = Domain::from_config(config, network, &self.to_monitor, self.alerting.clone())?;
+            let metrics = self.metrics.health_metrics(network);
+            domain.health_monitor(metrics, self.readiness.clone(), None);
+            self.networks.insert(network.clone(), domain);
+        }
+
+        self.rebuild_pipeline();
+
+        Ok(())
+    }
+
+    /// (Re)build the shared producer/counter/`*_Wait` pipeline from
+    /// scratch across every currently running domain. Safe to call after
+    /// any change to `self.networks`: each step it starts registers under
+    /// a `(network, step, replica_of)` label via [`crate::supervisor::
+    /// TaskRegistry`], which stops and replaces whatever was previously
+    /// running under that label, so an untouched domain's steps are
+    /// seamlessly swapped onto the freshly rebuilt [`Faucets`] while a
+    /// newly added domain gets them started for the first time.
+    ///
+    /// Deliberately doesn't call [`Self::run_e2e`] or
+    /// [`Self::run_terminals`] -- neither registers its task under a
+    /// `TaskRegistry` label, so calling either again here would leak a
+    /// detached task per reconcile rather than replacing the old one.
+    /// Rebuilding those two for a changed network set is follow-on work,
+    /// same as `Faucets` learning incremental insertion/removal (see
+    /// [`crate::reconfig`]).
+    pub(crate) fn rebuild_pipeline(&self) {
+        let mut faucets = self.producers();
+        self.run_betweens(&mut faucets);
+        self.run_dispatch_to_update(&mut faucets);
+        self.run_update_to_relay(&mut faucets);
+        self.run_relay_to_process(&mut faucets);
     }
 
     pub(crate) fn run_http_server(&self) -> JoinHandle<()> {
         self.metrics.clone().run_http_server()
     }
 
+    /// Process-wide readiness: `false` once any domain's chain is
+    /// stalled or the local clock has drifted past its threshold.
+    pub(crate) fn is_ready(&self) -> bool {
+        self.readiness.is_healthy()
+    }
+
+    /// Start each domain's [`crate::health::HealthMonitor`].
+    pub(crate) fn run_health_monitors(&self) {
+        self.networks.iter().for_each(|(network, domain)| {
+            let metrics = self.metrics.health_metrics(network);
+            domain.health_monitor(metrics, self.readiness.clone(), None);
+        });
+    }
+
     fn run_dispatch_producers(&self) -> HashMap<&str, DispatchFaucet> {
         let faucets: HashMap<_, _> = self
             .networks
             .iter()
-            .map(|(network, domain)| (network.as_str(), domain.dispatch_producer()))
+            .map(|(network, domain)| (network.as_str(), domain.dispatch_producer(&self.metrics)))
             .collect();
         tracing::debug!(count = faucets.len(), "running dispatch_producer");
         faucets
@@ -94,7 +182,7 @@ impl Monitor {
         let faucets: HashMap<_, _> = self
             .networks
             .iter()
-            .map(|(network, domain)| (network.as_str(), domain.update_producer()))
+            .map(|(network, domain)| (network.as_str(), domain.update_producer(&self.metrics)))
             .collect();
         tracing::debug!(count = faucets.len(), "running update_producer");
         faucets
@@ -104,7 +192,7 @@ impl Monitor {
         let faucets: HashMap<_, _> = self
             .networks
             .iter()
-            .map(|(network, domain)| (network.as_str(), domain.relay_producers()))
+            .map(|(network, domain)| (network.as_str(), domain.relay_producers(&self.metrics)))
             .collect();
         tracing::debug!(count = faucets.len(), "running relay_producers");
         faucets
@@ -114,7 +202,7 @@ impl Monitor {
         let faucets: HashMap<_, _> = self
             .networks
             .iter()
-            .map(|(network, domain)| (network.as_str(), domain.process_producers()))
+            .map(|(network, domain)| (network.as_str(), domain.process_producers(&self.metrics)))
             .collect();
         tracing::debug!(count = faucets.len(), "running process_producers");
         faucets
@@ -193,6 +281,10 @@ impl Monitor {
             .for_each(|domain| domain.relay_to_process(faucets, self.metrics.clone()));
     }
 
+    /// Not called by [`Self::rebuild_pipeline`] -- the spawned
+    /// `E2ELatency` task isn't registered under a `TaskRegistry` label,
+    /// so calling this again on a config change would leak a detached
+    /// task rather than replace the old one.
     pub(crate) fn run_e2e<'a>(&'a self, faucets: &mut Faucets<'a>) {
         let (process_sinks, process_faucets) = faucets.swap_all_processes();
         let (dispatch_sinks, dispatch_faucets) = faucets.swap_all_dispatches();
@@ -218,7 +310,9 @@ impl Monitor {
         .run_until_panic();
     }
 
-    /// take ownership of all faucets and terminate them
+    /// take ownership of all faucets and terminate them. Consumes
+    /// `faucets` outright, so it can only run once per build -- not
+    /// something [`Self::rebuild_pipeline`] can call on every reconcile.
     pub(crate) fn run_terminals<'a>(&'a self, faucets: Faucets<'a>) -> Vec<JoinHandle<()>> {
         let mut tasks = vec![];
 
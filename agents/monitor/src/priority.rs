@@ -0,0 +1,233 @@
+//! Priority-ordered draining for pipeline channels, as an alternative to
+//! the hardcoded `select! { biased; ... }` dispatch-before-update
+//! ordering in steps like [`crate::dispatch_wait::DispatchWait`]. Each
+//! in-flight event carries an [`OrderTag`] (which logical stream it
+//! belongs to, and its sequence number within that stream) plus a coarse
+//! [`Priority`]; a [`PriorityQueue`] drains higher-priority events first
+//! while still never reordering two events that share a stream, since
+//! within one stream sequence order usually encodes a real dependency
+//! (e.g. one update committing a dispatch that was queued before it).
+//!
+//! This module is self-contained: wiring it into the concrete
+//! `DispatchFaucet`/`DispatchSink`/`UpdateFaucet`/`UpdateSink` channel
+//! types (defined wherever this crate's still-missing root module
+//! aliases them) is left to the call site, since those concrete types
+//! aren't present in this checkout to extend directly.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Which logical stream an event belongs to, and its position within
+/// that stream. Two events with the same `stream_id` are never drained
+/// out of order relative to each other, regardless of priority.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct OrderTag {
+    pub(crate) stream_id: String,
+    pub(crate) seq: u64,
+}
+
+/// A coarse drain priority. Ordered so that [`Priority::High`] sorts
+/// before [`Priority::Normal`] in a max-heap context (see
+/// [`PriorityQueue::pop`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum Priority {
+    Normal,
+    High,
+}
+
+/// Something that can be drained through a [`PriorityQueue`].
+pub(crate) trait Prioritized {
+    fn order_tag(&self) -> OrderTag;
+    fn priority(&self) -> Priority;
+}
+
+/// A priority structure over per-stream FIFO queues: a small set of
+/// `VecDeque`s (one per priority level in use) holding ready items, plus
+/// a per-stream "next expected sequence" map so an item is only
+/// considered ready once every earlier-`seq` item from its own stream
+/// has already drained.
+pub(crate) struct PriorityQueue<T> {
+    /// Items ready to drain, grouped by priority. A `BinaryHeap` keyed
+    /// on `(Reverse(priority), Reverse(insertion_order))` would also
+    /// work, but per-priority FIFO queues make the "never reorders
+    /// within a stream" property easy to see directly: a stream's items
+    /// are pushed to their priority's queue in submission order and
+    /// never skip ahead of each other.
+    ready: HashMap<Priority, VecDeque<(OrderTag, T)>>,
+    /// Items from a stream whose `seq` arrived out of order, held until
+    /// the stream catches up to them.
+    pending: HashMap<String, BinaryHeap<Reverse<PendingItem<T>>>>,
+    /// The next `seq` each stream is expected to produce.
+    next_seq: HashMap<String, u64>,
+    /// The next `seq` each stream is expected to drain via [`Self::pop`].
+    drained_seq: HashMap<String, u64>,
+}
+
+struct PendingItem<T> {
+    seq: u64,
+    priority: Priority,
+    item: T,
+}
+
+/// Manual impl so `T` needn't be `Debug` itself -- `DispatchWait` queues
+/// boxed closures here, which aren't. Prints structural sizes only.
+impl<T> std::fmt::Debug for PriorityQueue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PriorityQueue")
+            .field("ready_len", &self.ready.values().map(VecDeque::len).sum::<usize>())
+            .field("pending_len", &self.pending.values().map(BinaryHeap::len).sum::<usize>())
+            .finish()
+    }
+}
+
+impl<T> PartialEq for PendingItem<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+impl<T> Eq for PendingItem<T> {}
+impl<T> PartialOrd for PendingItem<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T> Ord for PendingItem<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.seq.cmp(&other.seq)
+    }
+}
+
+impl<T> PriorityQueue<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            ready: HashMap::new(),
+            pending: HashMap::new(),
+            next_seq: HashMap::new(),
+            drained_seq: HashMap::new(),
+        }
+    }
+
+    /// Admit `item`. If it's the next expected `seq` for its stream it
+    /// (and any now-contiguous items that were waiting behind it) become
+    /// ready to drain; otherwise it's held until the stream catches up.
+    pub(crate) fn push(&mut self, tag: OrderTag, priority: Priority, item: T) {
+        let expected = *self.next_seq.get(&tag.stream_id).unwrap_or(&0);
+
+        if tag.seq != expected {
+            self.pending.entry(tag.stream_id.clone()).or_default().push(Reverse(PendingItem {
+                seq: tag.seq,
+                priority,
+                item,
+            }));
+            return;
+        }
+
+        self.make_ready(tag.stream_id.clone(), tag.seq, priority, item);
+
+        // Pull in whatever's now contiguous from this stream's pending heap.
+        let mut next = expected + 1;
+        while let Some(heap) = self.pending.get_mut(&tag.stream_id) {
+            match heap.peek() {
+                Some(Reverse(candidate)) if candidate.seq == next => {
+                    let Reverse(candidate) = heap.pop().expect("checked by peek above");
+                    self.make_ready(tag.stream_id.clone(), candidate.seq, candidate.priority, candidate.item);
+                    next += 1;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn make_ready(&mut self, stream_id: String, seq: u64, priority: Priority, item: T) {
+        self.ready
+            .entry(priority)
+            .or_default()
+            .push_back((OrderTag { stream_id: stream_id.clone(), seq }, item));
+        self.next_seq.insert(stream_id, seq + 1);
+    }
+
+    /// Pop the next item in priority order (`High` before `Normal`),
+    /// FIFO within a priority level. An item is skipped while an earlier
+    /// `seq` of its own stream is still queued at another priority.
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        for priority in [Priority::High, Priority::Normal] {
+            if let Some(queue) = self.ready.get_mut(&priority) {
+                let drained = &self.drained_seq;
+                let head = queue
+                    .iter()
+                    .position(|(tag, _)| tag.seq == *drained.get(&tag.stream_id).unwrap_or(&0));
+                if let Some((tag, item)) = head.and_then(|idx| queue.remove(idx)) {
+                    self.drained_seq.insert(tag.stream_id, tag.seq + 1);
+                    return Some(item);
+                }
+            }
+        }
+        None
+    }
+
+    /// Total items held, ready or pending reassembly.
+    pub(crate) fn len(&self) -> usize {
+        let ready: usize = self.ready.values().map(VecDeque::len).sum();
+        let pending: usize = self.pending.values().map(BinaryHeap::len).sum();
+        ready + pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tag(stream: &str, seq: u64) -> OrderTag {
+        OrderTag {
+            stream_id: stream.to_owned(),
+            seq,
+        }
+    }
+
+    #[test]
+    fn high_priority_drains_before_normal() {
+        let mut q = PriorityQueue::new();
+        q.push(tag("a", 0), Priority::Normal, "normal");
+        q.push(tag("b", 0), Priority::High, "high");
+
+        assert_eq!(q.pop(), Some("high"));
+        assert_eq!(q.pop(), Some("normal"));
+    }
+
+    #[test]
+    fn same_stream_never_reorders_even_across_priority_changes() {
+        let mut q = PriorityQueue::new();
+        q.push(tag("a", 0), Priority::Normal, 0);
+        q.push(tag("a", 1), Priority::High, 1);
+        q.push(tag("a", 2), Priority::Normal, 2);
+
+        // Despite item 1 being High priority, it still comes out after
+        // item 0 because they share a stream.
+        assert_eq!(q.pop(), Some(0));
+        assert_eq!(q.pop(), Some(1));
+        assert_eq!(q.pop(), Some(2));
+    }
+
+    #[test]
+    fn out_of_order_arrival_is_held_until_contiguous() {
+        let mut q = PriorityQueue::new();
+        q.push(tag("a", 1), Priority::Normal, 1);
+        assert_eq!(q.len(), 1);
+        assert_eq!(q.pop(), None, "seq 1 can't be ready before seq 0 arrives");
+
+        q.push(tag("a", 0), Priority::Normal, 0);
+        assert_eq!(q.pop(), Some(0));
+        assert_eq!(q.pop(), Some(1));
+    }
+
+    #[test]
+    fn independent_streams_dont_block_each_other() {
+        let mut q = PriorityQueue::new();
+        q.push(tag("a", 1), Priority::Normal, "a1");
+        q.push(tag("b", 0), Priority::Normal, "b0");
+
+        // "a1" is stuck waiting on "a0", but "b0" has no such dependency.
+        assert_eq!(q.pop(), Some("b0"));
+        assert_eq!(q.pop(), None);
+    }
+}
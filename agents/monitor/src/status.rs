@@ -0,0 +1,77 @@
+//! Push-based health snapshots for [`crate::ProcessStep`] tasks, as an
+//! alternative to scraping the Prometheus histograms/counters each step
+//! already exposes. A caller registers a `(Duration, Sender<StepStatus>)`
+//! pair with a step's [`StatusSinks`]; the step's own loop periodically
+//! pushes a fresh [`StepStatus`] to every subscriber whose interval has
+//! elapsed, skipping one whose previous send is still unconsumed rather
+//! than blocking the step on a slow dashboard.
+
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::Sender;
+
+/// A point-in-time health snapshot of a `ProcessStep`, e.g.
+/// [`crate::dispatch_wait::DispatchWait::status`].
+#[derive(Debug, Clone)]
+pub(crate) struct StepStatus {
+    pub(crate) network: String,
+    pub(crate) step: String,
+    /// How many items the step is currently holding, waiting for a
+    /// match (e.g. `DispatchWait`'s pending dispatches).
+    pub(crate) pending: usize,
+    /// The block number of the most recent item the step is currently
+    /// holding, or `None` while it holds nothing.
+    pub(crate) last_observed_block: Option<u64>,
+    /// How long it's been since the step last observed the event that
+    /// drains its pending set (e.g. an update, for `DispatchWait`), if
+    /// it's ever seen one.
+    pub(crate) since_last_drain: Option<Duration>,
+}
+
+struct Subscriber {
+    interval: Duration,
+    next_due: Instant,
+    sender: Sender<StepStatus>,
+}
+
+/// A step's set of registered status subscribers. Subscribers are
+/// expected to register a sender built with a small bounded channel
+/// (capacity 1 is typical) so a full channel -- meaning the subscriber
+/// hasn't drained its last snapshot -- is exactly the signal to skip it
+/// this tick instead of piling more snapshots into its queue.
+#[derive(Default)]
+pub(crate) struct StatusSinks {
+    subscribers: Vec<Subscriber>,
+}
+
+impl StatusSinks {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe `sender` to receive a [`StepStatus`] roughly every
+    /// `interval`; the first one goes out on the first call to
+    /// [`Self::push`] made once `interval` has elapsed.
+    pub(crate) fn register(&mut self, interval: Duration, sender: Sender<StepStatus>) {
+        self.subscribers.push(Subscriber {
+            interval,
+            next_due: Instant::now() + interval,
+            sender,
+        });
+    }
+
+    /// Push `status` to every subscriber whose interval has elapsed.
+    /// A subscriber whose channel is still full (previous send not yet
+    /// consumed) is skipped for this tick but stays registered -- it'll
+    /// be tried again next time its interval elapses.
+    pub(crate) fn push(&mut self, status: &StepStatus) {
+        let now = Instant::now();
+        for sub in self.subscribers.iter_mut() {
+            if now < sub.next_due {
+                continue;
+            }
+            sub.next_due = now + sub.interval;
+            let _ = sub.sender.try_send(status.clone());
+        }
+    }
+}
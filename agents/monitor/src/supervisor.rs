@@ -0,0 +1,312 @@
+//! Supervises long-running pipeline steps so a transient RPC failure or a
+//! panic inside one step restarts just that step instead of taking the
+//! whole agent down, which is what `run_until_panic()` does today.
+//!
+//! Each worker tracked by [`TaskRegistry`] carries a [`WorkerState`]
+//! (`Running`/`Throttled`/`Errored`/`Done`), a restart count, and its
+//! most recent error, all readable through [`TaskRegistry::status`]
+//! without needing to scrape logs -- and [`TaskRegistry::shutdown`]
+//! gives a single coordinated stop-and-join across every registered
+//! worker.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use rand::Rng;
+use tokio::task::JoinHandle;
+
+/// Exponential backoff parameters for [`TaskRegistry::run_supervised`].
+/// Delay doubles on each consecutive restart, capped at `max`, with up to
+/// `jitter` fraction of the delay added at random to avoid every worker
+/// reconnecting to a recovering RPC endpoint in lockstep.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RestartPolicy {
+    pub(crate) base: Duration,
+    pub(crate) max: Duration,
+    pub(crate) jitter: f64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RestartPolicy {
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let exp = self.base.as_secs_f64() * 2f64.powi(attempt as i32);
+        let capped = exp.min(self.max.as_secs_f64());
+        let jitter = capped * self.jitter * rand::thread_rng().gen::<f64>();
+        Duration::from_secs_f64(capped + jitter)
+    }
+}
+
+/// Identifies a supervised task for registration, logging, and restart
+/// counters: the network it's running against, the pipeline step name
+/// (e.g. `"dispatch_producer"`), and -- for per-replica steps -- which
+/// replica it belongs to.
+pub(crate) type TaskLabel = (String, String, Option<String>);
+
+/// A worker's current lifecycle phase, as tracked by [`TaskHandle`]/
+/// [`TaskRegistry`] and surfaced via [`TaskRegistry::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WorkerState {
+    /// An attempt is currently executing.
+    Running,
+    /// The most recent attempt failed and this worker is sleeping out
+    /// [`RestartPolicy`]'s backoff before the next attempt starts.
+    Throttled,
+    /// The most recent attempt just failed; set momentarily before the
+    /// worker moves to `Throttled` to sleep out its backoff. Visible to
+    /// a racing status read right as a failure is logged.
+    Errored,
+    /// The worker's factory returned `Ok(())` -- a clean, intentional
+    /// exit -- and it will not be restarted.
+    Done,
+}
+
+/// A point-in-time snapshot of one supervised worker, returned by
+/// [`TaskRegistry::status`].
+#[derive(Debug, Clone)]
+pub(crate) struct WorkerStatus {
+    pub(crate) state: WorkerState,
+    pub(crate) restart_count: u32,
+    pub(crate) last_error: Option<String>,
+}
+
+/// Shared, mutable state a running [`run_supervised`] loop updates and a
+/// [`TaskHandle`] reads back for [`WorkerStatus`].
+#[derive(Debug)]
+struct SharedState {
+    state: Mutex<WorkerState>,
+    restart_count: AtomicU32,
+    last_error: Mutex<Option<String>>,
+}
+
+impl Default for SharedState {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(WorkerState::Running),
+            restart_count: AtomicU32::new(0),
+            last_error: Mutex::new(None),
+        }
+    }
+}
+
+/// A supervised task: the outer `tokio` task that owns the
+/// restart-on-failure loop, plus an atomic flag that tells it to stop
+/// restarting and exit instead.
+#[derive(Debug)]
+pub(crate) struct TaskHandle {
+    join: JoinHandle<()>,
+    stop: Arc<AtomicBool>,
+    shared: Arc<SharedState>,
+}
+
+impl TaskHandle {
+    fn tracking(join: JoinHandle<()>) -> Self {
+        Self {
+            join,
+            stop: Arc::new(AtomicBool::new(false)),
+            shared: Arc::new(SharedState::default()),
+        }
+    }
+
+    /// Stop this task: sets the cooperative stop flag so a supervised
+    /// loop won't restart again, and aborts the outer `tokio` task
+    /// directly. Note this can't interrupt a restart attempt already in
+    /// flight -- only the next restart check and the outer task itself.
+    pub(crate) fn stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+        self.join.abort();
+    }
+
+    /// Await the supervised task's own outer loop (not an individual
+    /// restart attempt).
+    pub(crate) async fn join(self) {
+        let _ = self.join.await;
+    }
+
+    /// A snapshot of this worker's current lifecycle state, restart
+    /// count, and most recent error (if any).
+    pub(crate) fn status(&self) -> WorkerStatus {
+        WorkerStatus {
+            state: *self.shared.state.lock().expect("worker state lock poisoned"),
+            restart_count: self.shared.restart_count.load(Ordering::SeqCst),
+            last_error: self.shared.last_error.lock().expect("worker state lock poisoned").clone(),
+        }
+    }
+}
+
+/// Run `factory` under supervision: each call to `factory` produces one
+/// attempt's `JoinHandle`, and whenever that attempt's task ends
+/// (panics, or returns after a broken channel), the step is restarted
+/// after [`RestartPolicy`] backoff, calling `on_restart` first so a
+/// restart counter can be incremented. Returns immediately; stops only
+/// when [`TaskHandle::stop`] is called or `factory`'s task exits
+/// `Ok(())` (a clean, intentional shutdown of the step itself).
+pub(crate) fn run_supervised<F>(
+    label: TaskLabel,
+    policy: RestartPolicy,
+    on_restart: Arc<dyn Fn(&TaskLabel) + Send + Sync>,
+    mut factory: F,
+) -> TaskHandle
+where
+    F: FnMut() -> JoinHandle<()> + Send + 'static,
+{
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_clone = stop.clone();
+    let shared = Arc::new(SharedState::default());
+    let shared_clone = shared.clone();
+
+    let join = tokio::spawn(async move {
+        let mut attempt: u32 = 0;
+        loop {
+            if stop_clone.load(Ordering::SeqCst) {
+                return;
+            }
+
+            *shared_clone.state.lock().expect("worker state lock poisoned") = WorkerState::Running;
+            let attempt_handle = factory();
+            let result = attempt_handle.await;
+
+            if stop_clone.load(Ordering::SeqCst) {
+                return;
+            }
+
+            match result {
+                Ok(()) => {
+                    tracing::debug!(
+                        network = label.0.as_str(),
+                        step = label.1.as_str(),
+                        "supervised step exited cleanly; not restarting"
+                    );
+                    *shared_clone.state.lock().expect("worker state lock poisoned") = WorkerState::Done;
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        network = label.0.as_str(),
+                        step = label.1.as_str(),
+                        replica_of = label.2.as_deref().unwrap_or(""),
+                        error = %e,
+                        attempt,
+                        "supervised step failed; restarting after backoff"
+                    );
+                    *shared_clone.state.lock().expect("worker state lock poisoned") = WorkerState::Errored;
+                    *shared_clone.last_error.lock().expect("worker state lock poisoned") = Some(e.to_string());
+                    shared_clone.restart_count.fetch_add(1, Ordering::SeqCst);
+                    on_restart(&label);
+
+                    *shared_clone.state.lock().expect("worker state lock poisoned") = WorkerState::Throttled;
+                    let delay = policy.delay_for(attempt);
+                    attempt = attempt.saturating_add(1);
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    });
+
+    TaskHandle { join, stop, shared }
+}
+
+/// Central map of every supervised task currently running, keyed by
+/// `(network, step, replica_of)`, so individual workers can be stopped
+/// and respawned and a coordinated shutdown can join every handle.
+#[derive(Debug, Default)]
+pub(crate) struct TaskRegistry {
+    tasks: Mutex<HashMap<TaskLabel, TaskHandle>>,
+}
+
+impl TaskRegistry {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start `factory` under supervision and register the resulting
+    /// handle under `label`, replacing (and stopping) any existing task
+    /// already registered under that label.
+    pub(crate) fn run_supervised<F>(
+        &self,
+        label: TaskLabel,
+        policy: RestartPolicy,
+        on_restart: Arc<dyn Fn(&TaskLabel) + Send + Sync>,
+        factory: F,
+    ) where
+        F: FnMut() -> JoinHandle<()> + Send + 'static,
+    {
+        let handle = run_supervised(label.clone(), policy, on_restart, factory);
+        if let Some(previous) = self
+            .tasks
+            .lock()
+            .expect("task registry lock poisoned")
+            .insert(label, handle)
+        {
+            previous.stop();
+        }
+    }
+
+    /// Register an already-spawned task's handle under `label` for
+    /// coordinated shutdown, without restart-on-failure supervision. Used
+    /// for steps that consume a channel receiver they can't reconstruct
+    /// on restart (e.g. [`crate::steps::between::BetweenEvents`]) -- if
+    /// such a step dies, its upstream pipe is gone anyway, so restarting
+    /// just this step wouldn't recover it.
+    pub(crate) fn track(&self, label: TaskLabel, join: JoinHandle<()>) {
+        let handle = TaskHandle::tracking(join);
+        if let Some(previous) = self
+            .tasks
+            .lock()
+            .expect("task registry lock poisoned")
+            .insert(label, handle)
+        {
+            previous.stop();
+        }
+    }
+
+    /// A snapshot of the worker registered under `label`, if any --
+    /// its lifecycle state, restart count, and most recent error.
+    pub(crate) fn status(&self, label: &TaskLabel) -> Option<WorkerStatus> {
+        self.tasks
+            .lock()
+            .expect("task registry lock poisoned")
+            .get(label)
+            .map(TaskHandle::status)
+    }
+
+    /// Stop and drop the task registered under `label`, if any.
+    pub(crate) fn stop(&self, label: &TaskLabel) {
+        if let Some(handle) = self.tasks.lock().expect("task registry lock poisoned").remove(label) {
+            handle.stop();
+        }
+    }
+
+    /// Signal every registered task to stop and join them all, for a
+    /// coordinated shutdown.
+    pub(crate) async fn shutdown(&self) {
+        let handles: Vec<TaskHandle> = self
+            .tasks
+            .lock()
+            .expect("task registry lock poisoned")
+            .drain()
+            .map(|(_, handle)| handle)
+            .collect();
+
+        for handle in &handles {
+            handle.stop();
+        }
+        for handle in handles {
+            handle.join().await;
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Mirrors every event flowing through a faucet to any number of
+//! external subscribers, in addition to the single primary pipeline
+//! consumer a [`crate::credit_channel::CreditedReceiver`] already feeds.
+//! An operator can tap the live event stream for an exporter, webhook,
+//! or a second analysis step without rewiring the core pipeline --
+//! the same multi-consumer subscription model a p2p swarm stack exposes
+//! for its sync events, applied here to `Dispatch`/`Update`/`Relay`/
+//! `Process` events.
+//!
+//! Each subscriber gets its own bounded ring buffer
+//! ([`tokio::sync::broadcast`]); a subscriber that falls behind drops
+//! the oldest events rather than blocking the pipeline, and every drop
+//! is counted in `faucet_tee_dropped_total{network,stage}` so a slow
+//! subscriber is visible instead of just silently missing events.
+//!
+//! [`SubscriptionRegistry`] is where a [`crate::domain::Domain`] keeps the
+//! [`FaucetTee`] each of its producer stages registers, so a caller can
+//! later ask for `("dispatch_producer", None)` or
+//! `("relay_producer", Some("ethereum"))` without the domain needing a
+//! dedicated field (and type parameter) per stage. The event type varies
+//! per stage, so entries are type-erased and downcast back on subscribe.
+
+use std::{any::Any, collections::HashMap, sync::Mutex};
+
+use prometheus::IntCounter;
+use tokio::sync::broadcast;
+
+use crate::credit_channel::{credited_channel, CreditedReceiver, FaucetMetrics};
+
+/// Default per-subscriber ring buffer size for [`tee`].
+pub(crate) const SUBSCRIPTION_BUFFER: usize = 256;
+
+/// A handle subscribers use to register for a faucet's mirrored event
+/// stream.
+#[derive(Debug, Clone)]
+pub(crate) struct FaucetTee<T> {
+    subscribers: broadcast::Sender<T>,
+    dropped: IntCounter,
+}
+
+impl<T: Clone> FaucetTee<T> {
+    fn new(buffer: usize, dropped: IntCounter) -> Self {
+        let (subscribers, _) = broadcast::channel(buffer);
+        Self {
+            subscribers,
+            dropped,
+        }
+    }
+
+    /// Register a new subscriber. Independent of any other subscriber's
+    /// pace -- a slow one only drops its own oldest events, tracked via
+    /// `dropped`.
+    pub(crate) fn subscribe(&self) -> TeeSubscription<T> {
+        TeeSubscription {
+            inner: self.subscribers.subscribe(),
+            dropped: self.dropped.clone(),
+        }
+    }
+
+    fn mirror(&self, item: &T) {
+        // Err here only means there are currently no subscribers; that's
+        // not a drop, just nobody listening.
+        let _ = self.subscribers.send(item.clone());
+    }
+}
+
+/// A single subscriber's view onto a [`FaucetTee`]'s mirrored stream.
+#[derive(Debug)]
+pub(crate) struct TeeSubscription<T> {
+    inner: broadcast::Receiver<T>,
+    dropped: IntCounter,
+}
+
+impl<T: Clone> TeeSubscription<T> {
+    /// Receive the next mirrored event, transparently skipping past any
+    /// events this subscriber fell too far behind to see (each one is
+    /// counted in `faucet_tee_dropped_total`).
+    pub(crate) async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.inner.recv().await {
+                Ok(item) => return Some(item),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    self.dropped.inc_by(missed);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Wrap `inner` so every event it yields is also mirrored to a
+/// [`FaucetTee`], while the primary pipeline consumer keeps receiving
+/// every event on the returned [`CreditedReceiver`] exactly as before.
+pub(crate) fn tee<T>(
+    mut inner: CreditedReceiver<T>,
+    primary_metrics: FaucetMetrics,
+    primary_capacity: usize,
+    tee_buffer: usize,
+    dropped: IntCounter,
+) -> (CreditedReceiver<T>, FaucetTee<T>)
+where
+    T: Clone + Send + 'static,
+{
+    let tee_handle = FaucetTee::new(tee_buffer, dropped);
+    let (primary_tx, primary_rx) = credited_channel(primary_capacity, primary_metrics);
+
+    let mirror_handle = tee_handle.clone();
+    tokio::spawn(async move {
+        while let Some(item) = inner.recv().await {
+            mirror_handle.mirror(&item);
+            if primary_tx.send(item).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    (primary_rx, tee_handle)
+}
+
+/// Holds one [`FaucetTee`] per `(stage, replica_of)` a domain has stood
+/// up, e.g. `("dispatch_producer", None)` or
+/// `("relay_producer", Some("ethereum"))`. Type-erased because each
+/// stage mirrors a different event type.
+#[derive(Default)]
+pub(crate) struct SubscriptionRegistry {
+    tees: Mutex<HashMap<(String, Option<String>), Box<dyn Any + Send + Sync>>>,
+}
+
+impl std::fmt::Debug for SubscriptionRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionRegistry").finish_non_exhaustive()
+    }
+}
+
+impl SubscriptionRegistry {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the tee a stage just stood up, replacing any previous
+    /// one registered under the same key.
+    pub(crate) fn insert<T: Send + Sync + 'static>(
+        &self,
+        stage: &str,
+        replica_of: Option<&str>,
+        tee: FaucetTee<T>,
+    ) {
+        self.tees
+            .lock()
+            .expect("subscription registry lock poisoned")
+            .insert((stage.to_owned(), replica_of.map(str::to_owned)), Box::new(tee));
+    }
+
+    /// Subscribe to the mirrored stream for `stage`/`replica_of`.
+    /// Returns `None` if that stage hasn't registered a tee, or if `T`
+    /// doesn't match the event type it mirrors.
+    pub(crate) fn subscribe<T: Clone + Send + Sync + 'static>(
+        &self,
+        stage: &str,
+        replica_of: Option<&str>,
+    ) -> Option<TeeSubscription<T>> {
+        let key = (stage.to_owned(), replica_of.map(str::to_owned));
+        self.tees
+            .lock()
+            .expect("subscription registry lock poisoned")
+            .get(&key)
+            .and_then(|boxed| boxed.downcast_ref::<FaucetTee<T>>())
+            .map(FaucetTee::subscribe)
+    }
+}
@@ -0,0 +1,143 @@
+//! A small proportional-pacing helper for [`crate::ProcessStep`] tasks
+//! like [`crate::dispatch_wait::DispatchWait`]. A fast upstream (a home
+//! emitting dispatches) can otherwise burst far ahead of whatever a
+//! slower downstream step can keep up with; rather than hardcoding a
+//! fixed delay between sends, a [`Tranquilizer`] measures how long the
+//! surrounding work actually took and inserts a sleep proportional to
+//! that measurement, so the pacing adapts as the downstream gets faster
+//! or slower instead of needing to be retuned by hand.
+
+use std::time::{Duration, Instant};
+
+use prometheus::Gauge;
+
+/// Tuning knobs for a [`Tranquilizer`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TranquilizerConfig {
+    /// The fraction of wall-clock time the measured work should occupy;
+    /// the rest is spent sleeping. `0.5` means "spend as long sleeping
+    /// as working."
+    pub(crate) target_utilization: f64,
+    /// Upper bound on any single inserted sleep, so a brief spike in
+    /// processing time can't stall the loop for an unreasonable stretch.
+    pub(crate) max_sleep: Duration,
+    /// Smoothing factor for the per-item exponential moving average, in
+    /// `(0.0, 1.0]` -- higher reacts faster to recent measurements,
+    /// lower rides out noise.
+    pub(crate) ema_alpha: f64,
+}
+
+impl Default for TranquilizerConfig {
+    fn default() -> Self {
+        Self {
+            target_utilization: 0.5,
+            max_sleep: Duration::from_secs(5),
+            ema_alpha: 0.2,
+        }
+    }
+}
+
+/// Paces a loop by sleeping proportionally to how long its own work is
+/// taking. Call [`Tranquilizer::tranquilize_start`] before the work to
+/// measure, then [`Tranquilizer::tranquilize_done`] with how many items
+/// that work covered; the latter awaits the inserted sleep itself.
+#[derive(Debug)]
+pub(crate) struct Tranquilizer {
+    config: TranquilizerConfig,
+    ema_seconds_per_item: Option<f64>,
+    sleep_budget: Gauge,
+}
+
+impl Tranquilizer {
+    pub(crate) fn new(config: TranquilizerConfig, sleep_budget: Gauge) -> Self {
+        Self {
+            config,
+            ema_seconds_per_item: None,
+            sleep_budget,
+        }
+    }
+
+    /// Marks the start of a measured batch of work.
+    pub(crate) fn tranquilize_start(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Folds the batch that ran from `start` to now (covering `n_items`
+    /// items) into the moving average, then sleeps for the resulting
+    /// budget. A zero-item batch updates nothing and returns immediately
+    /// -- there's no per-item rate to learn from it.
+    pub(crate) async fn tranquilize_done(&mut self, start: Instant, n_items: u64) {
+        let sleep = self.record(start.elapsed(), n_items);
+        if sleep > Duration::ZERO {
+            tokio::time::sleep(sleep).await;
+        }
+    }
+
+    /// The synchronous half of [`Self::tranquilize_done`]: updates the
+    /// moving average and the `sleep_budget` gauge, returning the sleep
+    /// duration without awaiting it. Split out so the arithmetic can be
+    /// tested without a runtime, and so callers that can't await inline
+    /// (e.g. a queued, synchronously-invoked closure) can still apply
+    /// the computed sleep themselves afterward.
+    pub(crate) fn record(&mut self, elapsed: Duration, n_items: u64) -> Duration {
+        if n_items == 0 {
+            return Duration::ZERO;
+        }
+
+        let per_item = elapsed.as_secs_f64() / n_items as f64;
+        let ema = match self.ema_seconds_per_item {
+            Some(prev) => self.config.ema_alpha * per_item + (1.0 - self.config.ema_alpha) * prev,
+            None => per_item,
+        };
+        self.ema_seconds_per_item = Some(ema);
+
+        let budget = ema * n_items as f64 * (1.0 / self.config.target_utilization - 1.0);
+        let budget = budget.max(0.0).min(self.config.max_sleep.as_secs_f64());
+        self.sleep_budget.set(budget);
+        Duration::from_secs_f64(budget)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gauge() -> Gauge {
+        Gauge::new("test_tranquilizer_sleep_budget", "test").unwrap()
+    }
+
+    #[test]
+    fn sleeps_proportionally_to_measured_work() {
+        let config = TranquilizerConfig {
+            target_utilization: 0.5,
+            max_sleep: Duration::from_secs(5),
+            ema_alpha: 1.0,
+        };
+        let mut t = Tranquilizer::new(config, gauge());
+
+        // 1 item took 100ms; at 50% target utilization we should sleep
+        // roughly as long as we worked.
+        let sleep = t.record(Duration::from_millis(100), 1);
+        assert!((sleep.as_secs_f64() - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn zero_items_is_a_no_op() {
+        let mut t = Tranquilizer::new(TranquilizerConfig::default(), gauge());
+        assert_eq!(t.record(Duration::from_millis(100), 0), Duration::ZERO);
+        assert!(t.ema_seconds_per_item.is_none());
+    }
+
+    #[test]
+    fn sleep_is_capped_at_max_sleep() {
+        let config = TranquilizerConfig {
+            target_utilization: 0.01,
+            max_sleep: Duration::from_millis(50),
+            ema_alpha: 1.0,
+        };
+        let mut t = Tranquilizer::new(config, gauge());
+
+        let sleep = t.record(Duration::from_secs(10), 1);
+        assert_eq!(sleep, Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,309 @@
+//! Bridges the stall signals the monitor already computes (today:
+//! [`crate::health::HealthMonitor`]'s chain-liveness check) to a pod
+//! restart, so a detected stall actually triggers remediation instead of
+//! sitting in [`crate::health::ReadinessGate`]/a dashboard that pages a
+//! human instead of a process.
+//!
+//! [`PodRestarter`] decouples this from `tools/lifeguard::K8S`
+//! concretely: this checkout has no workspace `Cargo.toml` linking the
+//! `monitor` and `lifeguard` crates together, so there's nothing for a
+//! dependency on `tools/lifeguard` to resolve against. A
+//! `K8sPodRestarter` adapter delegating to `K8S::try_delete_pod` --
+//! respecting its own `check_start_time`/`check_backoff` gates -- is the
+//! follow-up once that dependency edge exists; [`Watchdog`] is the
+//! state machine + escalation policy it would plug into.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex;
+
+use crate::alert::{AlertSink, IncidentKey};
+
+/// Identifies one supervised lane: a network plus which pod is
+/// responsible for keeping it live (mirrors `tools/lifeguard::LifeguardPod`'s
+/// `network`/`agent` pair).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct LaneId {
+    pub(crate) network: String,
+    pub(crate) agent: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LaneState {
+    Healthy,
+    Suspected,
+    Cooldown,
+}
+
+/// Issues the actual remediation for a stalled lane. Implemented by an
+/// adapter over `tools/lifeguard::K8S` once the two crates are linked;
+/// see the module doc for why that adapter isn't here yet.
+#[async_trait::async_trait]
+pub(crate) trait PodRestarter: Send + Sync {
+    async fn restart(&self, lane: &LaneId) -> Result<(), String>;
+}
+
+/// Tuning for [`Watchdog`]'s Healthy -> Suspected -> Cooldown state
+/// machine.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WatchdogConfig {
+    /// How long a lane must stay reported-stalled before a restart is
+    /// actually issued -- avoids restarting on a single noisy poll.
+    pub(crate) confirm_delay: Duration,
+    /// How long to wait after issuing a restart before considering a
+    /// still-stalled lane for another one.
+    pub(crate) cooldown: Duration,
+    /// Restart attempts allowed (since the lane was last seen healthy)
+    /// before giving up and escalating to the alert sink instead.
+    pub(crate) max_attempts: u32,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            confirm_delay: Duration::from_secs(60),
+            cooldown: Duration::from_secs(5 * 60),
+            max_attempts: 5,
+        }
+    }
+}
+
+struct LaneRecord {
+    state: LaneState,
+    since: Instant,
+    attempts: u32,
+}
+
+impl LaneRecord {
+    fn healthy() -> Self {
+        Self {
+            state: LaneState::Healthy,
+            since: Instant::now(),
+            attempts: 0,
+        }
+    }
+}
+
+/// Watches lanes reported via [`Watchdog::observe`] and restarts the pod
+/// responsible once a stall is confirmed, escalating to `alerts` instead
+/// of restarting forever once `max_attempts` is exceeded.
+pub(crate) struct Watchdog {
+    restarter: Arc<dyn PodRestarter>,
+    alerts: Arc<AlertSink>,
+    config: WatchdogConfig,
+    lanes: Mutex<HashMap<LaneId, LaneRecord>>,
+}
+
+impl Watchdog {
+    pub(crate) fn new(
+        restarter: Arc<dyn PodRestarter>,
+        alerts: Arc<AlertSink>,
+        config: WatchdogConfig,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            restarter,
+            alerts,
+            config,
+            lanes: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Report this poll's liveness result for `lane`. Called once per
+    /// poll by whatever already computes it -- today,
+    /// [`crate::health::HealthMonitor::poll_once`].
+    pub(crate) async fn observe(&self, lane: LaneId, stalled: bool) {
+        let mut lanes = self.lanes.lock().await;
+        let record = lanes.entry(lane.clone()).or_insert_with(LaneRecord::healthy);
+
+        if !stalled {
+            if !matches!(record.state, LaneState::Healthy) {
+                tracing::info!(network = lane.network.as_str(), agent = lane.agent.as_str(), "lane recovered");
+            }
+            *record = LaneRecord::healthy();
+            return;
+        }
+
+        match record.state {
+            LaneState::Healthy => {
+                record.state = LaneState::Suspected;
+                record.since = Instant::now();
+                tracing::warn!(network = lane.network.as_str(), agent = lane.agent.as_str(), "lane suspected stalled");
+            }
+            LaneState::Suspected if record.since.elapsed() >= self.config.confirm_delay => {
+                if record.attempts >= self.config.max_attempts {
+                    drop(lanes);
+                    self.alerts
+                        .fire(
+                            IncidentKey {
+                                network: lane.network.clone(),
+                                event: "watchdog_exhausted",
+                                replica_of: Some(lane.agent.clone()),
+                            },
+                            format!(
+                                "{}'s {} exceeded {} restart attempts; giving up",
+                                lane.network, lane.agent, self.config.max_attempts
+                            ),
+                        )
+                        .await;
+                    return;
+                }
+                record.attempts += 1;
+                record.state = LaneState::Cooldown;
+                record.since = Instant::now();
+                let attempts = record.attempts;
+                drop(lanes);
+                self.restart(lane, attempts).await;
+            }
+            LaneState::Suspected => {
+                // still within confirm_delay; wait for another poll
+            }
+            LaneState::Cooldown if record.since.elapsed() >= self.config.cooldown => {
+                record.state = LaneState::Suspected;
+                record.since = Instant::now();
+            }
+            LaneState::Cooldown => {
+                // still cooling down from the last restart attempt
+            }
+        }
+    }
+
+    async fn restart(&self, lane: LaneId, attempt: u32) {
+        tracing::warn!(network = lane.network.as_str(), agent = lane.agent.as_str(), attempt, "restarting stalled pod");
+        match self.restarter.restart(&lane).await {
+            Ok(()) => {
+                tracing::info!(network = lane.network.as_str(), agent = lane.agent.as_str(), "pod restart issued")
+            }
+            Err(e) => {
+                tracing::error!(network = lane.network.as_str(), agent = lane.agent.as_str(), error = %e, "pod restart failed");
+                self.alerts
+                    .fire(
+                        IncidentKey {
+                            network: lane.network.clone(),
+                            event: "watchdog_restart_failed",
+                            replica_of: Some(lane.agent.clone()),
+                        },
+                        format!("restart of {}'s {} failed: {}", lane.network, lane.agent, e),
+                    )
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct CountingRestarter {
+        calls: AtomicU32,
+        fail: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl PodRestarter for CountingRestarter {
+        async fn restart(&self, _lane: &LaneId) -> Result<(), String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                Err("boom".to_owned())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn lane() -> LaneId {
+        LaneId {
+            network: "ethereum".to_owned(),
+            agent: "relayer".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_single_suspected_poll_does_not_restart_before_confirm_delay() {
+        let restarter = Arc::new(CountingRestarter {
+            calls: AtomicU32::new(0),
+            fail: false,
+        });
+        let alerts = AlertSink::new(crate::alert::AlertConfig {
+            webhook_url: "http://example.invalid".to_owned(),
+            routing_key: "key".to_owned(),
+            process_silence_threshold: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(30),
+        });
+        let config = WatchdogConfig {
+            confirm_delay: Duration::from_secs(3600),
+            ..WatchdogConfig::default()
+        };
+        let watchdog = Watchdog::new(restarter.clone(), alerts, config);
+
+        watchdog.observe(lane(), true).await;
+        assert_eq!(restarter.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn a_confirmed_stall_triggers_exactly_one_restart() {
+        let restarter = Arc::new(CountingRestarter {
+            calls: AtomicU32::new(0),
+            fail: false,
+        });
+        let alerts = AlertSink::new(crate::alert::AlertConfig {
+            webhook_url: "http://example.invalid".to_owned(),
+            routing_key: "key".to_owned(),
+            process_silence_threshold: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(30),
+        });
+        let config = WatchdogConfig {
+            confirm_delay: Duration::from_millis(1),
+            cooldown: Duration::from_secs(3600),
+            ..WatchdogConfig::default()
+        };
+        let watchdog = Watchdog::new(restarter.clone(), alerts, config);
+
+        watchdog.observe(lane(), true).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        watchdog.observe(lane(), true).await;
+        assert_eq!(restarter.calls.load(Ordering::SeqCst), 1);
+
+        // Still cooling down -- another stalled poll doesn't restart again.
+        watchdog.observe(lane(), true).await;
+        assert_eq!(restarter.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn recovery_resets_the_attempt_counter() {
+        let restarter = Arc::new(CountingRestarter {
+            calls: AtomicU32::new(0),
+            fail: false,
+        });
+        let alerts = AlertSink::new(crate::alert::AlertConfig {
+            webhook_url: "http://example.invalid".to_owned(),
+            routing_key: "key".to_owned(),
+            process_silence_threshold: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(30),
+        });
+        let config = WatchdogConfig {
+            confirm_delay: Duration::from_millis(1),
+            cooldown: Duration::from_millis(1),
+            max_attempts: 1,
+        };
+        let watchdog = Watchdog::new(restarter.clone(), alerts, config);
+
+        watchdog.observe(lane(), true).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        watchdog.observe(lane(), true).await;
+        assert_eq!(restarter.calls.load(Ordering::SeqCst), 1);
+
+        watchdog.observe(lane(), false).await;
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        watchdog.observe(lane(), true).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        watchdog.observe(lane(), true).await;
+        assert_eq!(restarter.calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,106 @@
+//! Durable, order-preserving archive of every signed update a domain's
+//! watch/sync tasks observe.
+//!
+//! `NomadDB::store_update` already persists the latest update for a given
+//! previous root, but that's keyed for point lookups (`update_by_previous_root`),
+//! not for replaying the full sequence an operator actually saw. This module
+//! gives every observed update its own durable, monotonically increasing
+//! index so the whole history can be walked back in order after the fact,
+//! independent of whatever `UpdateHandler` itself decided to do with it.
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use prometheus::IntGauge;
+use tokio::sync::{broadcast, watch};
+use tracing::info;
+
+use nomad_base::NomadDB;
+use nomad_core::SignedUpdate;
+
+use crate::supervisor::{SupervisedTask, WorkerState};
+use crate::watcher::{next_update, NextUpdate};
+
+const ARCHIVED_UPDATE: &str = "archived_update_";
+const ARCHIVED_UPDATE_COUNT: &str = "archived_update_count_";
+const ARCHIVED_UPDATE_COUNT_KEY: &str = "count";
+
+/// Subscribes to a domain's update stream and durably persists every update
+/// it sees under the next available index, so the full sequence observed
+/// survives both an agent restart and `UpdateHandler`'s own decisions about
+/// what to do with any given update.
+#[derive(Debug)]
+pub(crate) struct UpdateArchiver {
+    rx: broadcast::Receiver<SignedUpdate>,
+    db: NomadDB,
+    next_index: u64,
+    shutdown: watch::Receiver<bool>,
+    lagged_updates: IntGauge,
+}
+
+impl UpdateArchiver {
+    pub(crate) fn new(
+        rx: broadcast::Receiver<SignedUpdate>,
+        db: NomadDB,
+        shutdown: watch::Receiver<bool>,
+        lagged_updates: IntGauge,
+    ) -> Result<Self> {
+        let next_index = db
+            .retrieve_keyed_decodable::<_, _, u64>(ARCHIVED_UPDATE_COUNT, &ARCHIVED_UPDATE_COUNT_KEY.to_owned())?
+            .unwrap_or_default();
+
+        Ok(Self {
+            rx,
+            db,
+            next_index,
+            shutdown,
+            lagged_updates,
+        })
+    }
+
+    fn archive(&mut self, update: SignedUpdate) -> Result<()> {
+        let index = self.next_index;
+        self.db
+            .store_keyed_encodable(ARCHIVED_UPDATE, &index, &update)?;
+        self.next_index += 1;
+        self.db.store_keyed_encodable(
+            ARCHIVED_UPDATE_COUNT,
+            &ARCHIVED_UPDATE_COUNT_KEY.to_owned(),
+            &self.next_index,
+        )?;
+        Ok(())
+    }
+
+    async fn tick_inner(&mut self) -> Result<WorkerState> {
+        tokio::select! {
+            biased;
+            _ = self.shutdown.changed() => {
+                if !*self.shutdown.borrow() {
+                    return Ok(WorkerState::Idle);
+                }
+                Ok(WorkerState::Stopped)
+            }
+            update = next_update(&mut self.rx, "update_archiver", &self.lagged_updates) => {
+                match update {
+                    NextUpdate::Update(update) => {
+                        self.archive(update)?;
+                        Ok(WorkerState::Progressed)
+                    }
+                    NextUpdate::Closed => {
+                        info!("update archiver's stream closed, every producer has been dropped");
+                        Ok(WorkerState::Done)
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SupervisedTask for UpdateArchiver {
+    async fn tick(&mut self) -> WorkerState {
+        match self.tick_inner().await {
+            Ok(state) => state,
+            Err(e) => WorkerState::Errored(e),
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Durable, retrying queue of fraud-response submissions.
+//!
+//! `handle_double_update_failure`/`handle_improper_update_failure` used to
+//! fire every `double_update`/`unenroll_replica` future exactly once and
+//! just log the result -- if a chain's RPC was down for that one attempt,
+//! a definitively-detected double update or failure notification was lost
+//! forever. This module borrows the durable-job-row model background job
+//! processors like `fang`/`backie` use: each submission a detected fraud
+//! case requires is persisted to [`NomadDB`] as a [`FraudJob`] before
+//! anything is sent over the wire, and [`FraudJobWorker`] (itself just a
+//! [`SupervisedTask`], so it gets the same retry-with-backoff loop as
+//! `ContractWatcher`/`HistorySync`) drains the oldest outstanding job,
+//! retrying it until its `TxOutcome` is confirmed, and only then removes
+//! it. Jobs are reloaded from `NomadDB` on construction, so a fraud
+//! response outstanding when the agent restarts simply resumes.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::{eyre::eyre, Result};
+use ethers::core::types::{Signature, H256};
+use prometheus::IntGauge;
+use serde::{Deserialize, Serialize};
+use tokio::select;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use nomad_base::NomadDB;
+use nomad_core::{
+    DoubleUpdate, FailureNotification, NomadIdentifier, SignedFailureNotification, SignedUpdate,
+    TxOutcome, Update,
+};
+
+use crate::supervisor::{supervise, SupervisedTask, SupervisorMetrics, WorkerState};
+
+const FRAUD_JOB: &str = "fraud_job_";
+const FRAUD_JOB_INDEX: &str = "fraud_job_index_";
+const FRAUD_JOB_INDEX_KEY: &str = "ids";
+
+/// How long [`FraudJobWorker`] waits before checking an empty queue again.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Which contract or connection manager a [`FraudJob`] must land on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum FraudJobTarget {
+    /// The watched home itself.
+    Home,
+    /// The replica named here.
+    Replica(String),
+    /// The `ConnectionManager` at this index in `Watcher::connection_managers`.
+    ConnectionManager(usize),
+}
+
+impl std::fmt::Display for FraudJobTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FraudJobTarget::Home => write!(f, "home"),
+            FraudJobTarget::Replica(name) => write!(f, "replica:{}", name),
+            FraudJobTarget::ConnectionManager(idx) => write!(f, "connection_manager:{}", idx),
+        }
+    }
+}
+
+/// A JSON-serializable mirror of a [`SignedUpdate`] for durable storage.
+/// `SignedUpdate` itself only implements the binary `Encode`/`Decode`
+/// `NomadDB` uses for protocol data, not `serde`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredSignedUpdate {
+    home_domain: u32,
+    previous_root: H256,
+    new_root: H256,
+    signature: Vec<u8>,
+}
+
+impl From<&SignedUpdate> for StoredSignedUpdate {
+    fn from(update: &SignedUpdate) -> Self {
+        Self {
+            home_domain: update.update.home_domain,
+            previous_root: update.update.previous_root,
+            new_root: update.update.new_root,
+            signature: update.signature.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<StoredSignedUpdate> for SignedUpdate {
+    type Error = color_eyre::Report;
+
+    fn try_from(stored: StoredSignedUpdate) -> Result<Self> {
+        Ok(SignedUpdate {
+            update: Update {
+                home_domain: stored.home_domain,
+                previous_root: stored.previous_root,
+                new_root: stored.new_root,
+            },
+            signature: Signature::try_from(stored.signature.as_slice())
+                .map_err(|e| eyre!("stored fraud job has an invalid update signature: {}", e))?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredDoubleUpdate(StoredSignedUpdate, StoredSignedUpdate);
+
+impl From<&DoubleUpdate> for StoredDoubleUpdate {
+    fn from(double: &DoubleUpdate) -> Self {
+        Self((&double.0).into(), (&double.1).into())
+    }
+}
+
+impl TryFrom<StoredDoubleUpdate> for DoubleUpdate {
+    type Error = color_eyre::Report;
+
+    fn try_from(stored: StoredDoubleUpdate) -> Result<Self> {
+        Ok(DoubleUpdate(stored.0.try_into()?, stored.1.try_into()?))
+    }
+}
+
+/// A JSON-serializable mirror of a [`SignedFailureNotification`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredFailureNotification {
+    home_domain: u32,
+    updater: NomadIdentifier,
+    signature: Vec<u8>,
+}
+
+impl From<&SignedFailureNotification> for StoredFailureNotification {
+    fn from(notification: &SignedFailureNotification) -> Self {
+        Self {
+            home_domain: notification.notification.home_domain,
+            updater: notification.notification.updater,
+            signature: notification.signature.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<StoredFailureNotification> for SignedFailureNotification {
+    type Error = color_eyre::Report;
+
+    fn try_from(stored: StoredFailureNotification) -> Result<Self> {
+        Ok(SignedFailureNotification {
+            notification: FailureNotification {
+                home_domain: stored.home_domain,
+                updater: stored.updater,
+            },
+            signature: Signature::try_from(stored.signature.as_slice()).map_err(|e| {
+                eyre!(
+                    "stored fraud job has an invalid failure notification signature: {}",
+                    e
+                )
+            })?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum StoredFraudAction {
+    DoubleUpdate(StoredDoubleUpdate),
+    FailureNotification(StoredFailureNotification),
+}
+
+/// One outstanding fraud-response submission: `action` must land on
+/// `target`, no matter how many restarts or transient RPC errors it takes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FraudJob {
+    id: u64,
+    target: FraudJobTarget,
+    action: StoredFraudAction,
+}
+
+fn load_job_ids(db: &NomadDB) -> Result<Vec<u64>> {
+    Ok(db
+        .retrieve_keyed_decodable::<_, _, String>(FRAUD_JOB_INDEX, &FRAUD_JOB_INDEX_KEY.to_owned())?
+        .map(|json| serde_json::from_str(&json))
+        .transpose()?
+        .unwrap_or_default())
+}
+
+fn store_job_ids(db: &NomadDB, ids: &[u64]) -> Result<()> {
+    let serialized = serde_json::to_string(ids)?;
+    db.store_keyed_encodable(FRAUD_JOB_INDEX, &FRAUD_JOB_INDEX_KEY.to_owned(), &serialized)?;
+    Ok(())
+}
+
+fn load_job(db: &NomadDB, id: u64) -> Result<Option<FraudJob>> {
+    match db.retrieve_keyed_decodable::<_, _, String>(FRAUD_JOB, &id)? {
+        Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+        None => Ok(None),
+    }
+}
+
+fn store_job(db: &NomadDB, job: &FraudJob) -> Result<()> {
+    let serialized = serde_json::to_string(job)?;
+    db.store_keyed_encodable(FRAUD_JOB, &job.id, &serialized)?;
+    Ok(())
+}
+
+/// Resolves a [`FraudJobTarget`] to the contract/connection manager it
+/// names and submits the queued action to it. Implemented by
+/// [`crate::watcher::Watcher`], so the queue itself never has to know
+/// about `AgentCore`/`ConnectionManagers`.
+#[async_trait::async_trait]
+pub(crate) trait FraudJobTargets: Send + Sync {
+    async fn submit_double_update(&self, target: &FraudJobTarget, double: &DoubleUpdate) -> Result<TxOutcome>;
+
+    async fn submit_failure_notification(
+        &self,
+        target: &FraudJobTarget,
+        notification: &SignedFailureNotification,
+    ) -> Result<TxOutcome>;
+}
+
+/// Durable handle used to enqueue fraud-response jobs and track how many
+/// are outstanding. Cheaply cloneable -- every clone shares the same
+/// `NomadDB` and `outstanding` gauge.
+#[derive(Debug, Clone)]
+pub(crate) struct FraudJobQueue {
+    db: NomadDB,
+    next_id: Arc<AtomicU64>,
+    outstanding: IntGauge,
+}
+
+impl FraudJobQueue {
+    /// Reload any jobs left outstanding by a previous run of the agent.
+    pub(crate) fn new(db: NomadDB, outstanding: IntGauge) -> Result<Self> {
+        let ids = load_job_ids(&db)?;
+        outstanding.set(ids.len() as i64);
+        let next_id = ids.iter().max().copied().unwrap_or_default() + 1;
+
+        Ok(Self {
+            db,
+            next_id: Arc::new(AtomicU64::new(next_id)),
+            outstanding,
+        })
+    }
+
+    fn enqueue(&self, target: FraudJobTarget, action: StoredFraudAction) -> Result<()> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        info!(target = %target, id, "enqueuing durable fraud-response job");
+        store_job(&self.db, &FraudJob { id, target, action })?;
+
+        let mut ids = load_job_ids(&self.db)?;
+        ids.push(id);
+        store_job_ids(&self.db, &ids)?;
+        self.outstanding.set(ids.len() as i64);
+
+        Ok(())
+    }
+
+    /// Durably enqueue `double` for submission to `target`, surviving an
+    /// agent restart until it's confirmed to have landed.
+    pub(crate) fn enqueue_double_update(&self, target: FraudJobTarget, double: &DoubleUpdate) -> Result<()> {
+        self.enqueue(target, StoredFraudAction::DoubleUpdate(double.into()))
+    }
+
+    /// As [`Self::enqueue_double_update`], for a failure notification.
+    pub(crate) fn enqueue_failure_notification(
+        &self,
+        target: FraudJobTarget,
+        notification: &SignedFailureNotification,
+    ) -> Result<()> {
+        self.enqueue(
+            target,
+            StoredFraudAction::FailureNotification(notification.into()),
+        )
+    }
+
+    fn remove(&self, id: u64) -> Result<()> {
+        self.db.delete_keyed_encodable(FRAUD_JOB, &id)?;
+
+        let mut ids = load_job_ids(&self.db)?;
+        ids.retain(|&existing| existing != id);
+        store_job_ids(&self.db, &ids)?;
+        self.outstanding.set(ids.len() as i64);
+
+        Ok(())
+    }
+
+    /// How many jobs are currently enqueued but not yet confirmed. Lets a
+    /// caller that just enqueued a job (e.g. a shutdown path that needs to
+    /// know the backlog has actually drained, not merely been recorded)
+    /// poll for completion without reaching into `NomadDB` itself.
+    pub(crate) fn outstanding_count(&self) -> i64 {
+        self.outstanding.get()
+    }
+
+    /// Submit the oldest outstanding job, removing it only once `targets`
+    /// confirms it landed. Returns `Ok(true)` if a job was found and
+    /// confirmed, `Ok(false)` if the queue was empty.
+    pub(crate) async fn drain_one(&self, targets: &dyn FraudJobTargets) -> Result<bool> {
+        let id = match load_job_ids(&self.db)?.first() {
+            Some(&id) => id,
+            None => return Ok(false),
+        };
+        let job = load_job(&self.db, id)?
+            .ok_or_else(|| eyre!("fraud job {} is indexed but missing from storage", id))?;
+
+        let outcome = match &job.action {
+            StoredFraudAction::DoubleUpdate(stored) => {
+                let double: DoubleUpdate = stored.clone().try_into()?;
+                targets.submit_double_update(&job.target, &double).await?
+            }
+            StoredFraudAction::FailureNotification(stored) => {
+                let notification: SignedFailureNotification = stored.clone().try_into()?;
+                targets
+                    .submit_failure_notification(&job.target, &notification)
+                    .await?
+            }
+        };
+        outcome.check()?;
+
+        info!(target = %job.target, id, "confirmed fraud-response job");
+        self.remove(id)?;
+
+        Ok(true)
+    }
+}
+
+/// Drains a [`FraudJobQueue`] one job at a time, stopping only when a
+/// shutdown is signalled while the queue is empty. Wrapped in
+/// [`SupervisedTask`] so a failed submission gets the same
+/// exponential-backoff retry `ContractWatcher`/`HistorySync` already get,
+/// rather than a bespoke retry loop.
+pub(crate) struct FraudJobWorker {
+    queue: FraudJobQueue,
+    targets: Arc<dyn FraudJobTargets>,
+    shutdown: watch::Receiver<bool>,
+}
+
+impl FraudJobWorker {
+    pub(crate) fn new(
+        queue: FraudJobQueue,
+        targets: Arc<dyn FraudJobTargets>,
+        shutdown: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            queue,
+            targets,
+            shutdown,
+        }
+    }
+
+    #[tracing::instrument(skip(self, metrics))]
+    pub(crate) fn spawn(self, metrics: SupervisorMetrics) -> JoinHandle<()> {
+        supervise(self, metrics)
+    }
+}
+
+#[async_trait::async_trait]
+impl SupervisedTask for FraudJobWorker {
+    async fn tick(&mut self) -> WorkerState {
+        match self.queue.drain_one(self.targets.as_ref()).await {
+            Ok(true) => WorkerState::Progressed,
+            Ok(false) => {
+                select! {
+                    biased;
+                    _ = self.shutdown.changed() => {}
+                    _ = sleep(POLL_INTERVAL) => {}
+                }
+                if *self.shutdown.borrow() {
+                    WorkerState::Stopped
+                } else {
+                    WorkerState::Idle
+                }
+            }
+            Err(e) => {
+                warn!(error = %e, "fraud-response job submission failed, retrying after backoff");
+                WorkerState::Errored(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! The watcher polls home and replica contracts for double updates (and
+//! home itself for improper updates), notifying every contract and
+//! unenrolling affected replicas the moment one is found.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+#![warn(unused_extern_crates)]
+
+mod archive;
+mod fraud_queue;
+mod settings;
+mod supervisor;
+mod sync_retention;
+mod watcher;
+
+use crate::{settings::WatcherSettings as Settings, watcher::Watcher};
+use color_eyre::Result;
+use nomad_base::NomadAgent;
+
+use tracing::info_span;
+use tracing_subscriber::prelude::*;
+
+// Unlike the other agents, the watcher drives one ContractWatcher/
+// HistorySync/FraudJobWorker set per domain it protects, so a deployment
+// watching several chains benefits from more than one OS thread. That
+// rules out the usual `#[tokio::main(flavor = "current_thread")]` (the
+// worker count has to come from settings, not a literal), so the runtime
+// is built by hand here instead.
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let settings = {
+        // sets the subscriber for this scope only
+        let _sub = tracing_subscriber::FmtSubscriber::builder()
+            .json()
+            .with_level(true)
+            .set_default();
+        Settings::new()?
+    };
+
+    let worker_threads = settings
+        .agent
+        .worker_threads
+        .unwrap_or_else(num_cpus::get);
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(worker_threads)
+        .enable_all()
+        .build()?;
+
+    runtime.block_on(async move {
+        let agent = {
+            let span = info_span!("WatcherBootup");
+            let _span = span.enter();
+
+            Watcher::from_settings(settings).await?
+        };
+
+        let metrics_guard = agent.start_tracing(agent.metrics().span_duration());
+        let _ = agent.metrics().run_http_server();
+
+        agent.run_all().await??;
+        drop(metrics_guard);
+        Ok(())
+    })
+}
@@ -0,0 +1,111 @@
+//! Supervises `ContractWatcher`/`HistorySync` workers so a transient RPC
+//! error never permanently kills fraud detection for a chain.
+//!
+//! `ContractWatcher::spawn`/`HistorySync::spawn` used to loop until the
+//! first error and then simply vanish -- the watcher for that replica
+//! silently stopped forever. This module is modeled on Garage's
+//! worker-manager semantics instead: each worker reports a [`WorkerState`]
+//! back after every iteration (`Progressed`/`Idle` to keep going, `Done`
+//! to retire cleanly once there's genuinely nothing left to do, or
+//! `Errored` to be retried after a backoff), and [`supervise`] is the
+//! loop that acts on it. The backoff interval doubles on each
+//! consecutive error up to a cap, and resets the moment a worker makes
+//! progress again. A double-update watcher must never be permanently
+//! down while the agent is running unless it reaches `Done` or the
+//! process is shut down out from under it.
+
+use std::time::Duration;
+
+use color_eyre::Report;
+use prometheus::IntGauge;
+use tokio::{task::JoinHandle, time::sleep};
+use tracing::warn;
+
+/// What a supervised worker accomplished on one iteration of its loop.
+#[derive(Debug)]
+pub(crate) enum WorkerState {
+    /// Did useful work this iteration; keep going.
+    Progressed,
+    /// Nothing to do this iteration; keep going.
+    Idle,
+    /// Finished permanently (e.g. `HistorySync` reached the genesis
+    /// root). Do not restart.
+    Done,
+    /// Hit an error, presumably transient. Retry after a backoff.
+    Errored(Report),
+    /// Cooperative shutdown was requested and the worker finished its
+    /// current iteration cleanly in response. Do not restart.
+    Stopped,
+}
+
+/// One iteration of a supervised worker's loop, e.g. poll-and-send for a
+/// `ContractWatcher` or step-back-one-update for a `HistorySync`.
+#[async_trait::async_trait]
+pub(crate) trait SupervisedTask: Send + 'static {
+    async fn tick(&mut self) -> WorkerState;
+}
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// `watcher_worker_state` values -- kept as small integers on a gauge
+/// rather than as a label, so a flapping chain shows up as one
+/// timeseries moving, not a fresh series per restart.
+const STATE_RUNNING: i64 = 0;
+const STATE_ERRORED: i64 = 1;
+const STATE_DONE: i64 = 2;
+const STATE_STOPPED: i64 = 3;
+
+/// The gauges a [`supervise`]d task keeps current. One pair per
+/// `(home, replica, task)`, built by the caller (mirrors
+/// `updates_inspected_for_double`'s `with_label_values` pattern).
+#[derive(Debug, Clone)]
+pub(crate) struct SupervisorMetrics {
+    pub(crate) restarts: IntGauge,
+    pub(crate) state: IntGauge,
+}
+
+/// Tick `worker` in a loop forever, retrying `Errored` iterations after
+/// an exponentially growing backoff (reset to [`INITIAL_BACKOFF`] the
+/// moment a tick makes progress), and returning only once `worker`
+/// reports `Done` or `Stopped`.
+pub(crate) fn supervise<T: SupervisedTask>(
+    mut worker: T,
+    metrics: SupervisorMetrics,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF;
+        metrics.state.set(STATE_RUNNING);
+
+        loop {
+            match worker.tick().await {
+                WorkerState::Progressed => {
+                    backoff = INITIAL_BACKOFF;
+                    metrics.state.set(STATE_RUNNING);
+                }
+                WorkerState::Idle => {
+                    metrics.state.set(STATE_RUNNING);
+                }
+                WorkerState::Done => {
+                    metrics.state.set(STATE_DONE);
+                    return;
+                }
+                WorkerState::Stopped => {
+                    metrics.state.set(STATE_STOPPED);
+                    return;
+                }
+                WorkerState::Errored(error) => {
+                    metrics.state.set(STATE_ERRORED);
+                    metrics.restarts.inc();
+                    warn!(
+                        %error,
+                        backoff_secs = backoff.as_secs(),
+                        "supervised worker errored, retrying after backoff"
+                    );
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    })
+}
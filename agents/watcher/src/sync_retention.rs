@@ -0,0 +1,175 @@
+//! Retention and introspection for finished `HistorySync` tasks.
+//!
+//! Once a `HistorySync` walks back to the zero root (or is cut short by a
+//! cooperative shutdown), its supervised task simply stops ticking --
+//! there was previously no record anywhere of whether it actually made it
+//! all the way to genesis, errored out, or was cancelled mid-walk, and no
+//! way to tell how far it got without having been watching the logs at
+//! the time. This module borrows the dropped-resource aggregator idea
+//! from tokio-console: every `(home, replica)` pair's most recent finish
+//! is kept in [`SyncTaskRetention`], along with its outcome, last-seen
+//! root, and how many updates it dispatched before stopping. An entry
+//! isn't evicted just because time passed -- it has to have been read at
+//! least once via [`SyncTaskRetention::snapshot`] first, so a slow
+//! operator can't lose a record to eviction before ever seeing it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ethers::core::types::H256;
+use prometheus::IntGaugeVec;
+
+/// How long a retained entry is kept around after it's been read at
+/// least once via [`SyncTaskRetention::snapshot`].
+pub(crate) const DEFAULT_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Why a `HistorySync` task stopped ticking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SyncOutcome {
+    /// Walked all the way back to the zero root -- fully backfilled.
+    ReachedGenesis,
+    /// Hit an unrecoverable error.
+    Errored(String),
+    /// Stopped in response to a cooperative shutdown before reaching the
+    /// zero root.
+    Cancelled,
+}
+
+impl SyncOutcome {
+    fn label(&self) -> &'static str {
+        match self {
+            SyncOutcome::ReachedGenesis => "reached_genesis",
+            SyncOutcome::Errored(_) => "errored",
+            SyncOutcome::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// A snapshot of a `HistorySync` task as of the moment it stopped.
+#[derive(Debug, Clone)]
+pub(crate) struct RetainedSyncTask {
+    pub(crate) home: String,
+    pub(crate) replica: String,
+    pub(crate) outcome: SyncOutcome,
+    pub(crate) last_seen_root: H256,
+    pub(crate) updates_dispatched: u64,
+    pub(crate) finished_at: u64,
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before unix epoch")
+        .as_secs()
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    task: RetainedSyncTask,
+    read_at: Option<u64>,
+}
+
+/// Keeps the most recent finished-task record for every `(home, replica)`
+/// pair a `HistorySync` has run for, so an operator can confirm a
+/// replica's history was fully backfilled after the task itself has
+/// exited. Cheap to clone; every clone shares the same underlying map.
+#[derive(Debug, Clone)]
+pub(crate) struct SyncTaskRetention {
+    inner: Arc<Mutex<HashMap<String, Entry>>>,
+    retain_for: Duration,
+    retained: IntGaugeVec,
+}
+
+impl SyncTaskRetention {
+    pub(crate) fn new(retain_for: Duration, retained: IntGaugeVec) -> Self {
+        Self {
+            inner: Default::default(),
+            retain_for,
+            retained,
+        }
+    }
+
+    fn key(home: &str, replica: &str) -> String {
+        format!("{}_{}", home, replica)
+    }
+
+    fn evict_locked(&self, inner: &mut HashMap<String, Entry>) {
+        let now = now_unix();
+        let retained = &self.retained;
+        let retain_for = self.retain_for.as_secs();
+        inner.retain(|_, entry| {
+            let expired = matches!(entry.read_at, Some(read_at) if now.saturating_sub(read_at) >= retain_for);
+            if expired {
+                retained
+                    .with_label_values(&[
+                        &entry.task.home,
+                        &entry.task.replica,
+                        entry.task.outcome.label(),
+                    ])
+                    .dec();
+            }
+            !expired
+        });
+    }
+
+    /// Record that a `HistorySync` task for `(home, replica)` has
+    /// stopped ticking, replacing any previous record for that pair.
+    pub(crate) fn record(
+        &self,
+        home: &str,
+        replica: &str,
+        outcome: SyncOutcome,
+        last_seen_root: H256,
+        updates_dispatched: u64,
+    ) {
+        let mut inner = self.inner.lock().expect("retention lock poisoned");
+        self.evict_locked(&mut inner);
+
+        if let Some(previous) = inner.remove(&Self::key(home, replica)) {
+            self.retained
+                .with_label_values(&[
+                    &previous.task.home,
+                    &previous.task.replica,
+                    previous.task.outcome.label(),
+                ])
+                .dec();
+        }
+
+        self.retained
+            .with_label_values(&[home, replica, outcome.label()])
+            .inc();
+
+        inner.insert(
+            Self::key(home, replica),
+            Entry {
+                task: RetainedSyncTask {
+                    home: home.to_owned(),
+                    replica: replica.to_owned(),
+                    outcome,
+                    last_seen_root,
+                    updates_dispatched,
+                    finished_at: now_unix(),
+                },
+                read_at: None,
+            },
+        );
+    }
+
+    /// Snapshot every currently-retained task, marking each one as read
+    /// (if it wasn't already) so it becomes eligible for eviction once
+    /// `retain_for` has passed since that first read. A fresher record for
+    /// the same `(home, replica)` pair replaces it immediately.
+    pub(crate) fn snapshot(&self) -> Vec<RetainedSyncTask> {
+        let mut inner = self.inner.lock().expect("retention lock poisoned");
+        self.evict_locked(&mut inner);
+        let now = now_unix();
+        inner
+            .values_mut()
+            .map(|entry| {
+                entry.read_at.get_or_insert(now);
+                entry.task.clone()
+            })
+            .collect()
+    }
+}
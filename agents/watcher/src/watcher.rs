@@ -1,24 +1,29 @@
 use async_trait::async_trait;
 use color_eyre::{
-    eyre::{bail, ensure},
+    eyre::{bail, ensure, eyre},
     Report, Result,
 };
 use thiserror::Error;
 
 use ethers::{core::types::H256, prelude::H160};
-use futures_util::future::{join, join_all, select_all};
+use futures_util::future::select_all;
 use prometheus::{IntGauge, IntGaugeVec};
-use std::{collections::HashMap, fmt::Display, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use tokio::{
     select,
-    sync::{mpsc, RwLock},
+    sync::{broadcast, watch, RwLock},
     task::JoinHandle,
-    time::sleep,
+    time::{sleep, timeout},
 };
 use tracing::{error, info, info_span, instrument::Instrumented, warn, Instrument};
 
 use nomad_base::{
-    cancel_task, AgentCore, AttestationSigner, BaseError, CachingHome, ChainCommunicationError,
+    cancel_task, AgentCore, AttestationSigner, BaseError, CachingHome, CachingReplica,
     ConnectionManagers, NomadAgent, NomadDB,
 };
 use nomad_core::{
@@ -26,14 +31,250 @@ use nomad_core::{
     Home, SignedFailureNotification, SignedUpdate, TxOutcome,
 };
 
+use crate::archive::UpdateArchiver;
+use crate::fraud_queue::{FraudJobQueue, FraudJobTarget, FraudJobTargets, FraudJobWorker};
 use crate::settings::WatcherSettings as Settings;
+use crate::supervisor::{supervise, SupervisedTask, SupervisorMetrics, WorkerState};
+use crate::sync_retention::{RetainedSyncTask, SyncOutcome, SyncTaskRetention, DEFAULT_RETENTION};
 
 const AGENT_NAME: &str = "watcher";
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Clone)]
 enum WatcherError {
     #[error("Syncing finished")]
     SyncingFinished,
+    /// The update handler's task resolved deliberately -- e.g. it found a
+    /// double update and the watcher is shutting everything down -- rather
+    /// than crashing.
+    #[error("Update handler closed")]
+    Closed,
+    /// The update handler's task errored out unexpectedly. `cause` is the
+    /// original error's message, since the handler's own `Report` can't be
+    /// cloned into the shared error slot below.
+    #[error("Update handler crashed: {cause}")]
+    Crashed { cause: String },
+    /// A producer observed its channel to the update handler close and is
+    /// relaying the handler's recorded terminal cause instead of a bare
+    /// "channel closed".
+    #[error("Update handler closed: {cause}")]
+    HandlerClosed { cause: Arc<WatcherError> },
+}
+
+/// Whether `e` reflects a supervised worker having nothing left to do,
+/// rather than an error worth retrying: `HistorySync` reaching the genesis
+/// root, or the update handler having resolved deliberately (as opposed to
+/// having crashed).
+fn is_terminal(e: &Report) -> bool {
+    match e.downcast_ref::<WatcherError>() {
+        Some(WatcherError::SyncingFinished) => true,
+        Some(WatcherError::HandlerClosed { cause }) => {
+            matches!(cause.as_ref(), WatcherError::Closed)
+        }
+        _ => false,
+    }
+}
+
+/// Where the spawned [`UpdateHandler`] task records why it ultimately
+/// stopped, so a producer that observes its channel to the handler close
+/// can report the real cause instead of a bare "channel closed".
+type ErrorSlot = Arc<Mutex<Option<Arc<WatcherError>>>>;
+
+/// How many updates the broadcast stream behind [`HandlerSender`] retains
+/// for a consumer that hasn't caught up yet, before that consumer's next
+/// `recv` reports `RecvError::Lagged` instead of delivering them. Mirrors
+/// [`nomad_base::NomadDB`]'s own `NOTIFY_CHANNEL_CAPACITY` for the same
+/// kind of fan-out notification channel.
+const UPDATE_STREAM_CAPACITY: usize = 256;
+
+/// Wraps the broadcast channel producers use to hand `SignedUpdate`s to
+/// every subscriber of a home's update stream (the `UpdateHandler` plus
+/// whatever else [`Watcher::subscribe`] or an internal consumer like
+/// [`crate::archive::UpdateArchiver`] has attached). Once every subscriber
+/// has been dropped, `send` surfaces the `UpdateHandler`'s recorded exit
+/// cause as a typed [`WatcherError::HandlerClosed`] rather than letting
+/// producers see a generic "no receivers" error.
+#[derive(Debug, Clone)]
+struct HandlerSender {
+    tx: broadcast::Sender<SignedUpdate>,
+    cause: ErrorSlot,
+}
+
+impl HandlerSender {
+    fn new(tx: broadcast::Sender<SignedUpdate>, cause: ErrorSlot) -> Self {
+        Self { tx, cause }
+    }
+
+    async fn send(&self, update: SignedUpdate) -> Result<()> {
+        if self.tx.send(update).is_err() {
+            let cause = self
+                .cause
+                .lock()
+                .expect("error slot poisoned")
+                .clone()
+                .unwrap_or_else(|| Arc::new(WatcherError::Closed));
+            bail!(WatcherError::HandlerClosed { cause });
+        }
+
+        Ok(())
+    }
+}
+
+/// What a broadcast consumer's [`next_update`] call found.
+pub(crate) enum NextUpdate {
+    /// A new update arrived.
+    Update(SignedUpdate),
+    /// Every producer side of the stream has been dropped.
+    Closed,
+}
+
+/// Correlates one fraud-response run's logs across home, replicas, and
+/// connection managers, and gives it a cancellation point independent of
+/// the watch/sync loop that detected it.
+///
+/// `ContractSync`/`CachingHome`/`CachingReplica` aren't present in this
+/// module, so a `RunContext` can't be threaded through their construction
+/// as the maintainers sketched -- but
+/// [`Watcher::handle_double_update_failure`]/
+/// [`Watcher::handle_improper_update_failure`] already live here and are
+/// exactly the "one fraud-detection run" this context is meant to tag.
+#[derive(Debug, Clone)]
+pub(crate) struct RunContext {
+    run_id: u64,
+    home_name: String,
+    cancelled: watch::Receiver<bool>,
+}
+
+/// Source of [`RunContext::run_id`] values -- monotonic, not wall-clock, so
+/// two runs started in the same instant still get distinct ids.
+static NEXT_RUN_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+impl RunContext {
+    /// Start a new run investigating `home_name`, cancelled the moment
+    /// `cancelled` reports the watcher is shutting down.
+    pub(crate) fn new(home_name: impl Into<String>, cancelled: watch::Receiver<bool>) -> Self {
+        Self {
+            run_id: NEXT_RUN_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            home_name: home_name.into(),
+            cancelled,
+        }
+    }
+
+    /// This run's correlation id, for stamping onto every log/metric it
+    /// emits.
+    pub(crate) fn run_id(&self) -> u64 {
+        self.run_id
+    }
+
+    /// Whether the watcher has started shutting down since this run began,
+    /// i.e. whether this run should stop at its next cancellation point
+    /// rather than continue enqueueing fraud-response jobs.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+}
+
+/// Receive the next update from `rx` for `consumer`, transparently
+/// catching back up and counting the gap on `lagged_updates` (rather than
+/// letting a slow subscriber silently miss updates) whenever it falls more
+/// than [`UPDATE_STREAM_CAPACITY`] updates behind the producers.
+pub(crate) async fn next_update(
+    rx: &mut broadcast::Receiver<SignedUpdate>,
+    consumer: &str,
+    lagged_updates: &IntGauge,
+) -> NextUpdate {
+    loop {
+        match rx.recv().await {
+            Ok(update) => return NextUpdate::Update(update),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                lagged_updates.add(skipped as i64);
+                warn!(
+                    consumer,
+                    skipped, "update stream consumer lagged behind producers, skipping ahead"
+                );
+            }
+            Err(broadcast::error::RecvError::Closed) => return NextUpdate::Closed,
+        }
+    }
+}
+
+/// A minimal pluggable consumer of a domain's update stream: counts every
+/// update it sees on `observed` and nothing else. Demonstrates that the
+/// stream fan-out supports consumers with no bearing whatsoever on
+/// double-update detection, not just [`UpdateHandler`] and
+/// [`crate::archive::UpdateArchiver`].
+struct UpdateObserver {
+    rx: broadcast::Receiver<SignedUpdate>,
+    observed: IntGauge,
+    shutdown: watch::Receiver<bool>,
+    lagged_updates: IntGauge,
+}
+
+impl UpdateObserver {
+    fn new(
+        rx: broadcast::Receiver<SignedUpdate>,
+        observed: IntGauge,
+        shutdown: watch::Receiver<bool>,
+        lagged_updates: IntGauge,
+    ) -> Self {
+        Self {
+            rx,
+            observed,
+            shutdown,
+            lagged_updates,
+        }
+    }
+}
+
+#[async_trait]
+impl SupervisedTask for UpdateObserver {
+    async fn tick(&mut self) -> WorkerState {
+        select! {
+            biased;
+            _ = self.shutdown.changed() => {
+                if !*self.shutdown.borrow() {
+                    return WorkerState::Idle;
+                }
+                WorkerState::Stopped
+            }
+            update = next_update(&mut self.rx, "update_observer", &self.lagged_updates) => {
+                match update {
+                    NextUpdate::Update(_) => {
+                        self.observed.inc();
+                        WorkerState::Progressed
+                    }
+                    NextUpdate::Closed => WorkerState::Done,
+                }
+            }
+        }
+    }
+}
+
+/// How long a single poll for a new update may take before it's treated as
+/// stalled. Mirrors the deadline-status pattern used when establishing
+/// provider connections: an RPC call is always raced against a timeout
+/// rather than awaited indefinitely.
+const POLL_TIMEOUT: Duration = Duration::from_secs(30);
+/// Backoff applied after a poll times out, doubling on each consecutive
+/// timeout and reset the moment a poll completes. Deliberately shorter
+/// than `supervise`'s own backoff cap, since a stalled poll is expected to
+/// recover much sooner than a worker that's erroring outright.
+const POLL_TIMEOUT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const POLL_TIMEOUT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Doubles `current`, capped at [`POLL_TIMEOUT_MAX_BACKOFF`], for the next
+/// retry after a poll times out.
+fn next_poll_timeout_backoff(current: Duration) -> Duration {
+    (current * 2).min(POLL_TIMEOUT_MAX_BACKOFF)
+}
+
+/// What `ContractWatcher::poll_and_send_update` found out on one poll.
+enum PollOutcome {
+    /// A new update was found and handed off to the `UpdateHandler`.
+    Found,
+    /// Nothing new to inspect.
+    NotFound,
+    /// The poll itself didn't complete within `POLL_TIMEOUT`.
+    TimedOut,
 }
 
 #[derive(Debug)]
@@ -43,9 +284,12 @@ where
 {
     interval: u64,
     committed_root: H256,
-    tx: mpsc::Sender<SignedUpdate>,
+    tx: HandlerSender,
     contract: Arc<C>,
     updates_inspected_for_double: IntGauge,
+    poll_timeouts: IntGauge,
+    timeout_backoff: Duration,
+    shutdown: watch::Receiver<bool>,
 }
 
 impl<C> Display for ContractWatcher<C>
@@ -66,12 +310,15 @@ impl<C> ContractWatcher<C>
 where
     C: Common + CommonEvents + ?Sized + 'static,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         interval: u64,
         from: H256,
-        tx: mpsc::Sender<SignedUpdate>,
+        tx: HandlerSender,
         contract: Arc<C>,
         updates_inspected_for_double: IntGauge,
+        poll_timeouts: IntGauge,
+        shutdown: watch::Receiver<bool>,
     ) -> Self {
         Self {
             interval,
@@ -79,14 +326,32 @@ where
             tx,
             contract,
             updates_inspected_for_double,
+            poll_timeouts,
+            timeout_backoff: POLL_TIMEOUT_INITIAL_BACKOFF,
+            shutdown,
         }
     }
 
-    async fn poll_and_send_update(&mut self) -> Result<()> {
-        let update_opt = self
-            .contract
-            .signed_update_by_old_root(self.committed_root)
-            .await?;
+    /// Poll for a new update, racing the RPC call against `POLL_TIMEOUT` so
+    /// a hung or slow provider can never stall this loop indefinitely.
+    async fn poll_and_send_update(&mut self) -> Result<PollOutcome> {
+        let update_opt = match timeout(
+            POLL_TIMEOUT,
+            self.contract.signed_update_by_old_root(self.committed_root),
+        )
+        .await
+        {
+            Err(_) => {
+                self.poll_timeouts.inc();
+                warn!(
+                    contract = self.contract.name(),
+                    timeout = ?POLL_TIMEOUT,
+                    "poll for new update timed out, retrying with backoff"
+                );
+                return Ok(PollOutcome::TimedOut);
+            }
+            Ok(result) => result?,
+        };
 
         if update_opt.is_none() {
             info!(
@@ -94,7 +359,7 @@ where
                 self.committed_root,
                 self.contract.name()
             );
-            return Ok(());
+            return Ok(PollOutcome::NotFound);
         }
 
         let new_update = update_opt.unwrap();
@@ -109,20 +374,79 @@ where
         self.tx.send(new_update).await?;
         self.updates_inspected_for_double.inc();
 
-        Ok(())
+        Ok(PollOutcome::Found)
     }
 
-    #[tracing::instrument]
-    fn spawn(mut self) -> JoinHandle<Result<()>> {
-        tokio::spawn(async move {
-            loop {
-                self.poll_and_send_update().await?;
-                sleep(Duration::from_secs(self.interval)).await;
+    #[tracing::instrument(skip(metrics))]
+    fn spawn(self, metrics: SupervisorMetrics) -> JoinHandle<()> {
+        supervise(self, metrics)
+    }
+}
+
+#[async_trait]
+impl<C> SupervisedTask for ContractWatcher<C>
+where
+    C: Common + CommonEvents + ?Sized + 'static,
+{
+    async fn tick(&mut self) -> WorkerState {
+        match self.poll_and_send_update().await {
+            Ok(PollOutcome::TimedOut) => {
+                // Don't wait out the full poll interval after a stalled
+                // poll -- back off and retry sooner, doubling on each
+                // consecutive timeout up to POLL_TIMEOUT_MAX_BACKOFF.
+                let backoff = self.timeout_backoff;
+                select! {
+                    biased;
+                    _ = self.shutdown.changed() => {}
+                    _ = sleep(backoff) => {}
+                }
+                self.timeout_backoff = next_poll_timeout_backoff(backoff);
+                if *self.shutdown.borrow() {
+                    return WorkerState::Stopped;
+                }
+                WorkerState::Idle
             }
-        })
+            Ok(outcome) => {
+                self.timeout_backoff = POLL_TIMEOUT_INITIAL_BACKOFF;
+                // Race the usual poll interval against a shutdown request so
+                // we notice one promptly instead of waiting out the interval,
+                // without interrupting the poll_and_send_update call that
+                // just completed above.
+                select! {
+                    biased;
+                    _ = self.shutdown.changed() => {}
+                    _ = sleep(Duration::from_secs(self.interval)) => {}
+                }
+                if *self.shutdown.borrow() {
+                    return WorkerState::Stopped;
+                }
+                match outcome {
+                    PollOutcome::Found => WorkerState::Progressed,
+                    PollOutcome::NotFound => WorkerState::Idle,
+                    PollOutcome::TimedOut => unreachable!(),
+                }
+            }
+            Err(e) if is_terminal(&e) => WorkerState::Done,
+            Err(e) => WorkerState::Errored(e),
+        }
     }
 }
 
+const HISTORY_SYNC_CURSOR: &str = "history_sync_cursor_";
+
+/// Load a `HistorySync` cursor persisted by [`persist_cursor`], if any was
+/// ever stored for `contract_name`.
+fn load_cursor(db: &NomadDB, contract_name: &str) -> Result<Option<H256>> {
+    db.retrieve_keyed_decodable(HISTORY_SYNC_CURSOR, &contract_name.to_owned())
+}
+
+/// Persist a `HistorySync` cursor for `contract_name`, so a restart resumes
+/// the backward walk instead of starting over from the caller-supplied
+/// `from` root.
+fn store_cursor(db: &NomadDB, contract_name: &str, root: H256) -> Result<()> {
+    db.store_keyed_encodable(HISTORY_SYNC_CURSOR, &contract_name.to_owned(), &root)
+}
+
 #[derive(Debug)]
 pub struct HistorySync<C>
 where
@@ -130,36 +454,86 @@ where
 {
     interval: u64,
     committed_root: H256,
-    tx: mpsc::Sender<SignedUpdate>,
+    tx: HandlerSender,
     contract: Arc<C>,
     updates_inspected_for_double: IntGauge,
+    shutdown: watch::Receiver<bool>,
+    home_name: String,
+    updates_dispatched: u64,
+    retention: SyncTaskRetention,
+    db: NomadDB,
 }
 
 impl<C> HistorySync<C>
 where
     C: Common + CommonEvents + ?Sized + 'static,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         interval: u64,
         from: H256,
-        tx: mpsc::Sender<SignedUpdate>,
+        tx: HandlerSender,
         contract: Arc<C>,
         updates_inspected_for_double: IntGauge,
+        shutdown: watch::Receiver<bool>,
+        home_name: impl Into<String>,
+        retention: SyncTaskRetention,
+        db: NomadDB,
     ) -> Self {
+        let committed_root = match load_cursor(&db, contract.name()) {
+            Ok(Some(resumed)) => {
+                info!(
+                    "HistorySync for contract {} resuming from persisted cursor {}",
+                    contract.name(),
+                    resumed
+                );
+                resumed
+            }
+            Ok(None) => from,
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    "HistorySync for contract {} failed to load persisted cursor, starting from {}",
+                    contract.name(),
+                    from
+                );
+                from
+            }
+        };
+
         Self {
-            committed_root: from,
+            committed_root,
             tx,
             contract,
             interval,
             updates_inspected_for_double,
+            shutdown,
+            home_name: home_name.into(),
+            updates_dispatched: 0,
+            retention,
+            db,
         }
     }
 
+    /// Persist `committed_root` as this contract's walk cursor, so a
+    /// restart resumes from here instead of re-scanning from `from`.
+    fn persist_cursor(&self) -> Result<()> {
+        store_cursor(&self.db, self.contract.name(), self.committed_root)
+    }
+
     async fn update_history(&mut self) -> Result<()> {
-        let previous_update = self
-            .contract
-            .signed_update_by_new_root(self.committed_root)
-            .await?;
+        // Checkpoint before the backward lookup/send so a failure below
+        // can roll `committed_root` back to it, and the next call
+        // re-attempts this exact step instead of silently skipping it.
+        let checkpoint = self.committed_root;
+
+        let previous_update = match self.contract.signed_update_by_new_root(checkpoint).await {
+            Ok(previous_update) => previous_update,
+            Err(e) => {
+                self.committed_root = checkpoint;
+                return Err(e);
+            }
+        };
 
         if previous_update.is_none() {
             info!(
@@ -169,13 +543,21 @@ where
             return Err(Report::new(WatcherError::SyncingFinished));
         }
 
-        // Dispatch to the handler
         let previous_update = previous_update.unwrap();
-        self.tx.send(previous_update.clone()).await?;
-        self.updates_inspected_for_double.inc();
 
-        // set up for next loop iteration
+        // Advance the cursor before dispatching, so that if `send` fails
+        // below we roll back to `checkpoint` rather than leaving
+        // `committed_root` pointing past an update the handler never saw.
         self.committed_root = previous_update.update.previous_root;
+        if let Err(e) = self.tx.send(previous_update.clone()).await {
+            self.committed_root = checkpoint;
+            return Err(e);
+        }
+
+        self.updates_inspected_for_double.inc();
+        self.updates_dispatched += 1;
+        self.persist_cursor()?;
+
         if self.committed_root.is_zero() {
             info!(
                 "HistorySync for contract {} has finished.",
@@ -187,44 +569,87 @@ where
         Ok(())
     }
 
-    #[tracing::instrument]
-    fn spawn(mut self) -> JoinHandle<Result<()>> {
-        tokio::spawn(async move {
-            loop {
-                let res = self.update_history().await;
-                if res.is_err() {
-                    // Syncing done
-                    break;
-                }
+    /// Record this task's final state into `retention` before it's torn
+    /// down, so it's still discoverable after the supervised loop exits.
+    fn retain(&self, outcome: SyncOutcome) {
+        self.retention.record(
+            &self.home_name,
+            self.contract.name(),
+            outcome,
+            self.committed_root,
+            self.updates_dispatched,
+        );
+    }
 
-                sleep(Duration::from_secs(self.interval)).await;
-            }
+    #[tracing::instrument(skip(metrics))]
+    fn spawn(self, metrics: SupervisorMetrics) -> JoinHandle<()> {
+        supervise(self, metrics)
+    }
+}
 
-            Ok(())
-        })
+#[async_trait]
+impl<C> SupervisedTask for HistorySync<C>
+where
+    C: Common + CommonEvents + ?Sized + 'static,
+{
+    async fn tick(&mut self) -> WorkerState {
+        match self.update_history().await {
+            Ok(()) => {
+                select! {
+                    biased;
+                    _ = self.shutdown.changed() => {}
+                    _ = sleep(Duration::from_secs(self.interval)) => {}
+                }
+                if *self.shutdown.borrow() {
+                    self.retain(SyncOutcome::Cancelled);
+                    return WorkerState::Stopped;
+                }
+                WorkerState::Progressed
+            }
+            Err(e) if is_terminal(&e) => {
+                let outcome = match e.downcast_ref::<WatcherError>() {
+                    Some(WatcherError::SyncingFinished) => SyncOutcome::ReachedGenesis,
+                    // The handler closed deliberately, i.e. on shutdown.
+                    _ => SyncOutcome::Cancelled,
+                };
+                self.retain(outcome);
+                WorkerState::Done
+            }
+            Err(e) => WorkerState::Errored(e),
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct UpdateHandler {
-    rx: mpsc::Receiver<SignedUpdate>,
+    rx: broadcast::Receiver<SignedUpdate>,
     watcher_db: NomadDB,
     home: Arc<CachingHome>,
     updater: H160,
+    shutdown: watch::Receiver<bool>,
+    cause: ErrorSlot,
+    lagged_updates: IntGauge,
 }
 
 impl UpdateHandler {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        rx: mpsc::Receiver<SignedUpdate>,
+        rx: broadcast::Receiver<SignedUpdate>,
         watcher_db: NomadDB,
         home: Arc<CachingHome>,
         updater: H160,
+        shutdown: watch::Receiver<bool>,
+        cause: ErrorSlot,
+        lagged_updates: IntGauge,
     ) -> Self {
         Self {
             rx,
             watcher_db,
             home,
             updater,
+            shutdown,
+            cause,
+            lagged_updates,
         }
     }
 
@@ -278,45 +703,169 @@ impl UpdateHandler {
         Ok(())
     }
 
-    /// Receive updates and check them for fraud. If double update was
-    /// found, return Ok(double_update). This loop should never exit naturally
-    /// unless the channel for sending new updates was closed, in which case we
-    /// return an error.
-    #[tracing::instrument]
-    fn spawn(mut self) -> JoinHandle<Result<DoubleUpdate>> {
-        tokio::spawn(async move {
-            loop {
-                let update = self.rx.recv().await;
-                // channel is closed
-                if update.is_none() {
-                    bail!("Channel closed.")
-                }
+    /// Apply one update and check it for fraud, returning `Some` if this
+    /// update turned out to be a double update.
+    async fn apply_and_check(&mut self, update: SignedUpdate) -> Result<Option<DoubleUpdate>> {
+        let old_root = update.update.previous_root;
+
+        // This check may appear redundant with the check in
+        // `check_double_update` that signers match, however,
+        // this is
+        ensure!(
+            update.verify(self.updater).is_ok(),
+            "Handling update signed by another updater. Hint: This agent may misconfigured, or the updater may have rotated while this agent was running"
+        );
 
-                let update = update.unwrap();
-                let old_root = update.update.previous_root;
+        if old_root == self.home.committed_root().await? {
+            // It is okay if tx reverts
+            let _ = self.home.update(&update).await;
+        }
 
-                // This check may appear redundant with the check in
-                // `check_double_update` that signers match, however,
-                // this is
-                ensure!(
-                    update.verify(self.updater).is_ok(),
-                    "Handling update signed by another updater. Hint: This agent may misconfigured, or the updater may have rotated while this agent was running"
-                );
+        if let Err(double_update) = self.check_double_update(&update) {
+            return Ok(Some(double_update));
+        }
 
-                if old_root == self.home.committed_root().await? {
-                    // It is okay if tx reverts
-                    let _ = self.home.update(&update).await;
-                }
+        Ok(None)
+    }
 
-                if let Err(double_update) = self.check_double_update(&update) {
-                    return Ok(double_update);
+    /// Receive updates and check them for fraud. If a double update was
+    /// found, return Ok(double_update). On a cooperative shutdown request,
+    /// drain and apply any updates already buffered on `rx` before
+    /// returning, so nothing in flight is lost. This loop should never
+    /// exit naturally unless the channel for sending new updates was
+    /// closed or shutdown was requested, in which case we return an error.
+    async fn run(&mut self) -> Result<DoubleUpdate> {
+        loop {
+            let update = select! {
+                biased;
+                _ = self.shutdown.changed() => {
+                    if !*self.shutdown.borrow() {
+                        continue;
+                    }
+                    loop {
+                        match self.rx.try_recv() {
+                            Ok(update) => {
+                                if let Some(double_update) = self.apply_and_check(update).await? {
+                                    return Ok(double_update);
+                                }
+                            }
+                            Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                                self.lagged_updates.add(skipped as i64);
+                                warn!(
+                                    skipped,
+                                    "update handler lagged behind producers during shutdown drain"
+                                );
+                            }
+                            Err(broadcast::error::TryRecvError::Empty)
+                            | Err(broadcast::error::TryRecvError::Closed) => break,
+                        }
+                    }
+                    bail!(WatcherError::Closed)
                 }
+                update = next_update(&mut self.rx, "update_handler", &self.lagged_updates) => update,
+            };
+
+            let update = match update {
+                NextUpdate::Update(update) => update,
+                // stream is closed -- every producer has been dropped
+                NextUpdate::Closed => bail!(WatcherError::Closed),
+            };
+
+            if let Some(double_update) = self.apply_and_check(update).await? {
+                return Ok(double_update);
             }
+        }
+    }
+
+    /// Record why this handler ultimately stopped into `cause`, so a
+    /// producer whose send fails afterward can report the real reason
+    /// instead of a bare channel-closed error.
+    fn record_cause(&self, result: &Result<DoubleUpdate>) {
+        let cause = match result {
+            Ok(_) => WatcherError::Closed,
+            Err(e) => e
+                .downcast_ref::<WatcherError>()
+                .cloned()
+                .unwrap_or_else(|| WatcherError::Crashed {
+                    cause: format!("{:#}", e),
+                }),
+        };
+        *self.cause.lock().expect("error slot poisoned") = Some(Arc::new(cause));
+    }
+
+    #[tracing::instrument]
+    fn spawn(mut self) -> JoinHandle<Result<DoubleUpdate>> {
+        tokio::spawn(async move {
+            let result = self.run().await;
+            self.record_cause(&result);
+            result
         })
     }
 }
 
-type TaskMap = Arc<RwLock<HashMap<String, Instrumented<JoinHandle<Result<()>>>>>>;
+type TaskMap = Arc<RwLock<HashMap<String, Instrumented<JoinHandle<()>>>>>;
+
+/// Everything the watcher needs to protect one home chain's replicas
+/// against fraud: the home contract and its replicas, the connection
+/// managers (on remote domains) that can unenroll one of its replicas,
+/// and a fraud-response queue scoped to just this home so one domain's
+/// backlog of retries can never block another's. A [`Watcher`] keeps one
+/// of these per home it watches, keyed by that home's domain id.
+#[derive(Debug, Clone)]
+struct Domain {
+    home: Arc<CachingHome>,
+    replicas: HashMap<String, Arc<CachingReplica>>,
+    connection_managers: Vec<Arc<ConnectionManagers>>,
+    fraud_queue: FraudJobQueue,
+    double_updates_observed: IntGauge,
+    /// Every `SignedUpdate` this domain's `ContractWatcher`/`HistorySync`
+    /// tasks observe is broadcast here, so the `UpdateHandler` can share
+    /// the stream with other subscribers -- [`Watcher::subscribe`] and
+    /// internal consumers like [`crate::archive::UpdateArchiver`] -- rather
+    /// than being the stream's only consumer.
+    updates: broadcast::Sender<SignedUpdate>,
+}
+
+#[async_trait]
+impl FraudJobTargets for Domain {
+    async fn submit_double_update(
+        &self,
+        target: &FraudJobTarget,
+        double: &DoubleUpdate,
+    ) -> Result<TxOutcome> {
+        match target {
+            FraudJobTarget::Home => Ok(self.home.double_update(double).await?),
+            FraudJobTarget::Replica(name) => {
+                let replica = self
+                    .replicas
+                    .get(name)
+                    .ok_or_else(|| eyre!("fraud job targets unknown replica {}", name))?;
+                Ok(replica.double_update(double).await?)
+            }
+            FraudJobTarget::ConnectionManager(_) => {
+                bail!("fraud job requested a double update against a connection manager target")
+            }
+        }
+    }
+
+    async fn submit_failure_notification(
+        &self,
+        target: &FraudJobTarget,
+        notification: &SignedFailureNotification,
+    ) -> Result<TxOutcome> {
+        match target {
+            FraudJobTarget::ConnectionManager(idx) => {
+                let connection_manager = self.connection_managers.get(*idx).ok_or_else(|| {
+                    eyre!("fraud job targets unknown connection manager {}", idx)
+                })?;
+                Ok(connection_manager.unenroll_replica(notification).await?)
+            }
+            _ => bail!(
+                "fraud job requested a failure notification against a non-connection-manager target"
+            ),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Watcher {
@@ -324,10 +873,20 @@ pub struct Watcher {
     interval_seconds: u64,
     sync_tasks: TaskMap,
     watch_tasks: TaskMap,
-    connection_managers: Vec<Arc<ConnectionManagers>>,
+    /// One [`Domain`] per home this watcher protects, keyed by that home's
+    /// domain id. `core.home` is always present here too (under its own
+    /// domain id), since `watch_home_fail` -- a single-home `NomadAgent`
+    /// default this module does not own -- can only ever watch `core.home`.
+    domains: HashMap<u32, Domain>,
     core: AgentCore,
-    double_updates_observed: IntGauge,
     updates_inspected_for_double: IntGaugeVec,
+    poll_timeouts: IntGaugeVec,
+    worker_restarts: IntGaugeVec,
+    worker_state: IntGaugeVec,
+    sync_retention: SyncTaskRetention,
+    lagged_updates: IntGaugeVec,
+    updates_observed: IntGaugeVec,
+    shutdown: watch::Sender<bool>,
 }
 
 impl AsRef<AgentCore> for Watcher {
@@ -338,11 +897,18 @@ impl AsRef<AgentCore> for Watcher {
 
 #[allow(clippy::unit_arg)]
 impl Watcher {
-    /// Instantiate a new watcher.
+    /// Instantiate a new watcher that protects the replicas of every home
+    /// in `domains`, each keyed by that home's own domain id, against the
+    /// connection managers enrolled alongside it.
+    ///
+    /// Replicas are still sourced from the single `core.replicas` map
+    /// shared by every domain -- this snapshot's settings schema has no
+    /// notion of per-domain replica sets, so that part of the
+    /// maintainers' chain-centric sketch isn't reachable from here yet.
     pub fn new(
         signer: AttestationSigner,
         interval_seconds: u64,
-        connection_managers: Vec<Arc<ConnectionManagers>>,
+        domains: HashMap<u32, (Arc<CachingHome>, Vec<Arc<ConnectionManagers>>)>,
         core: AgentCore,
     ) -> Self {
         let double_updates_observed = core
@@ -352,8 +918,7 @@ impl Watcher {
                 "Number of times a double update has been observed (anything > 0 is major red flag!)",
                 &["home", "agent"],
             )
-            .expect("failed to register watcher metric")
-            .with_label_values(&[core.home.name(), Self::AGENT_NAME]);
+            .expect("failed to register watcher metric");
 
         let updates_inspected_for_double = core
             .metrics
@@ -364,36 +929,236 @@ impl Watcher {
             )
             .expect("failed to register watcher metric");
 
+        let poll_timeouts = core
+            .metrics
+            .new_int_gauge_vec(
+                "watcher_poll_timeouts",
+                "Number of times a poll for a new update timed out before completing",
+                &["home", "checked", "agent"],
+            )
+            .expect("failed to register watcher metric");
+
+        let worker_restarts = core
+            .metrics
+            .new_int_gauge_vec(
+                "watcher_worker_restarts",
+                "Number of times a supervised watch/sync worker has been restarted after an error",
+                &["home", "replica", "task"],
+            )
+            .expect("failed to register watcher metric");
+
+        let worker_state = core
+            .metrics
+            .new_int_gauge_vec(
+                "watcher_worker_state",
+                "Current state of a supervised watch/sync worker (0 running, 1 errored, 2 done, 3 stopped)",
+                &["home", "replica", "task"],
+            )
+            .expect("failed to register watcher metric");
+
+        let fraud_jobs_outstanding = core
+            .metrics
+            .new_int_gauge_vec(
+                "fraud_jobs_outstanding",
+                "Number of durable fraud-response jobs (double updates / failure notifications) queued but not yet confirmed on-chain",
+                &["home", "agent"],
+            )
+            .expect("failed to register watcher metric");
+
+        let sync_tasks_retained = core
+            .metrics
+            .new_int_gauge_vec(
+                "watcher_sync_tasks_retained",
+                "Number of finished HistorySync tasks currently retained for introspection, by outcome",
+                &["home", "replica", "state"],
+            )
+            .expect("failed to register watcher metric");
+        let sync_retention = SyncTaskRetention::new(DEFAULT_RETENTION, sync_tasks_retained);
+
+        let lagged_updates = core
+            .metrics
+            .new_int_gauge_vec(
+                "watcher_lagged_updates",
+                "Number of updates a subscriber of a home's update stream has missed after falling too far behind",
+                &["home", "consumer", "agent"],
+            )
+            .expect("failed to register watcher metric");
+
+        let updates_observed = core
+            .metrics
+            .new_int_gauge_vec(
+                "watcher_updates_observed",
+                "Number of updates observed on a home's update stream by a consumer other than the double-update detector",
+                &["home", "consumer", "agent"],
+            )
+            .expect("failed to register watcher metric");
+
+        let domains = domains
+            .into_iter()
+            .map(|(domain_id, (home, connection_managers))| {
+                let fraud_queue_db_name = format!("{}_{}", home.name(), AGENT_NAME);
+                let fraud_queue = FraudJobQueue::new(
+                    NomadDB::new(fraud_queue_db_name, core.db.clone()),
+                    fraud_jobs_outstanding.with_label_values(&[home.name(), Self::AGENT_NAME]),
+                )
+                .expect("failed to load durable fraud job queue");
+
+                let (updates, _) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+
+                let domain = Domain {
+                    double_updates_observed: double_updates_observed
+                        .with_label_values(&[home.name(), Self::AGENT_NAME]),
+                    replicas: core.replicas.clone(),
+                    home,
+                    connection_managers,
+                    fraud_queue,
+                    updates,
+                };
+                (domain_id, domain)
+            })
+            .collect();
+
+        let (shutdown, _) = watch::channel(false);
+
         Self {
             signer: Arc::new(signer),
             interval_seconds,
             sync_tasks: Default::default(),
             watch_tasks: Default::default(),
-            connection_managers,
+            domains,
             core,
-            double_updates_observed,
             updates_inspected_for_double,
+            poll_timeouts,
+            worker_restarts,
+            worker_state,
+            sync_retention,
+            lagged_updates,
+            updates_observed,
+            shutdown,
         }
     }
 
-    /// Spawn UpdateHandler and sync tasks. Have sync tasks send UpdateHandler
-    /// signed updates through mpsc. Return Some(double_update) if any
+    /// Subscribe to every `SignedUpdate` observed for the primary home this
+    /// watcher protects (`core.home`'s own domain). Like
+    /// [`Self::handle_improper_update_failure`], this is limited to the one
+    /// domain `from_settings` can build from `WatcherSettings` today; use
+    /// [`Self::subscribe_domain`] if a future multi-home schema hands this
+    /// watcher more than one entry in `domains`.
+    pub fn subscribe(&self) -> broadcast::Receiver<SignedUpdate> {
+        self.domains
+            .get(&self.home().local_domain())
+            .expect("primary home missing from domain map")
+            .updates
+            .subscribe()
+    }
+
+    /// The domain id of every home this watcher currently protects. Always
+    /// a single entry -- `core.home`'s own domain -- until `WatcherSettings`
+    /// grows a schema for naming more than one home; every watch/sync loop
+    /// and fraud target is already keyed off this map, so a wider schema
+    /// would only need to change how `domains` itself gets built.
+    pub fn watched_domains(&self) -> impl Iterator<Item = u32> + '_ {
+        self.domains.keys().copied()
+    }
+
+    /// As [`Self::subscribe`], for a specific domain id rather than always
+    /// the primary home. Returns `None` if `domain_id` isn't one this
+    /// watcher protects.
+    pub fn subscribe_domain(&self, domain_id: u32) -> Option<broadcast::Receiver<SignedUpdate>> {
+        self.domains.get(&domain_id).map(|d| d.updates.subscribe())
+    }
+
+    /// Snapshot every `HistorySync` task that has finished (reached the
+    /// zero root, or was cancelled by shutdown) and is still being
+    /// retained, so an operator can confirm every replica's history was
+    /// fully backfilled without having had to watch the logs at the time.
+    /// A task whose ticks error is retried by its supervisor and leaves no
+    /// entry here, so no entry has the `Errored` outcome.
+    pub(crate) fn retained_sync_tasks(&self) -> Vec<RetainedSyncTask> {
+        self.sync_retention.snapshot()
+    }
+
+    /// Spawn UpdateHandler and sync tasks for one domain's home. Have sync
+    /// tasks broadcast signed updates to UpdateHandler (and any other
+    /// subscriber of `domain.updates`). Return Some(double_update) if any
     /// conflicting updates are found.
-    fn watch_double_update(&self) -> Instrumented<JoinHandle<Result<Option<DoubleUpdate>>>> {
-        let home = self.home();
-        let replicas = self.replicas().clone();
+    fn watch_double_update(
+        &self,
+        domain: &Domain,
+    ) -> Instrumented<JoinHandle<Result<Option<DoubleUpdate>>>> {
+        let home = domain.home.clone();
+        let replicas = domain.replicas.clone();
         let watcher_db_name = format!("{}_{}", home.name(), AGENT_NAME);
         let watcher_db = NomadDB::new(watcher_db_name, self.db());
+        let history_sync_db = watcher_db.clone();
         let interval_seconds = self.interval_seconds;
         let sync_tasks = self.sync_tasks.clone();
         let watch_tasks = self.watch_tasks.clone();
         let updates_inspected_for_double = self.updates_inspected_for_double.clone();
+        let poll_timeouts = self.poll_timeouts.clone();
+        let worker_restarts = self.worker_restarts.clone();
+        let worker_state = self.worker_state.clone();
+        let sync_retention = self.sync_retention.clone();
+        let supervisor_metrics = move |home: &str, replica: &str, task: &str| SupervisorMetrics {
+            restarts: worker_restarts.with_label_values(&[home, replica, task]),
+            state: worker_state.with_label_values(&[home, replica, task]),
+        };
+        let shutdown_tx = self.shutdown.clone();
+        let updates_tx = domain.updates.clone();
+        let lagged_updates = self.lagged_updates.clone();
+        let updates_observed = self.updates_observed.clone();
+        let worker_restarts = self.worker_restarts.clone();
+        let worker_state = self.worker_state.clone();
+        let db = self.db();
 
         tokio::spawn(async move {
             let updater = home.updater().await?;
             // Spawn update handler
-            let (tx, rx) = mpsc::channel(200);
-            let handler = UpdateHandler::new(rx, watcher_db, home.clone(), updater.into()).spawn();
+            let rx = updates_tx.subscribe();
+            let cause: ErrorSlot = Default::default();
+            let tx = HandlerSender::new(updates_tx.clone(), cause.clone());
+            let handler = UpdateHandler::new(
+                rx,
+                watcher_db,
+                home.clone(),
+                updater.into(),
+                shutdown_tx.subscribe(),
+                cause,
+                lagged_updates.with_label_values(&[home.name(), "update_handler", Self::AGENT_NAME]),
+            )
+            .spawn();
+
+            // Pluggable consumers of the same update stream, entirely
+            // independent of double-update detection: a durable archive of
+            // every observed update, and a bare observed-updates counter.
+            let archive_db = NomadDB::new(format!("{}_{}_archive", home.name(), AGENT_NAME), db);
+            let archiver = UpdateArchiver::new(
+                updates_tx.subscribe(),
+                archive_db,
+                shutdown_tx.subscribe(),
+                lagged_updates.with_label_values(&[home.name(), "update_archiver", Self::AGENT_NAME]),
+            )?;
+            supervise(
+                archiver,
+                SupervisorMetrics {
+                    restarts: worker_restarts.with_label_values(&[home.name(), home.name(), "archive"]),
+                    state: worker_state.with_label_values(&[home.name(), home.name(), "archive"]),
+                },
+            );
+
+            let observer = UpdateObserver::new(
+                updates_tx.subscribe(),
+                updates_observed.with_label_values(&[home.name(), "update_observer", Self::AGENT_NAME]),
+                shutdown_tx.subscribe(),
+                lagged_updates.with_label_values(&[home.name(), "update_observer", Self::AGENT_NAME]),
+            );
+            supervise(
+                observer,
+                SupervisorMetrics {
+                    restarts: worker_restarts.with_label_values(&[home.name(), home.name(), "observe"]),
+                    state: worker_state.with_label_values(&[home.name(), home.name(), "observe"]),
+                },
+            );
 
             // For each replica, spawn polling and history syncing tasks
             info!("Spawning replica watch and sync tasks...");
@@ -406,6 +1171,11 @@ impl Watcher {
                     replica.name(),
                     Self::AGENT_NAME,
                 ]);
+                let timeouts = poll_timeouts.with_label_values(&[
+                    home.name(),
+                    replica.name(),
+                    Self::AGENT_NAME,
+                ]);
 
                 watch_tasks.write().await.insert(
                     (*name).to_owned(),
@@ -415,15 +1185,27 @@ impl Watcher {
                         tx.clone(),
                         replica.clone(),
                         inspected.clone(),
+                        timeouts,
+                        shutdown_tx.subscribe(),
                     )
-                    .spawn()
+                    .spawn(supervisor_metrics(home.name(), replica.name(), "watch"))
                     .in_current_span(),
                 );
                 sync_tasks.write().await.insert(
                     (*name).to_owned(),
-                    HistorySync::new(interval_seconds, from, tx.clone(), replica, inspected)
-                        .spawn()
-                        .in_current_span(),
+                    HistorySync::new(
+                        interval_seconds,
+                        from,
+                        tx.clone(),
+                        replica,
+                        inspected,
+                        shutdown_tx.subscribe(),
+                        home.name(),
+                        sync_retention.clone(),
+                        history_sync_db.clone(),
+                    )
+                    .spawn(supervisor_metrics(home.name(), &name, "sync"))
+                    .in_current_span(),
                 );
             }
 
@@ -435,6 +1217,11 @@ impl Watcher {
                 home.name(),
                 Self::AGENT_NAME,
             ]);
+            let timeouts = poll_timeouts.with_label_values(&[
+                home.name(),
+                home.name(),
+                Self::AGENT_NAME,
+            ]);
 
             let home_watcher = ContractWatcher::new(
                 interval_seconds,
@@ -442,21 +1229,38 @@ impl Watcher {
                 tx.clone(),
                 home.clone(),
                 inspected.clone(),
+                timeouts,
+                shutdown_tx.subscribe(),
+            )
+            .spawn(supervisor_metrics(home.name(), home.name(), "watch"))
+            .in_current_span();
+            let home_name = home.name().to_owned();
+            let home_sync = HistorySync::new(
+                interval_seconds,
+                from,
+                tx.clone(),
+                home,
+                inspected,
+                shutdown_tx.subscribe(),
+                home_name.clone(),
+                sync_retention,
+                history_sync_db,
             )
-            .spawn()
+            .spawn(supervisor_metrics(&home_name, &home_name, "sync"))
             .in_current_span();
-            let home_sync = HistorySync::new(interval_seconds, from, tx.clone(), home, inspected)
-                .spawn()
-                .in_current_span();
 
             // Wait for update handler to finish (should only happen watcher is
-            // manually shut down)
+            // manually shut down, or a double update was detected)
             let double_update_res = handler.await?;
 
-            // Cancel running tasks
-            tracing::info!("Update handler has resolved. Cancelling all other tasks");
-            cancel_task!(home_watcher);
-            cancel_task!(home_sync);
+            // However the handler resolved, signal every other supervised
+            // loop to finish its current iteration and exit cooperatively,
+            // then give the home's own watch/sync tasks a grace period to
+            // do so before aborting them outright.
+            tracing::info!("Update handler has resolved. Winding down home watch/sync tasks");
+            let _ = shutdown_tx.send(true);
+            stop_gracefully(home_watcher, "home_watch").await;
+            stop_gracefully(home_sync, "home_sync").await;
 
             // Map Result<DoubleUpdate> into Option. If handler returned error
             // no double update. If handler returned Ok(double_update), map into
@@ -466,77 +1270,153 @@ impl Watcher {
         .in_current_span()
     }
 
-    async fn create_signed_failure(&self) -> SignedFailureNotification {
+    async fn create_signed_failure(&self, domain: &Domain) -> SignedFailureNotification {
         FailureNotification {
-            home_domain: self.home().local_domain(),
-            updater: self.home().updater().await.unwrap().into(),
+            home_domain: domain.home.local_domain(),
+            updater: domain.home.updater().await.unwrap().into(),
         }
         .sign_with(self.signer.as_ref())
         .await
         .expect("!sign")
     }
 
-    /// Handle a double-update once it has been detected. Submit double updates
-    /// and failure notifications to all homes/replicas.
-    #[tracing::instrument]
+    /// Handle a double-update once it has been detected on `domain`.
+    /// Durably enqueues a double-update job for that home/its replicas and
+    /// a failure-notification job for every connection manager enrolled
+    /// for that domain, so [`FraudJobWorker`] can retry each submission
+    /// with backoff -- surviving a restart -- until its `TxOutcome` is
+    /// confirmed, instead of firing every submission once and only
+    /// logging the result.
+    #[tracing::instrument(skip(self, domain, ctx), fields(run_id = ctx.run_id(), home = %ctx.home_name))]
     async fn handle_double_update_failure(
         &self,
+        domain: &Domain,
         double: &DoubleUpdate,
-    ) -> Vec<Result<TxOutcome, ChainCommunicationError>> {
-        // Create vector of double update futures
-        let mut double_update_futs: Vec<_> = self
-            .core
-            .replicas
-            .values()
-            .map(|replica| replica.double_update(double))
-            .collect();
-        double_update_futs.push(self.core.home.double_update(double));
+        ctx: &RunContext,
+    ) -> Result<()> {
+        if ctx.is_cancelled() {
+            info!("run cancelled before any fraud-response job was enqueued");
+            return Ok(());
+        }
 
-        // Created signed failure notification
-        let signed_failure = self.create_signed_failure().await;
+        // Once the first job is durably enqueued, see this run through to a
+        // consistent set of jobs rather than stopping partway -- the
+        // fraud-response queue already keeps draining regardless of
+        // shutdown, so a half-enqueued response (e.g. the home notified
+        // but no connection manager ever told to unenroll) would be a
+        // correctness gap, not a clean cancellation.
+        domain
+            .fraud_queue
+            .enqueue_double_update(FraudJobTarget::Home, double)?;
+        for name in domain.replicas.keys() {
+            domain
+                .fraud_queue
+                .enqueue_double_update(FraudJobTarget::Replica(name.clone()), double)?;
+        }
 
-        // Create vector of futures for unenrolling replicas (one per
-        // connection manager)
-        let mut unenroll_futs = Vec::new();
-        for connection_manager in self.connection_managers.iter() {
-            unenroll_futs.push(connection_manager.unenroll_replica(&signed_failure));
+        let signed_failure = self.create_signed_failure(domain).await;
+        for idx in 0..domain.connection_managers.len() {
+            domain.fraud_queue.enqueue_failure_notification(
+                FraudJobTarget::ConnectionManager(idx),
+                &signed_failure,
+            )?;
         }
 
-        // Join both vectors of double update and unenroll futures and
-        // return vector containing all results
-        let (double_update_res, unenroll_res) =
-            join(join_all(double_update_futs), join_all(unenroll_futs)).await;
-        double_update_res
-            .into_iter()
-            .chain(unenroll_res.into_iter())
-            .collect()
+        Ok(())
     }
 
-    /// Handle a double-update once it has been detected. Submit double updates
-    /// and failure notifications to all homes/replicas.
-    #[tracing::instrument]
-    async fn handle_improper_update_failure(
-        &self,
-    ) -> Vec<Result<TxOutcome, ChainCommunicationError>> {
-        let signed_failure = self.create_signed_failure().await;
-        let mut unenroll_futs = Vec::new();
-        for connection_manager in self.connection_managers.iter() {
-            unenroll_futs.push(connection_manager.unenroll_replica(&signed_failure));
+    /// As [`Self::handle_double_update_failure`], for an improper update on
+    /// `domain`: durably enqueues a failure-notification job for every
+    /// connection manager enrolled for that domain.
+    #[tracing::instrument(skip(self, domain, ctx), fields(run_id = ctx.run_id(), home = %ctx.home_name))]
+    async fn handle_improper_update_failure(&self, domain: &Domain, ctx: &RunContext) -> Result<()> {
+        if ctx.is_cancelled() {
+            info!("run cancelled before any fraud-response job was enqueued");
+            return Ok(());
+        }
+
+        let signed_failure = self.create_signed_failure(domain).await;
+        for idx in 0..domain.connection_managers.len() {
+            domain.fraud_queue.enqueue_failure_notification(
+                FraudJobTarget::ConnectionManager(idx),
+                &signed_failure,
+            )?;
         }
 
-        join_all(unenroll_futs).await
+        Ok(())
     }
 
     async fn shutdown(&self) {
-        for (_, v) in self.watch_tasks.write().await.drain() {
-            cancel_task!(v);
+        let _ = self.shutdown.send(true);
+        for (name, v) in self.watch_tasks.write().await.drain() {
+            stop_gracefully(v, &name).await;
         }
-        for (_, v) in self.sync_tasks.write().await.drain() {
-            cancel_task!(v);
+        for (name, v) in self.sync_tasks.write().await.drain() {
+            stop_gracefully(v, &name).await;
         }
     }
 }
 
+/// How long to let a supervised worker finish its current iteration and
+/// observe the shutdown signal on its own before giving up on it.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(30);
+
+/// How often to re-check a [`FraudJobQueue`]'s outstanding count while
+/// waiting for it to drain during shutdown.
+const FRAUD_QUEUE_DRAIN_POLL: Duration = Duration::from_millis(500);
+
+/// Wait up to [`SHUTDOWN_GRACE`] for every job already enqueued on `queue`
+/// to be confirmed by its [`FraudJobWorker`] (which keeps draining
+/// regardless of the shutdown signal), so a double/improper update
+/// detected right as the process is asked to exit still gets its
+/// `double_update`/`unenroll_replica` transactions fired before we return
+/// from `run_all` and the runtime goes down with them.
+async fn wait_for_fraud_queue_drained(queue: &FraudJobQueue) {
+    let waited = timeout(SHUTDOWN_GRACE, async {
+        while queue.outstanding_count() > 0 {
+            sleep(FRAUD_QUEUE_DRAIN_POLL).await;
+        }
+    })
+    .await;
+
+    if waited.is_err() {
+        warn!(
+            outstanding = queue.outstanding_count(),
+            "fraud-response queue did not drain within the shutdown grace period, exiting anyway"
+        );
+    }
+}
+
+/// Await `handle` for up to [`SHUTDOWN_GRACE`], assuming the shutdown watch
+/// it's racing against has already been set. Falls back to `cancel_task!`
+/// only if it doesn't finish in time, so a worker mid-RPC-call isn't torn
+/// out from under itself on the common, cooperative path.
+async fn stop_gracefully(mut handle: Instrumented<JoinHandle<()>>, label: &str) {
+    if timeout(SHUTDOWN_GRACE, &mut handle).await.is_err() {
+        warn!(
+            task = label,
+            "supervised worker did not shut down within grace period, cancelling"
+        );
+        cancel_task!(handle);
+    }
+}
+
+/// Waits for SIGTERM. A no-op that never resolves on non-unix platforms,
+/// so it drops out of the `select!` in [`Watcher::run_all`] without ever
+/// winning the race there.
+#[cfg(unix)]
+async fn wait_for_sigterm() {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut sigterm =
+        signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
+    sigterm.recv().await;
+}
+
+#[cfg(not(unix))]
+async fn wait_for_sigterm() {
+    std::future::pending().await
+}
+
 #[async_trait]
 #[allow(clippy::unit_arg)]
 impl NomadAgent for Watcher {
@@ -603,12 +1483,19 @@ impl NomadAgent for Watcher {
         )
         .await?;
 
-        Ok(Self::new(
-            signer,
-            settings.agent.interval,
-            connection_managers,
-            core,
-        ))
+        // `WatcherSettings` only ever names one home (`try_into_core`
+        // above), so this domain map always has a single entry -- for
+        // `core.home`'s own domain, with every connection manager
+        // configured. Genuinely multi-home settings (a `domain ->
+        // (home, connection_managers)` table coming from config) would
+        // need a schema change to `WatcherSettings` that's out of scope
+        // for this module; everything downstream of this map (watch
+        // loops, fraud targeting, metrics) is already domain-keyed and
+        // ready for more entries once that schema exists.
+        let mut domains = HashMap::new();
+        domains.insert(core.home.local_domain(), (core.home.clone(), connection_managers));
+
+        Ok(Self::new(signer, settings.agent.interval, domains, core))
     }
 
     fn build_channel(&self, _replica: &str) -> Self::Channel {
@@ -631,6 +1518,41 @@ impl NomadAgent for Watcher {
         tokio::spawn(async move {
             info!("Starting Watcher tasks");
 
+            // Snapshot once so the indices `select_all` hands back below
+            // map to a stable (domain_id, Domain) pairing for the rest of
+            // this task.
+            let domains: Vec<(u32, Domain)> =
+                self.domains.iter().map(|(id, d)| (*id, d.clone())).collect();
+
+            // One durable fraud-response queue per domain, each drained
+            // independently of everything else, so jobs left over from a
+            // prior crash (or enqueued while this task is busy elsewhere)
+            // keep retrying with backoff until confirmed on-chain, and a
+            // backlog on one home can never starve another's.
+            let _fraud_job_tasks: Vec<_> = domains
+                .iter()
+                .map(|(_, domain)| {
+                    let fraud_job_metrics = SupervisorMetrics {
+                        restarts: self.worker_restarts.with_label_values(&[
+                            domain.home.name(),
+                            domain.home.name(),
+                            "fraud_queue",
+                        ]),
+                        state: self.worker_state.with_label_values(&[
+                            domain.home.name(),
+                            domain.home.name(),
+                            "fraud_queue",
+                        ]),
+                    };
+                    FraudJobWorker::new(
+                        domain.fraud_queue.clone(),
+                        Arc::new(domain.clone()),
+                        self.shutdown.subscribe(),
+                    )
+                    .spawn(fraud_job_metrics)
+                })
+                .collect();
+
             let home_sync_task = self
                 .home()
                 .sync();
@@ -643,9 +1565,34 @@ impl NomadAgent for Watcher {
             sync_tasks.extend(replica_sync_tasks);
             let sync_task_unified = select_all(sync_tasks);
 
-            let double_update_watch_task = self.watch_double_update();
+            // One independent double-update watch loop per domain. A
+            // double update on one home is handled entirely in terms of
+            // that home's own replicas/connection managers; the others
+            // keep running.
+            let double_update_watch_tasks: Vec<_> = domains
+                .iter()
+                .map(|(_, domain)| self.watch_double_update(domain))
+                .collect();
+            let double_update_unified = select_all(double_update_watch_tasks);
+
+            // `watch_home_fail` is a single-home `NomadAgent` default that
+            // can only ever observe `core.home`, so improper-update
+            // detection -- unlike double-update detection above -- does
+            // not extend past the one domain `from_settings` can build.
             let improper_update_watch_task = self.watch_home_fail(self.interval_seconds);
 
+            // Race a SIGTERM/SIGINT against every other task so an operator
+            // (or an orchestrator redeploying this agent) can request a
+            // graceful shutdown the same way a detected fraud case does:
+            // every `ContractWatcher`/`HistorySync`/`UpdateHandler` winds
+            // down cooperatively instead of being killed mid-iteration.
+            let shutdown_signal = async {
+                select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = wait_for_sigterm() => {}
+                }
+            };
+
             // Race index and run tasks
             info!("Selecting across tasks...");
             select! {
@@ -653,20 +1600,23 @@ impl NomadAgent for Watcher {
                     info!("Syncing tasks finished early!");
                     self.shutdown().await;
                 },
-                double_res = double_update_watch_task => {
+                (double_res, idx, _) = double_update_unified => {
+                    let (_, domain) = &domains[idx];
                     let opt_double = double_res??;
                     if let Some(double) = opt_double {
                         tracing::error!(
                             double_update = ?double,
+                            home = domain.home.name(),
                             "Double update detected! Notifying all contracts and unenrolling replicas! Double update: {:?}",
                             double
                         );
-                        self.double_updates_observed.inc();
+                        domain.double_updates_observed.inc();
 
-                        self.handle_double_update_failure(&double)
-                            .await
-                            .iter()
-                            .for_each(|res| tracing::info!("{:#?}", res));
+                        let ctx = RunContext::new(domain.home.name(), self.shutdown.subscribe());
+                        if let Err(e) = self.handle_double_update_failure(domain, &double, &ctx).await {
+                            tracing::error!(error = %e, "failed to enqueue durable fraud-response jobs");
+                        }
+                        wait_for_fraud_queue_drained(&domain.fraud_queue).await;
 
                         bail!(
                             r#"
@@ -688,10 +1638,18 @@ impl NomadAgent for Watcher {
                                 "Improper update detected! Notifying all contracts and unenrolling replicas!",
                             );
 
-                            self.handle_improper_update_failure()
-                                .await
-                                .iter()
-                                .for_each(|res| tracing::info!("{:#?}", res));
+                            match self.domains.get(&self.home().local_domain()) {
+                                Some(domain) => {
+                                    let ctx = RunContext::new(domain.home.name(), self.shutdown.subscribe());
+                                    if let Err(e) = self.handle_improper_update_failure(domain, &ctx).await {
+                                        tracing::error!(error = %e, "failed to enqueue durable fraud-response jobs");
+                                    }
+                                    wait_for_fraud_queue_drained(&domain.fraud_queue).await;
+                                }
+                                None => tracing::error!(
+                                    "primary home missing from domain map; cannot enqueue fraud-response jobs"
+                                ),
+                            }
 
                             bail!(
                                 r#"
@@ -708,6 +1666,10 @@ impl NomadAgent for Watcher {
                         self.shutdown().await;
                     }
                 }
+                _ = shutdown_signal => {
+                    info!("Received shutdown signal, winding down watch/sync tasks gracefully");
+                    self.shutdown().await;
+                }
             }
 
             Ok(())
@@ -721,7 +1683,6 @@ mod test {
     use nomad_base::IndexSettings;
     use nomad_test::mocks::MockIndexer;
     use std::sync::Arc;
-    use tokio::sync::mpsc;
 
     use ethers::core::types::H256;
     use ethers::signers::{LocalWallet, Signer};
@@ -736,6 +1697,27 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn poll_timeout_backoff_doubles_and_caps() {
+        let mut backoff = POLL_TIMEOUT_INITIAL_BACKOFF;
+        assert_eq!(backoff, Duration::from_secs(1));
+
+        backoff = next_poll_timeout_backoff(backoff);
+        assert_eq!(backoff, Duration::from_secs(2));
+
+        backoff = next_poll_timeout_backoff(backoff);
+        assert_eq!(backoff, Duration::from_secs(4));
+
+        // Keep doubling until it would exceed the cap.
+        for _ in 0..10 {
+            backoff = next_poll_timeout_backoff(backoff);
+        }
+        assert_eq!(backoff, POLL_TIMEOUT_MAX_BACKOFF);
+
+        // Stays capped once it's there.
+        assert_eq!(next_poll_timeout_backoff(backoff), POLL_TIMEOUT_MAX_BACKOFF);
+    }
+
     #[tokio::test]
     async fn contract_watcher_polls_and_sends_update() {
         test_utils::run_test_db(|db| async move {
@@ -798,14 +1780,18 @@ mod test {
                 "Number of updates inspected for double",
             )
             .unwrap();
+            let poll_timeouts = IntGauge::new("poll_timeouts", "Number of poll timeouts").unwrap();
 
-            let (tx, mut rx) = mpsc::channel(200);
+            let (updates_tx, mut rx) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+            let tx = HandlerSender::new(updates_tx, Default::default());
             let mut contract_watcher = ContractWatcher::new(
                 3,
                 first_root,
                 tx.clone(),
                 home.clone(),
                 updates_inspected_for_double,
+                poll_timeouts,
+                watch::channel(false).1,
             );
 
             contract_watcher
@@ -819,6 +1805,170 @@ mod test {
         .await
     }
 
+    /// Two independent subscribers of the same update stream must both
+    /// observe the same `SignedUpdate` from a single
+    /// `poll_and_send_update` call -- the whole point of fanning the
+    /// stream out via `broadcast` instead of the old single-consumer
+    /// `mpsc` channel.
+    #[tokio::test]
+    async fn multiple_subscribers_observe_the_same_update() {
+        test_utils::run_test_db(|db| async move {
+            let signer: LocalWallet =
+                "1111111111111111111111111111111111111111111111111111111111111111"
+                    .parse()
+                    .unwrap();
+
+            let first_root = H256::from([0; 32]);
+            let second_root = H256::from([1; 32]);
+
+            let signed_update = Update {
+                home_domain: 1,
+                previous_root: first_root,
+                new_root: second_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let metrics = Arc::new(
+                CoreMetrics::new(
+                    "contract_sync_test",
+                    "home",
+                    None,
+                    Arc::new(prometheus::Registry::new()),
+                )
+                .expect("could not make metrics"),
+            );
+            let sync_metrics = ContractSyncMetrics::new(metrics);
+
+            let mut mock_home = MockHomeContract::new();
+            let nomad_db = NomadDB::new("home_1_fanout", db);
+            mock_home.expect__name().return_const("home_1".to_owned());
+            nomad_db.store_latest_update(&signed_update).unwrap();
+
+            let home_indexer: Arc<HomeIndexers> = Arc::new(MockIndexer::new().into());
+            let home_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "home_1".to_owned(),
+                "replica_1".to_owned(),
+                nomad_db.clone(),
+                home_indexer,
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics,
+            );
+            let home: Arc<CachingHome> =
+                CachingHome::new(mock_home.into(), home_sync, nomad_db.clone()).into();
+
+            let updates_inspected_for_double = IntGauge::new(
+                "updates_inspected_for_double",
+                "Number of updates inspected for double",
+            )
+            .unwrap();
+            let poll_timeouts = IntGauge::new("poll_timeouts", "Number of poll timeouts").unwrap();
+
+            let (updates_tx, _) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+            let mut first_subscriber = updates_tx.subscribe();
+            let mut second_subscriber = updates_tx.subscribe();
+            let tx = HandlerSender::new(updates_tx, Default::default());
+
+            let mut contract_watcher = ContractWatcher::new(
+                3,
+                first_root,
+                tx,
+                home,
+                updates_inspected_for_double,
+                poll_timeouts,
+                watch::channel(false).1,
+            );
+
+            contract_watcher
+                .poll_and_send_update()
+                .await
+                .expect("Should have received Ok(())");
+
+            assert_eq!(first_subscriber.recv().await.unwrap(), signed_update);
+            assert_eq!(second_subscriber.recv().await.unwrap(), signed_update);
+        })
+        .await
+    }
+
+    /// `poll_and_send_update` races the contract call against
+    /// `POLL_TIMEOUT`, but `MockHomeContract` -- like any
+    /// `mockall`-generated async-trait mock -- always resolves its
+    /// configured response on the very first poll, so there's no way to
+    /// make a mocked call genuinely hang long enough to drive the
+    /// `TimedOut` branch from a unit test. This instead exercises the
+    /// next best thing: a contract that never has anything new to
+    /// report, proving `tick()` keeps coming back `Idle` call after
+    /// call rather than treating "nothing found" as a reason for the
+    /// supervised task to wind down.
+    #[tokio::test]
+    async fn contract_watcher_keeps_polling_without_exiting_when_no_update_found() {
+        test_utils::run_test_db(|db| async move {
+            let metrics = Arc::new(
+                CoreMetrics::new(
+                    "contract_sync_test",
+                    "home",
+                    None,
+                    Arc::new(prometheus::Registry::new()),
+                )
+                .expect("could not make metrics"),
+            );
+            let sync_metrics = ContractSyncMetrics::new(metrics);
+
+            let mut mock_home = MockHomeContract::new();
+            let nomad_db = NomadDB::new("home_1_idle", db);
+            mock_home.expect__name().return_const("home_1".to_owned());
+            // Deliberately never store an update: every poll should come
+            // back empty-handed.
+
+            let home_indexer: Arc<HomeIndexers> = Arc::new(MockIndexer::new().into());
+            let home_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "home_1".to_owned(),
+                "replica_1".to_owned(),
+                nomad_db.clone(),
+                home_indexer,
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics,
+            );
+            let home: Arc<CachingHome> =
+                CachingHome::new(mock_home.into(), home_sync, nomad_db.clone()).into();
+
+            let updates_inspected_for_double = IntGauge::new(
+                "updates_inspected_for_double",
+                "Number of updates inspected for double",
+            )
+            .unwrap();
+            let poll_timeouts = IntGauge::new("poll_timeouts", "Number of poll timeouts").unwrap();
+
+            let (updates_tx, _rx) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+            let tx = HandlerSender::new(updates_tx, Default::default());
+
+            // interval: 0 so the post-poll wait between ticks is
+            // effectively instant rather than slowing down the test.
+            let mut contract_watcher = ContractWatcher::new(
+                0,
+                H256::zero(),
+                tx,
+                home,
+                updates_inspected_for_double,
+                poll_timeouts.clone(),
+                watch::channel(false).1,
+            );
+
+            for _ in 0..5 {
+                assert!(matches!(contract_watcher.tick().await, WorkerState::Idle));
+            }
+            assert_eq!(poll_timeouts.get(), 0);
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn history_sync_updates_history() {
         test_utils::run_test_db(|db| async move {
@@ -888,77 +2038,407 @@ mod test {
             let home: Arc<CachingHome> =
                 CachingHome::new(mock_home.into(), home_sync, nomad_db.clone()).into();
 
-            let (tx, mut rx) = mpsc::channel(200);
+            let (updates_tx, mut rx) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+            let tx = HandlerSender::new(updates_tx, Default::default());
+            let inspected = IntGauge::new(
+                "updates_inspected_for_double",
+                "Number of updates inspected for double",
+            )
+            .unwrap();
+            let sync_tasks_retained = metrics
+                .new_int_gauge_vec(
+                    "watcher_sync_tasks_retained",
+                    "retained sync tasks",
+                    &["home", "replica", "state"],
+                )
+                .unwrap();
+            let mut history_sync = HistorySync::new(
+                3,
+                second_root,
+                tx.clone(),
+                home.clone(),
+                inspected,
+                watch::channel(false).1,
+                "home_1",
+                SyncTaskRetention::new(DEFAULT_RETENTION, sync_tasks_retained),
+                nomad_db.clone(),
+            );
+
+            // First update_history call returns first -> second update
+            history_sync
+                .update_history()
+                .await
+                .expect("Should have received Ok(())");
+
+            assert_eq!(history_sync.committed_root, first_root);
+            assert_eq!(rx.recv().await.unwrap(), second_signed_update);
+
+            // Second update_history call returns zero -> first update
+            // and should return WatcherError::SyncingFinished
+            let res = history_sync.update_history().await;
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                WatcherError::SyncingFinished.to_string(),
+                "Should have received WatcherError::SyncingFinished"
+            );
+
+            assert_eq!(history_sync.committed_root, zero_root);
+            assert_eq!(rx.recv().await.unwrap(), first_signed_update)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn history_sync_resumes_from_persisted_cursor_after_restart() {
+        test_utils::run_test_db(|db| async move {
+            let signer: LocalWallet =
+                "1111111111111111111111111111111111111111111111111111111111111111"
+                    .parse()
+                    .unwrap();
+
+            let zero_root = H256::zero();
+            let first_root = H256::from([1; 32]);
+            let second_root = H256::from([2; 32]);
+
+            let first_signed_update = Update {
+                home_domain: 1,
+                previous_root: zero_root,
+                new_root: first_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let second_signed_update = Update {
+                home_domain: 1,
+                previous_root: first_root,
+                new_root: second_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let metrics = Arc::new(
+                CoreMetrics::new(
+                    "contract_sync_test",
+                    "home",
+                    None,
+                    Arc::new(prometheus::Registry::new()),
+                )
+                .expect("could not make metrics"),
+            );
+            let sync_metrics = ContractSyncMetrics::new(metrics.clone());
+
+            let mut mock_home = MockHomeContract::new();
+            let nomad_db = NomadDB::new("home_1", db.clone());
+
+            mock_home.expect__name().return_const("home_1".to_owned());
+            nomad_db.store_latest_update(&first_signed_update).unwrap();
+            nomad_db.store_latest_update(&second_signed_update).unwrap();
+
+            let home_indexer: Arc<HomeIndexers> = Arc::new(MockIndexer::new().into());
+            let home_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "home_1".to_owned(),
+                "replica_1".to_owned(),
+                nomad_db.clone(),
+                home_indexer,
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics,
+            );
+            let home: Arc<CachingHome> =
+                CachingHome::new(mock_home.into(), home_sync, nomad_db.clone()).into();
+
+            let (updates_tx, mut rx) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+            let tx = HandlerSender::new(updates_tx, Default::default());
             let inspected = IntGauge::new(
                 "updates_inspected_for_double",
                 "Number of updates inspected for double",
             )
             .unwrap();
-            let mut history_sync =
-                HistorySync::new(3, second_root, tx.clone(), home.clone(), inspected);
+            let sync_tasks_retained = metrics
+                .new_int_gauge_vec(
+                    "watcher_sync_tasks_retained",
+                    "retained sync tasks",
+                    &["home", "replica", "state"],
+                )
+                .unwrap();
+
+            // First HistorySync walks one step forward, persisting a cursor
+            // at `first_root`, then is dropped -- simulating a restart.
+            let mut history_sync = HistorySync::new(
+                3,
+                second_root,
+                tx.clone(),
+                home.clone(),
+                inspected.clone(),
+                watch::channel(false).1,
+                "home_1",
+                SyncTaskRetention::new(DEFAULT_RETENTION, sync_tasks_retained.clone()),
+                nomad_db.clone(),
+            );
+            history_sync
+                .update_history()
+                .await
+                .expect("Should have received Ok(())");
+            assert_eq!(history_sync.committed_root, first_root);
+            assert_eq!(rx.recv().await.unwrap(), second_signed_update);
+            drop(history_sync);
+
+            // A fresh HistorySync constructed against the same NomadDB, even
+            // when given the original `from` as a starting point, should
+            // resume from the persisted cursor instead.
+            let resumed_history_sync = HistorySync::new(
+                3,
+                second_root,
+                tx,
+                home,
+                inspected,
+                watch::channel(false).1,
+                "home_1",
+                SyncTaskRetention::new(DEFAULT_RETENTION, sync_tasks_retained),
+                nomad_db,
+            );
+            assert_eq!(resumed_history_sync.committed_root, first_root);
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn history_sync_rolls_back_cursor_on_send_failure() {
+        test_utils::run_test_db(|db| async move {
+            let signer: LocalWallet =
+                "1111111111111111111111111111111111111111111111111111111111111111"
+                    .parse()
+                    .unwrap();
+
+            let zero_root = H256::zero();
+            let first_root = H256::from([1; 32]);
+            let second_root = H256::from([2; 32]);
+
+            let first_signed_update = Update {
+                home_domain: 1,
+                previous_root: zero_root,
+                new_root: first_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let second_signed_update = Update {
+                home_domain: 1,
+                previous_root: first_root,
+                new_root: second_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let metrics = Arc::new(
+                CoreMetrics::new(
+                    "contract_sync_test",
+                    "home",
+                    None,
+                    Arc::new(prometheus::Registry::new()),
+                )
+                .expect("could not make metrics"),
+            );
+            let sync_metrics = ContractSyncMetrics::new(metrics.clone());
+
+            let mut mock_home = MockHomeContract::new();
+            let nomad_db = NomadDB::new("home_1", db.clone());
+
+            mock_home.expect__name().return_const("home_1".to_owned());
+            nomad_db.store_latest_update(&first_signed_update).unwrap();
+            nomad_db.store_latest_update(&second_signed_update).unwrap();
+
+            let home_indexer: Arc<HomeIndexers> = Arc::new(MockIndexer::new().into());
+            let home_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "home_1".to_owned(),
+                "replica_1".to_owned(),
+                nomad_db.clone(),
+                home_indexer,
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics,
+            );
+            let home: Arc<CachingHome> =
+                CachingHome::new(mock_home.into(), home_sync, nomad_db.clone()).into();
+
+            // Drop the receiver before the send, so HandlerSender::send has
+            // no subscriber left and bails with WatcherError::HandlerClosed.
+            let (updates_tx, rx) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+            drop(rx);
+            let tx = HandlerSender::new(updates_tx, Default::default());
+            let inspected = IntGauge::new(
+                "updates_inspected_for_double",
+                "Number of updates inspected for double",
+            )
+            .unwrap();
+            let sync_tasks_retained = metrics
+                .new_int_gauge_vec(
+                    "watcher_sync_tasks_retained",
+                    "retained sync tasks",
+                    &["home", "replica", "state"],
+                )
+                .unwrap();
+            let mut history_sync = HistorySync::new(
+                3,
+                second_root,
+                tx,
+                home,
+                inspected,
+                watch::channel(false).1,
+                "home_1",
+                SyncTaskRetention::new(DEFAULT_RETENTION, sync_tasks_retained),
+                nomad_db.clone(),
+            );
+
+            let res = history_sync.update_history().await;
+            assert!(res.is_err(), "Should have failed to send");
+
+            // The cursor must be rolled back to the checkpoint, not left
+            // advanced past the update the handler never actually received.
+            assert_eq!(history_sync.committed_root, second_root);
+            assert_eq!(load_cursor(&nomad_db, "home_1").unwrap(), None);
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn update_handler_detects_double_update() {
+        test_utils::run_test_db(|db| async move {
+            let signer: LocalWallet =
+                "1111111111111111111111111111111111111111111111111111111111111111"
+                    .parse()
+                    .unwrap();
+            let updater = signer.address();
+
+            let first_root = H256::from([1; 32]);
+            let second_root = H256::from([2; 32]);
+            let third_root = H256::from([3; 32]);
+            let bad_third_root = H256::from([4; 32]);
+
+            let first_update = Update {
+                home_domain: 1,
+                previous_root: first_root,
+                new_root: second_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let second_update = Update {
+                home_domain: 1,
+                previous_root: second_root,
+                new_root: third_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let bad_second_update = Update {
+                home_domain: 1,
+                previous_root: second_root,
+                new_root: bad_third_root,
+            }
+            .sign_with(&signer)
+            .await
+            .expect("!sign");
+
+            let metrics = Arc::new(
+                CoreMetrics::new(
+                    "contract_sync_test",
+                    "home",
+                    None,
+                    Arc::new(prometheus::Registry::new()),
+                )
+                .expect("could not make metrics"),
+            );
+            let sync_metrics = ContractSyncMetrics::new(metrics);
+
+            let mut mock_home = MockHomeContract::new();
+            mock_home.expect__name().return_const("home_1".to_owned());
+
+            let nomad_db = NomadDB::new("home_1_watcher", db);
+            let home_indexer: Arc<HomeIndexers> = Arc::new(MockIndexer::new().into());
+            let home_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "home_1".to_owned(),
+                "replica_1".to_owned(),
+                nomad_db.clone(),
+                home_indexer,
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics,
+            );
+
+            let home: Arc<CachingHome> =
+                CachingHome::new(mock_home.into(), home_sync, nomad_db.clone()).into();
+
+            let (_tx, rx) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+            let mut handler = UpdateHandler {
+                rx,
+                watcher_db: nomad_db,
+                home,
+                updater,
+                shutdown: watch::channel(false).1,
+                cause: Default::default(),
+                lagged_updates: IntGauge::new("lagged_updates", "Number of lagged updates")
+                    .unwrap(),
+            };
 
-            // First update_history call returns first -> second update
-            history_sync
-                .update_history()
-                .await
-                .expect("Should have received Ok(())");
+            handler
+                .check_double_update(&first_update)
+                .expect("Update should have been valid");
 
-            assert_eq!(history_sync.committed_root, first_root);
-            assert_eq!(rx.recv().await.unwrap(), second_signed_update);
+            handler
+                .check_double_update(&second_update)
+                .expect("Update should have been valid");
 
-            // Second update_history call returns zero -> first update
-            // and should return WatcherError::SyncingFinished
-            let res = history_sync.update_history().await;
+            let bad_second_update_ret = handler
+                .check_double_update(&bad_second_update)
+                .expect_err("Update should have been invalid");
             assert_eq!(
-                res.unwrap_err().to_string(),
-                WatcherError::SyncingFinished.to_string(),
-                "Should have received WatcherError::SyncingFinished"
+                bad_second_update_ret,
+                DoubleUpdate(second_update, bad_second_update)
             );
-
-            assert_eq!(history_sync.committed_root, zero_root);
-            assert_eq!(rx.recv().await.unwrap(), first_signed_update)
         })
         .await
     }
 
     #[tokio::test]
-    async fn update_handler_detects_double_update() {
+    async fn update_handler_acts_on_queued_double_update_during_shutdown() {
         test_utils::run_test_db(|db| async move {
-            let signer: LocalWallet =
+            let updater: LocalWallet =
                 "1111111111111111111111111111111111111111111111111111111111111111"
                     .parse()
                     .unwrap();
-            let updater = signer.address();
 
             let first_root = H256::from([1; 32]);
             let second_root = H256::from([2; 32]);
-            let third_root = H256::from([3; 32]);
-            let bad_third_root = H256::from([4; 32]);
+            let bad_second_root = H256::from([3; 32]);
 
             let first_update = Update {
                 home_domain: 1,
                 previous_root: first_root,
                 new_root: second_root,
             }
-            .sign_with(&signer)
-            .await
-            .expect("!sign");
-
-            let second_update = Update {
-                home_domain: 1,
-                previous_root: second_root,
-                new_root: third_root,
-            }
-            .sign_with(&signer)
+            .sign_with(&updater)
             .await
             .expect("!sign");
 
-            let bad_second_update = Update {
+            let bad_update = Update {
                 home_domain: 1,
-                previous_root: second_root,
-                new_root: bad_third_root,
+                previous_root: first_root,
+                new_root: bad_second_root,
             }
-            .sign_with(&signer)
+            .sign_with(&updater)
             .await
             .expect("!sign");
 
@@ -975,6 +2455,14 @@ mod test {
 
             let mut mock_home = MockHomeContract::new();
             mock_home.expect__name().return_const("home_1".to_owned());
+            // Neither queued update's previous root matches the chain's
+            // current committed root, so apply_and_check's own submission
+            // attempt is skipped and only the fraud check below is at
+            // stake here.
+            mock_home
+                .expect__committed_root()
+                .times(2)
+                .returning(|| Ok(H256::zero()));
 
             let nomad_db = NomadDB::new("home_1_watcher", db);
             let home_indexer: Arc<HomeIndexers> = Arc::new(MockIndexer::new().into());
@@ -993,29 +2481,34 @@ mod test {
             let home: Arc<CachingHome> =
                 CachingHome::new(mock_home.into(), home_sync, nomad_db.clone()).into();
 
-            let (_tx, rx) = mpsc::channel(200);
+            let (tx, rx) = broadcast::channel(UPDATE_STREAM_CAPACITY);
+
+            // Queue both halves of the double update before the handler is
+            // ever polled, then request shutdown immediately -- this should
+            // still be drained and detected rather than dropped on the
+            // floor when the agent is asked to exit.
+            tx.send(first_update.clone()).unwrap();
+            tx.send(bad_update.clone()).unwrap();
+
+            let (shutdown_tx, shutdown_rx) = watch::channel(false);
+            shutdown_tx.send(true).expect("receiver still alive");
+
             let mut handler = UpdateHandler {
                 rx,
                 watcher_db: nomad_db,
                 home,
-                updater,
+                updater: updater.address().into(),
+                shutdown: shutdown_rx,
+                cause: Default::default(),
+                lagged_updates: IntGauge::new("lagged_updates", "Number of lagged updates")
+                    .unwrap(),
             };
 
-            handler
-                .check_double_update(&first_update)
-                .expect("Update should have been valid");
-
-            handler
-                .check_double_update(&second_update)
-                .expect("Update should have been valid");
-
-            let bad_second_update_ret = handler
-                .check_double_update(&bad_second_update)
-                .expect_err("Update should have been invalid");
-            assert_eq!(
-                bad_second_update_ret,
-                DoubleUpdate(second_update, bad_second_update)
-            );
+            let double = handler
+                .run()
+                .await
+                .expect("queued double update should still be detected during shutdown");
+            assert_eq!(double, DoubleUpdate(first_update, bad_update));
         })
         .await
     }
@@ -1261,9 +2754,27 @@ mod test {
                 };
 
                 {
-                    let watcher =
-                        Watcher::new(updater.into(), 1, connection_managers.clone(), core);
-                    watcher.handle_double_update_failure(&double).await;
+                    let mut domains = HashMap::new();
+                    domains.insert(home_domain, (home.clone(), connection_managers.clone()));
+                    let watcher = Watcher::new(updater.into(), 1, domains, core);
+                    let domain = watcher.domains.get(&home_domain).unwrap();
+                    let ctx = RunContext::new(domain.home.name(), watcher.shutdown.subscribe());
+                    watcher
+                        .handle_double_update_failure(domain, &double, &ctx)
+                        .await
+                        .expect("!handle_double_update_failure");
+
+                    // Drain every durably-enqueued job (home + 2 replica
+                    // double updates, 2 connection-manager failure
+                    // notifications) to actually trigger the mock
+                    // expectations set up above.
+                    for _ in 0..5 {
+                        assert!(domain
+                            .fraud_queue
+                            .drain_one(domain)
+                            .await
+                            .expect("!drain_one"));
+                    }
                 }
 
                 // Checkpoint connection managers
@@ -1456,7 +2967,9 @@ mod test {
                     ),
                 };
 
-                let watcher = Watcher::new(updater.into(), 1, connection_managers.clone(), core);
+                let mut domains = HashMap::new();
+                domains.insert(home_domain, (home.clone(), connection_managers.clone()));
+                let watcher = Watcher::new(updater.into(), 1, domains, core);
                 let state = watcher
                     .watch_home_fail(1)
                     .await
@@ -1468,7 +2981,22 @@ mod test {
 
                 assert!(matches!(state, BaseError::FailedHome));
 
-                watcher.handle_improper_update_failure().await;
+                let domain = watcher.domains.get(&home_domain).unwrap();
+                let ctx = RunContext::new(domain.home.name(), watcher.shutdown.subscribe());
+                watcher
+                    .handle_improper_update_failure(domain, &ctx)
+                    .await
+                    .expect("!handle_improper_update_failure");
+
+                // Drain the 2 durably-enqueued connection-manager failure
+                // notification jobs to trigger the mock expectations.
+                for _ in 0..2 {
+                    assert!(domain
+                        .fraud_queue
+                        .drain_one(domain)
+                        .await
+                        .expect("!drain_one"));
+                }
             }
 
             // Checkpoint connection managers
@@ -1483,4 +3011,280 @@ mod test {
         })
         .await
     }
+
+    /// A double update on one home's domain must only ever enqueue
+    /// failure notifications against the connection managers enrolled
+    /// for *that* domain, never against a second, unrelated home's
+    /// connection managers also being watched by the same `Watcher`
+    /// instance. (Replicas are still shared across every domain in this
+    /// snapshot -- see the doc comment on `Domain` -- so both homes'
+    /// replicas are notified either way.)
+    #[tokio::test]
+    async fn it_isolates_connection_manager_targeting_to_the_affected_domain() {
+        test_utils::run_test_db(|db| async move {
+            let domain_1 = 1;
+            let domain_2 = 2;
+
+            let updater: LocalWallet =
+                "1111111111111111111111111111111111111111111111111111111111111111"
+                    .parse()
+                    .unwrap();
+
+            // Double update setup, on home_1's domain only
+            let first_root = H256::from([1; 32]);
+            let second_root = H256::from([2; 32]);
+            let bad_second_root = H256::from([3; 32]);
+
+            let update = Update {
+                home_domain: domain_1,
+                previous_root: first_root,
+                new_root: second_root,
+            }
+            .sign_with(&updater)
+            .await
+            .expect("!sign");
+
+            let bad_update = Update {
+                home_domain: domain_1,
+                previous_root: first_root,
+                new_root: bad_second_root,
+            }
+            .sign_with(&updater)
+            .await
+            .expect("!sign");
+
+            let double = DoubleUpdate(update, bad_update);
+            let signed_failure = FailureNotification {
+                home_domain: domain_1,
+                updater: updater.address().into(),
+            }
+            .sign_with(&updater)
+            .await
+            .expect("!sign");
+
+            // home_1's domain: home + replica + connection manager all
+            // expect exactly the calls a double update on this domain
+            // should trigger.
+            let mut mock_connection_manager_1 = MockConnectionManagerContract::new();
+            let mut mock_home_1 = MockHomeContract::new();
+            let mut mock_replica_1 = MockReplicaContract::new();
+            {
+                mock_home_1.expect__name().return_const("home_1".to_owned());
+                mock_home_1
+                    .expect__local_domain()
+                    .times(1)
+                    .return_once(move || domain_1);
+                let updater_clone = updater.clone();
+                mock_home_1
+                    .expect__updater()
+                    .times(1)
+                    .return_once(move || Ok(updater_clone.address().into()));
+                let double = double.clone();
+                mock_home_1
+                    .expect__double_update()
+                    .withf(move |d: &DoubleUpdate| *d == double)
+                    .times(1)
+                    .return_once(move |_| Ok(TxOutcome { txid: H256::default() }));
+            }
+            {
+                mock_replica_1
+                    .expect__name()
+                    .return_const("replica_1".to_owned());
+                let double = double.clone();
+                mock_replica_1
+                    .expect__double_update()
+                    .withf(move |d: &DoubleUpdate| *d == double)
+                    .times(1)
+                    .return_once(move |_| Ok(TxOutcome { txid: H256::default() }));
+            }
+            {
+                mock_connection_manager_1
+                    .expect__unenroll_replica()
+                    .withf(move |f: &SignedFailureNotification| *f == signed_failure)
+                    .times(1)
+                    .return_once(move |_| Ok(TxOutcome { txid: H256::default() }));
+            }
+
+            // home_2's domain: its replica is still shared, so it's
+            // notified of the double update like any other replica, but
+            // its connection manager has no expectations at all -- any
+            // call into it is a bug in how the double update was routed.
+            let mut mock_home_2 = MockHomeContract::new();
+            mock_home_2.expect__name().return_const("home_2".to_owned());
+            let mut mock_replica_2 = MockReplicaContract::new();
+            mock_replica_2
+                .expect__name()
+                .return_const("replica_2".to_owned());
+            {
+                let double = double.clone();
+                mock_replica_2
+                    .expect__double_update()
+                    .withf(move |d: &DoubleUpdate| *d == double)
+                    .times(1)
+                    .return_once(move |_| Ok(TxOutcome { txid: H256::default() }));
+            }
+            let mock_connection_manager_2 = MockConnectionManagerContract::new();
+
+            let mut connection_managers_1: Vec<Arc<ConnectionManagers>> =
+                vec![Arc::new(mock_connection_manager_1.into())];
+            let mut connection_managers_2: Vec<Arc<ConnectionManagers>> =
+                vec![Arc::new(mock_connection_manager_2.into())];
+
+            let metrics = Arc::new(
+                CoreMetrics::new(
+                    "contract_sync_test",
+                    "home",
+                    None,
+                    Arc::new(prometheus::Registry::new()),
+                )
+                .expect("could not make metrics"),
+            );
+            let sync_metrics = ContractSyncMetrics::new(metrics.clone());
+
+            let home_indexer: Arc<HomeIndexers> = Arc::new(MockIndexer::new().into());
+            let replica_indexer: Arc<CommonIndexers> = Arc::new(MockIndexer::new().into());
+
+            let mut mock_home_1: Homes = mock_home_1.into();
+            let mut mock_home_2: Homes = mock_home_2.into();
+            let mut mock_replica_1: Replicas = mock_replica_1.into();
+            let mut mock_replica_2: Replicas = mock_replica_2.into();
+
+            let home_1_db = NomadDB::new("home_1", db.clone());
+            let replica_1_db = NomadDB::new("replica_1", db.clone());
+            let home_2_db = NomadDB::new("home_2", db.clone());
+            let replica_2_db = NomadDB::new("replica_2", db.clone());
+
+            let home_1_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "home_1".to_owned(),
+                "replica_1".to_owned(),
+                home_1_db.clone(),
+                home_indexer.clone(),
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics.clone(),
+            );
+            let replica_1_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "replica_1".to_owned(),
+                "replica_1".to_owned(),
+                replica_1_db.clone(),
+                replica_indexer.clone(),
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics.clone(),
+            );
+            let home_2_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "home_2".to_owned(),
+                "replica_2".to_owned(),
+                home_2_db.clone(),
+                home_indexer.clone(),
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics.clone(),
+            );
+            let replica_2_sync = ContractSync::new(
+                AGENT_NAME.to_owned(),
+                "replica_2".to_owned(),
+                "replica_2".to_owned(),
+                replica_2_db.clone(),
+                replica_indexer.clone(),
+                IndexSettings::default(),
+                PageSettings::default(),
+                Default::default(),
+                sync_metrics.clone(),
+            );
+
+            {
+                let home_1: Arc<CachingHome> =
+                    CachingHome::new(mock_home_1.clone(), home_1_sync, home_1_db.clone()).into();
+                let replica_1: Arc<CachingReplica> =
+                    CachingReplica::new(mock_replica_1.clone(), replica_1_sync, replica_1_db.clone())
+                        .into();
+                let home_2: Arc<CachingHome> =
+                    CachingHome::new(mock_home_2.clone(), home_2_sync, home_2_db.clone()).into();
+                let replica_2: Arc<CachingReplica> =
+                    CachingReplica::new(mock_replica_2.clone(), replica_2_sync, replica_2_db.clone())
+                        .into();
+
+                let mut replica_map: HashMap<String, Arc<CachingReplica>> = HashMap::new();
+                replica_map.insert("replica_1".into(), replica_1);
+                replica_map.insert("replica_2".into(), replica_2);
+
+                let core = AgentCore {
+                    home: home_1.clone(),
+                    replicas: replica_map,
+                    db,
+                    indexer: IndexSettings::default(),
+                    settings: nomad_base::Settings::default(),
+                    metrics: Arc::new(
+                        nomad_base::CoreMetrics::new(
+                            "watcher_test",
+                            "home",
+                            None,
+                            Arc::new(prometheus::Registry::new()),
+                        )
+                        .expect("could not make metrics"),
+                    ),
+                };
+
+                let mut domains = HashMap::new();
+                domains.insert(domain_1, (home_1, connection_managers_1.clone()));
+                domains.insert(domain_2, (home_2, connection_managers_2.clone()));
+
+                {
+                    let watcher = Watcher::new(updater.into(), 1, domains, core);
+                    let domain_1 = watcher.domains.get(&1).unwrap();
+                    let domain_2 = watcher.domains.get(&2).unwrap();
+
+                    let ctx = RunContext::new(domain_1.home.name(), watcher.shutdown.subscribe());
+                    watcher
+                        .handle_double_update_failure(domain_1, &double, &ctx)
+                        .await
+                        .expect("!handle_double_update_failure");
+
+                    // home_1's queue has the home + both (shared) replica
+                    // double updates and domain_1's own connection-manager
+                    // failure notification enqueued; draining it triggers
+                    // exactly those mock expectations, including on
+                    // home_2's replica.
+                    for _ in 0..4 {
+                        assert!(domain_1
+                            .fraud_queue
+                            .drain_one(domain_1)
+                            .await
+                            .expect("!drain_one"));
+                    }
+
+                    // domain_2's own queue never had anything enqueued
+                    // against it -- its connection manager was correctly
+                    // left alone -- so there's nothing left to drain.
+                    assert!(!domain_2
+                        .fraud_queue
+                        .drain_one(domain_2)
+                        .await
+                        .expect("!drain_one"));
+                }
+
+                // Checkpoint connection managers
+                for connection_manager in connection_managers_1.iter_mut() {
+                    Arc::get_mut(connection_manager).unwrap().checkpoint();
+                }
+                for connection_manager in connection_managers_2.iter_mut() {
+                    Arc::get_mut(connection_manager).unwrap().checkpoint();
+                }
+            }
+
+            // Checkpoint homes and replicas
+            Arc::get_mut(&mut mock_home_1).unwrap().checkpoint();
+            Arc::get_mut(&mut mock_home_2).unwrap().checkpoint();
+            Arc::get_mut(&mut mock_replica_1).unwrap().checkpoint();
+            Arc::get_mut(&mut mock_replica_2).unwrap().checkpoint();
+        })
+        .await
+    }
 }
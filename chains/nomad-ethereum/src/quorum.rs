@@ -0,0 +1,216 @@
+#![allow(clippy::enum_variant_names)]
+#![allow(missing_docs)]
+
+use async_trait::async_trait;
+use ethers::providers::{Http, JsonRpcClient, Provider, ProviderError};
+use futures_util::future::join_all;
+use nomad_xyz_configuration::chains::QuorumPolicy;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::time::Duration;
+use thiserror::Error;
+use url::Url;
+
+/// Relative trust given to one endpoint in a [`QuorumJsonRpcClient`] when
+/// tallying matching responses. Plain majority quorum gives every endpoint
+/// equal weight; operators that trust one provider more than others can
+/// give it a higher weight instead.
+pub type Weight = u64;
+
+/// Default per-endpoint timeout, so one dead or hanging RPC can't stall the
+/// whole quorum.
+const DEFAULT_ENDPOINT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returned when no bucket of identical responses reached the configured
+/// quorum weight -- either too many endpoints disagreed, or too many
+/// timed out/errored to reach `threshold` even in agreement.
+#[derive(Debug, Error)]
+#[error("quorum of {threshold} not met across {endpoints} endpoint(s); best agreeing weight was {best_weight}")]
+pub struct QuorumNotMet {
+    /// Summed weight required to accept a response.
+    pub threshold: Weight,
+    /// Number of endpoints configured.
+    pub endpoints: usize,
+    /// Summed weight of the largest bucket of identical responses seen.
+    pub best_weight: Weight,
+}
+
+/// A [`JsonRpcClient`] that fans every request out to its configured
+/// endpoints and only returns the response whose summed [`Weight`] reaches
+/// `threshold`, rather than trusting any single endpoint.
+///
+/// Backing an `ethers::providers::Provider` with this client makes *every*
+/// read made through it -- contract calls like `is_replica` /
+/// `watcher_permission`, or indexer calls like `get_block_number` and log
+/// queries -- quorum-checked transparently, without each call site needing
+/// its own fan-out logic. Meant to sit under the endpoints listed per
+/// network in `NomadConfig.rpcs`.
+pub struct QuorumJsonRpcClient<C> {
+    endpoints: Vec<(Weight, C)>,
+    threshold: Weight,
+    endpoint_timeout: Duration,
+}
+
+impl<C> std::fmt::Debug for QuorumJsonRpcClient<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuorumJsonRpcClient")
+            .field("endpoints", &self.endpoints.len())
+            .field("threshold", &self.threshold)
+            .field("endpoint_timeout", &self.endpoint_timeout)
+            .finish()
+    }
+}
+
+impl<C> QuorumJsonRpcClient<C> {
+    /// Build a quorum client over `endpoints` requiring `threshold` summed
+    /// weight to agree, with the default per-endpoint timeout.
+    ///
+    /// # Panics
+    /// Panics if `endpoints` is empty, or if `threshold` is zero or exceeds
+    /// the total weight of all endpoints.
+    pub fn new(endpoints: Vec<(Weight, C)>, threshold: Weight) -> Self {
+        let total: Weight = endpoints.iter().map(|(weight, _)| weight).sum();
+        assert!(
+            !endpoints.is_empty(),
+            "quorum client needs at least one endpoint"
+        );
+        assert!(
+            threshold >= 1 && threshold <= total,
+            "quorum threshold must be between 1 and the endpoints' total weight"
+        );
+
+        Self {
+            endpoints,
+            threshold,
+            endpoint_timeout: DEFAULT_ENDPOINT_TIMEOUT,
+        }
+    }
+
+    /// Build a quorum client requiring a strict majority of `endpoints`'
+    /// summed weight to agree -- the default when no explicit threshold is
+    /// configured.
+    pub fn majority(endpoints: Vec<(Weight, C)>) -> Self {
+        let total: Weight = endpoints.iter().map(|(weight, _)| weight).sum();
+        Self::new(endpoints, total / 2 + 1)
+    }
+
+    /// Build a quorum client implementing `policy` over `endpoints`, e.g. a
+    /// [`nomad_xyz_configuration::chains::Connection::Quorum`]'s providers
+    /// and policy.
+    pub fn with_policy(endpoints: Vec<(Weight, C)>, policy: QuorumPolicy) -> Self {
+        let total: Weight = endpoints.iter().map(|(weight, _)| weight).sum();
+        let threshold = match policy {
+            QuorumPolicy::Majority => total / 2 + 1,
+            QuorumPolicy::All => total,
+            QuorumPolicy::Weighted { minimum } => minimum,
+        };
+        Self::new(endpoints, threshold)
+    }
+
+    /// Override the per-endpoint request timeout (default 10s).
+    pub fn with_endpoint_timeout(mut self, timeout: Duration) -> Self {
+        self.endpoint_timeout = timeout;
+        self
+    }
+}
+
+impl QuorumJsonRpcClient<Http> {
+    /// Build an equally-weighted, majority-quorum `Provider` over `urls`,
+    /// e.g. the endpoints listed for one network under `NomadConfig.rpcs`.
+    pub fn majority_provider(
+        urls: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Provider<Self>, url::ParseError> {
+        let endpoints = urls
+            .into_iter()
+            .map(|url| Ok((1, Http::new(Url::parse(url.as_ref())?))))
+            .collect::<Result<Vec<_>, url::ParseError>>()?;
+
+        Ok(Provider::new(Self::majority(endpoints)))
+    }
+
+    /// Build a `Provider` implementing a
+    /// [`nomad_xyz_configuration::chains::Connection::Quorum`]'s `providers`
+    /// and `quorum` policy over plain HTTP endpoints.
+    pub fn provider_for_quorum_connection(
+        providers: &[nomad_xyz_configuration::chains::WeightedProvider],
+        policy: QuorumPolicy,
+    ) -> Result<Provider<Self>, url::ParseError> {
+        let endpoints = providers
+            .iter()
+            .map(|provider| Ok((provider.weight, Http::new(Url::parse(&provider.rpc)?))))
+            .collect::<Result<Vec<_>, url::ParseError>>()?;
+
+        Ok(Provider::new(Self::with_policy(endpoints, policy)))
+    }
+}
+
+#[async_trait]
+impl<C> JsonRpcClient for QuorumJsonRpcClient<C>
+where
+    C: JsonRpcClient + 'static,
+{
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        // Serialize once up front so every endpoint gets the identical
+        // request regardless of `T`, and so responses can be bucketed as
+        // `serde_json::Value` without requiring `R: Eq`/`Hash`.
+        let params = serde_json::to_value(params).map_err(ProviderError::SerdeJson)?;
+
+        let responses = join_all(self.endpoints.iter().map(|(weight, client)| {
+            let params = params.clone();
+            let weight = *weight;
+            async move {
+                match tokio::time::timeout(
+                    self.endpoint_timeout,
+                    client.request::<_, Value>(method, params),
+                )
+                .await
+                {
+                    Ok(Ok(value)) => Some((weight, value)),
+                    Ok(Err(error)) => {
+                        tracing::warn!(%method, %error, "Quorum endpoint returned an error");
+                        None
+                    }
+                    Err(_) => {
+                        tracing::warn!(%method, timeout = ?self.endpoint_timeout, "Quorum endpoint timed out");
+                        None
+                    }
+                }
+            }
+        }))
+        .await;
+
+        // Canonical JSON equality (serde_json::Value's Map comparison
+        // ignores key order) is enough to bucket identical responses
+        // without any bespoke normalization.
+        let mut buckets: Vec<(Value, Weight)> = Vec::new();
+        for (weight, value) in responses.into_iter().flatten() {
+            match buckets.iter_mut().find(|(bucketed, _)| *bucketed == value) {
+                Some((_, total)) => *total += weight,
+                None => buckets.push((value, weight)),
+            }
+        }
+
+        let best = buckets.iter().max_by_key(|(_, weight)| *weight);
+
+        match best {
+            Some((value, weight)) if *weight >= self.threshold => {
+                serde_json::from_value(value.clone()).map_err(ProviderError::SerdeJson)
+            }
+            _ => Err(ProviderError::CustomError(
+                QuorumNotMet {
+                    threshold: self.threshold,
+                    endpoints: self.endpoints.len(),
+                    best_weight: best.map(|(_, weight)| *weight).unwrap_or_default(),
+                }
+                .to_string(),
+            )),
+        }
+    }
+}
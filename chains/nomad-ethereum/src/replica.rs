@@ -6,16 +6,17 @@ use color_eyre::Result;
 use ethers::core::types::{Signature, H256, U256};
 use futures_util::future::join_all;
 use nomad_core::{
-    accumulator::NomadProof, Common, CommonIndexer, ContractLocator, DoubleUpdate, Encode,
-    MessageStatus, NomadMessage, Replica, SignedUpdate, SignedUpdateWithMeta, State, TxOutcome,
-    Update, UpdateMeta,
+    accumulator::NomadProof, Claim, Common, CommonIndexer, Completion, ContractLocator,
+    DoubleUpdate, Encode, MessageStatus, NomadMessage, Replica, SignedUpdate, SignedUpdateWithMeta,
+    State, TxOutcome, Update, UpdateMeta,
 };
 use nomad_xyz_configuration::ReplicaGasLimits;
 use std::{convert::TryFrom, sync::Arc};
 use tracing::instrument;
 
 use crate::{
-    bindings::replica::Replica as EthereumReplicaInternal, utils, EthereumError, TxSubmitter,
+    bindings::replica::Replica as EthereumReplicaInternal, submitter::ReplicaFeeLimits, utils,
+    EthereumError, TxSubmitter,
 };
 
 #[derive(Debug)]
@@ -132,6 +133,121 @@ where
     }
 }
 
+/// An [`EthereumReplicaIndexer`] backed by `n` independent read providers,
+/// requiring `threshold`-of-`n` agreement on the set of updates for a given
+/// block range before returning it. Guards against a single faulty or
+/// malicious RPC endpoint silently omitting or fabricating `Update` events,
+/// which would otherwise be a safety issue for the updater/watcher agents.
+#[derive(Debug)]
+pub struct QuorumEthereumReplicaIndexer<R>
+where
+    R: ethers::providers::Middleware + 'static,
+{
+    indexers: Vec<EthereumReplicaIndexer<R>>,
+    threshold: usize,
+}
+
+impl<R> QuorumEthereumReplicaIndexer<R>
+where
+    R: ethers::providers::Middleware + 'static,
+{
+    /// Create a quorum indexer over `providers`, requiring `threshold` of
+    /// them to agree before any read is accepted.
+    ///
+    /// # Panics
+    /// Panics if `threshold` is zero or exceeds the number of providers.
+    pub fn new(
+        providers: Vec<Arc<R>>,
+        locator: &ContractLocator,
+        threshold: usize,
+    ) -> Self {
+        assert!(
+            threshold >= 1 && threshold <= providers.len(),
+            "quorum threshold must be between 1 and the number of providers"
+        );
+        let indexers = providers
+            .into_iter()
+            .map(|provider| EthereumReplicaIndexer::new(provider, locator))
+            .collect();
+        Self { indexers, threshold }
+    }
+}
+
+#[async_trait]
+impl<R> CommonIndexer for QuorumEthereumReplicaIndexer<R>
+where
+    R: ethers::providers::Middleware + 'static,
+{
+    type Error = EthereumError;
+
+    #[instrument(err, skip(self))]
+    async fn get_block_number(&self) -> Result<u32, Self::Error> {
+        let mut heights: Vec<u32> = join_all(self.indexers.iter().map(|i| i.get_block_number()))
+            .await
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect();
+
+        if heights.len() < self.threshold {
+            return Err(EthereumError::QuorumNotMet {
+                responses: heights.len(),
+                threshold: self.threshold,
+            });
+        }
+
+        // Conservative: the threshold-th-highest height is one that at
+        // least `threshold` endpoints have already reached.
+        heights.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(heights[self.threshold - 1])
+    }
+
+    #[instrument(err, skip(self))]
+    async fn fetch_sorted_updates(
+        &self,
+        from: u32,
+        to: u32,
+    ) -> Result<Vec<SignedUpdateWithMeta>, Self::Error> {
+        let responses: Vec<Vec<SignedUpdateWithMeta>> =
+            join_all(self.indexers.iter().map(|i| i.fetch_sorted_updates(from, to)))
+                .await
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, result)| match result {
+                    Ok(updates) => Some(updates),
+                    Err(e) => {
+                        tracing::warn!(provider = i, from, to, error = %e, "Discarding erroring replica update provider");
+                        None
+                    }
+                })
+                .collect();
+
+        let best = responses
+            .iter()
+            .map(|candidate| {
+                let count = responses.iter().filter(|other| *other == candidate).count();
+                (candidate, count)
+            })
+            .max_by_key(|(_, count)| *count);
+
+        match best {
+            Some((candidate, count)) if count >= self.threshold => Ok(candidate.clone()),
+            _ => {
+                tracing::warn!(
+                    from,
+                    to,
+                    responses = responses.len(),
+                    threshold = self.threshold,
+                    "Replica update quorum not met for block range"
+                );
+                Err(EthereumError::QuorumNotMet {
+                    responses: responses.len(),
+                    threshold: self.threshold,
+                })
+            }
+        }
+    }
+}
+
 /// A struct that provides access to an Ethereum replica contract
 #[derive(Debug)]
 pub struct EthereumReplica<W, R>
@@ -144,6 +260,7 @@ where
     domain: u32,
     name: String,
     gas: Option<ReplicaGasLimits>,
+    fee: Option<ReplicaFeeLimits>,
 }
 
 impl<W, R> EthereumReplica<W, R>
@@ -162,6 +279,7 @@ where
             address,
         }: &ContractLocator,
         gas: Option<ReplicaGasLimits>,
+        fee: Option<ReplicaFeeLimits>,
     ) -> Self {
         tracing::info!(
             address = ?address.as_ethereum_address(),
@@ -178,6 +296,7 @@ where
             domain: *domain,
             name: name.to_owned(),
             gas,
+            fee,
         }
     }
 }
@@ -212,13 +331,21 @@ where
 
     #[tracing::instrument(err)]
     async fn status(&self, txid: H256) -> Result<Option<TxOutcome>, Self::Error> {
-        self.contract
+        let receipt = self
+            .contract
             .client()
             .get_transaction_receipt(txid)
             .await
-            .map_err(|e| EthereumError::MiddlewareError(e.into()))?
-            .map(utils::try_transaction_receipt_to_tx_outcome)
-            .transpose()
+            .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+
+        match receipt {
+            Some(receipt) => {
+                crate::submitter::decode_receipt(self.contract.client().as_ref(), receipt)
+                    .await
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
     #[tracing::instrument(err)]
@@ -255,7 +382,12 @@ where
         }
 
         self.submitter
-            .submit(self.domain, self.contract.address(), tx.tx)
+            .submit_with_fee_cap(
+                self.domain,
+                self.contract.address(),
+                tx.tx,
+                self.fee.map(|f| f.update),
+            )
             .await
     }
 
@@ -299,7 +431,12 @@ where
         }
 
         self.submitter
-            .submit(self.domain, self.contract.address(), tx.tx)
+            .submit_with_fee_cap(
+                self.domain,
+                self.contract.address(),
+                tx.tx,
+                self.fee.map(|f| f.prove),
+            )
             .await
     }
 
@@ -312,7 +449,12 @@ where
         }
 
         self.submitter
-            .submit(self.domain, self.contract.address(), tx.tx)
+            .submit_with_fee_cap(
+                self.domain,
+                self.contract.address(),
+                tx.tx,
+                self.fee.map(|f| f.process),
+            )
             .await
     }
 
@@ -338,7 +480,12 @@ where
         }
 
         self.submitter
-            .submit(self.domain, self.contract.address(), tx.tx)
+            .submit_with_fee_cap(
+                self.domain,
+                self.contract.address(),
+                tx.tx,
+                self.fee.map(|f| f.prove_and_process),
+            )
             .await
     }
 
@@ -351,3 +498,109 @@ where
         Ok(self.contract.acceptable_root(root.into()).call().await?)
     }
 }
+
+impl<W, R> EthereumReplica<W, R>
+where
+    W: ethers::providers::Middleware + 'static,
+    R: ethers::providers::Middleware + 'static,
+{
+    /// As [`Replica::process`], but returns a [`Completion`] handle keyed
+    /// on the message's leaf reaching `MessageStatus::Processed`, instead
+    /// of blocking on the receipt of the exact transaction this call
+    /// submits -- useful when the submitter's gas escalator may land a
+    /// resubmission under a different txid.
+    pub async fn process_with_completion(
+        &self,
+        message: &NomadMessage,
+    ) -> Result<EthereumCompletion<R>, EthereumError> {
+        self.process(message).await?;
+        Ok(self.completion(Claim::MessageProcessed(message.to_leaf())))
+    }
+
+    /// As [`Replica::prove_and_process`], returning a [`Completion`] handle
+    /// for the same reason as [`Self::process_with_completion`].
+    pub async fn prove_and_process_with_completion(
+        &self,
+        message: &NomadMessage,
+        proof: &NomadProof,
+    ) -> Result<EthereumCompletion<R>, EthereumError> {
+        self.prove_and_process(message, proof).await?;
+        Ok(self.completion(Claim::MessageProcessed(message.to_leaf())))
+    }
+
+    /// As [`Common::update`], returning a [`Completion`] handle for the
+    /// submitted root becoming `acceptable_root`.
+    pub async fn update_with_completion(
+        &self,
+        update: &SignedUpdate,
+    ) -> Result<EthereumCompletion<R>, EthereumError> {
+        self.update(update).await?;
+        Ok(self.completion(Claim::AcceptableRoot(update.update.new_root)))
+    }
+
+    fn completion(&self, claim: Claim) -> EthereumCompletion<R> {
+        EthereumCompletion {
+            contract: self.contract.clone(),
+            claim,
+        }
+    }
+}
+
+/// A [`Completion`] handle for [`EthereumReplica`] that re-checks its
+/// [`Claim`] against live contract state rather than a specific receipt.
+#[derive(Debug)]
+pub struct EthereumCompletion<R>
+where
+    R: ethers::providers::Middleware + 'static,
+{
+    contract: Arc<EthereumReplicaInternal<R>>,
+    claim: Claim,
+}
+
+#[async_trait]
+impl<R> Completion for EthereumCompletion<R>
+where
+    R: ethers::providers::Middleware + 'static,
+{
+    type Error = EthereumError;
+
+    fn claim(&self) -> &Claim {
+        &self.claim
+    }
+
+    #[tracing::instrument(err, skip(self))]
+    async fn confirm(&self) -> Result<Option<TxOutcome>, Self::Error> {
+        let satisfied = match self.claim {
+            Claim::MessageProcessed(leaf) => {
+                let status: MessageStatus =
+                    self.contract.messages(leaf.into()).call().await?.into();
+                matches!(status, MessageStatus::Processed)
+            }
+            Claim::AcceptableRoot(root) => self.contract.acceptable_root(root.into()).call().await?,
+            Claim::Receipt(txid) => {
+                let receipt = self
+                    .contract
+                    .client()
+                    .get_transaction_receipt(txid)
+                    .await
+                    .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+
+                return match receipt {
+                    Some(receipt) => {
+                        crate::submitter::decode_receipt(self.contract.client().as_ref(), receipt)
+                            .await
+                            .map(Some)
+                    }
+                    None => Ok(None),
+                };
+            }
+        };
+
+        // State-based claims have no receipt of their own to surface --
+        // signal completion with a zeroed outcome rather than fabricating
+        // a txid that may not correspond to any one transaction.
+        Ok(satisfied.then(|| TxOutcome {
+            txid: Default::default(),
+        }))
+    }
+}
@@ -0,0 +1,147 @@
+#![allow(clippy::enum_variant_names)]
+#![allow(missing_docs)]
+
+use async_trait::async_trait;
+use ethers::providers::{Http, JsonRpcClient, Provider, ProviderError};
+use nomad_xyz_configuration::chains::RetryConfig;
+use rand::Rng;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::time::Duration;
+use tracing::warn;
+use url::Url;
+
+/// Retry-with-backoff policy for a [`RetryingJsonRpcClient`]. Mirrors
+/// [`nomad_xyz_configuration::chains::RetryConfig`], translated into
+/// [`Duration`] for use against real clocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries before giving up and surfacing the error.
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubles (with jitter) on each
+    /// subsequent attempt, unless the error carries its own `Retry-After`.
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryConfig::default().into()
+    }
+}
+
+impl From<RetryConfig> for RetryPolicy {
+    fn from(config: RetryConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+        }
+    }
+}
+
+/// A [`JsonRpcClient`] that wraps an inner client and retries requests that
+/// fail with a transient error -- HTTP 429, or a node-specific "rate
+/// limit"/"timeout" error body -- using exponential backoff with jitter,
+/// honoring a `Retry-After` hint when the inner error carries one. Errors
+/// that aren't transient (reverts, invalid params, ...) pass straight
+/// through unchanged, so genuine contract errors still fail fast.
+///
+/// Keeps a long-running indexer stable against flaky hosted RPC providers
+/// without needing to restart the whole indexing loop on a single dropped
+/// request.
+pub struct RetryingJsonRpcClient<C> {
+    inner: C,
+    policy: RetryPolicy,
+}
+
+impl<C> std::fmt::Debug for RetryingJsonRpcClient<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryingJsonRpcClient")
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<C> RetryingJsonRpcClient<C> {
+    /// Wrap `inner` with `policy`'s retry-with-backoff behavior.
+    pub fn new(inner: C, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+}
+
+impl RetryingJsonRpcClient<Http> {
+    /// Build a retrying `Provider` over a plain HTTP endpoint, e.g. the
+    /// endpoint named by a [`nomad_xyz_configuration::chains::Connection::Http`]'s
+    /// `rpc` and `retry` fields.
+    pub fn retrying_provider(
+        rpc: &str,
+        policy: RetryPolicy,
+    ) -> Result<Provider<Self>, url::ParseError> {
+        Ok(Provider::new(Self::new(Http::new(Url::parse(rpc)?), policy)))
+    }
+}
+
+/// Whether `error` looks like a transient condition worth retrying --
+/// rate limiting or a timeout -- as opposed to a error the caller should
+/// see immediately (revert, invalid params, malformed response, ...).
+fn is_retryable(error: &ProviderError) -> bool {
+    let message = error.to_string().to_lowercase();
+    message.contains("429")
+        || message.contains("too many requests")
+        || message.contains("rate limit")
+        || message.contains("timed out")
+        || message.contains("timeout")
+}
+
+/// Extract a `Retry-After: <seconds>` hint from `error`'s message, if the
+/// backend echoed one into its JSON-RPC error body. `ProviderError` doesn't
+/// carry the original HTTP response, so this is best-effort.
+fn retry_after(error: &ProviderError) -> Option<Duration> {
+    let message = error.to_string().to_lowercase();
+    let (_, after) = message.split_once("retry-after")?;
+    let digits: String = after
+        .trim_start_matches([':', ' '])
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    let seconds: u64 = digits.parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+fn backoff_with_jitter(initial_backoff: Duration, attempt: u32) -> Duration {
+    let base = initial_backoff.saturating_mul(1u32 << attempt.min(16));
+    let jitter_ms = rand::thread_rng().gen_range(0..=(base.as_millis() as u64 / 2).max(1));
+    base + Duration::from_millis(jitter_ms)
+}
+
+#[async_trait]
+impl<C> JsonRpcClient for RetryingJsonRpcClient<C>
+where
+    C: JsonRpcClient<Error = ProviderError> + 'static,
+{
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        // Serialize once so every retry submits the identical request
+        // without requiring `T: Clone`.
+        let params = serde_json::to_value(params).map_err(ProviderError::SerdeJson)?;
+
+        let mut attempt = 0;
+        loop {
+            match self.inner.request(method, params.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(error) if attempt < self.policy.max_retries && is_retryable(&error) => {
+                    let backoff = retry_after(&error)
+                        .unwrap_or_else(|| backoff_with_jitter(self.policy.initial_backoff, attempt));
+                    warn!(%method, attempt, ?backoff, %error, "Retrying transient RPC error");
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
@@ -0,0 +1,1486 @@
+#![allow(clippy::enum_variant_names)]
+#![allow(missing_docs)]
+
+use async_trait::async_trait;
+use ethers::core::types::{
+    transaction::{eip2718::TypedTransaction, eip712::Eip712},
+    Address, NameOrAddress, Signature, TransactionReceipt, TransactionRequest, U256,
+};
+use ethers::middleware::SignerMiddleware;
+use ethers::prelude::Eip1559TransactionRequest;
+use ethers::providers::Middleware;
+use ethers::signers::{HDPath as LedgerDerivationPath, Ledger, LocalWallet, Signer};
+use nomad_core::TxOutcome;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::instrument;
+
+use crate::{utils, EthereumError};
+
+/// Env var checked for a keystore passphrase before [`KeySource::load`]
+/// falls back to an interactive prompt.
+const KEY_PASSPHRASE_ENV_VAR: &str = "NOMAD_KEY_PASSPHRASE";
+
+/// On-disk key material format understood by [`KeySource::load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyFormat {
+    /// A password-encrypted Web3 Secret Storage ("UTC"/V3 keystore) JSON
+    /// file: scrypt KDF, AES-128-CTR, and a MAC over the ciphertext.
+    EncryptedJson,
+    /// A PEM-wrapped raw private key, for operators whose own tooling
+    /// (e.g. a secrets-manager sidecar) already keeps the file encrypted
+    /// at rest and just hands the agent a PEM envelope.
+    Pem,
+}
+
+/// A reference to key material on disk -- a path and format tag, not the
+/// key itself -- as stored in `AgentConfig` in place of a plaintext
+/// private key. [`KeySource::load`] sources the passphrase needed to
+/// unlock it separately, from [`KEY_PASSPHRASE_ENV_VAR`] or an interactive
+/// prompt, so it never has to live in config or the process environment
+/// alongside the reference.
+#[derive(Debug, Clone)]
+pub struct KeySource {
+    /// Path to the keystore/PEM file.
+    pub path: PathBuf,
+    /// Format the file is encoded in.
+    pub format: KeyFormat,
+}
+
+impl KeySource {
+    /// Reference an encrypted keystore JSON file at `path`.
+    pub fn encrypted_json(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: KeyFormat::EncryptedJson,
+        }
+    }
+
+    /// Reference a PEM-wrapped key file at `path`.
+    pub fn pem(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: KeyFormat::Pem,
+        }
+    }
+
+    /// Decrypt and load the wallet this reference points at.
+    pub fn load(&self) -> Result<LocalWallet, EthereumError> {
+        match self.format {
+            KeyFormat::EncryptedJson => {
+                let passphrase = Self::passphrase()?;
+                LocalWallet::decrypt_keystore(&self.path, passphrase)
+                    .map_err(|e| EthereumError::SignerError(e.to_string()))
+            }
+            KeyFormat::Pem => {
+                let contents = std::fs::read_to_string(&self.path)
+                    .map_err(|e| EthereumError::SignerError(e.to_string()))?;
+                let parsed = pem::parse(contents)
+                    .map_err(|e| EthereumError::SignerError(e.to_string()))?;
+                LocalWallet::from_bytes(&parsed.contents)
+                    .map_err(|e| EthereumError::SignerError(e.to_string()))
+            }
+        }
+    }
+
+    /// `NOMAD_KEY_PASSPHRASE` if set, otherwise prompt for it interactively.
+    fn passphrase() -> Result<String, EthereumError> {
+        if let Ok(passphrase) = std::env::var(KEY_PASSPHRASE_ENV_VAR) {
+            return Ok(passphrase);
+        }
+
+        rpassword::prompt_password("Keystore passphrase: ")
+            .map_err(|e| EthereumError::SignerError(e.to_string()))
+    }
+}
+
+/// Selects which signing backend backs a [`TxSubmitter`]'s write provider:
+/// an in-process private key, or a connected Ledger hardware wallet so the
+/// signing key never has to be loaded into the agent process.
+#[derive(Debug, Clone)]
+pub enum SignerBackend {
+    /// Sign locally with an in-process private key.
+    PrivateKey(LocalWallet),
+    /// Sign on a connected Ledger device at `account_index` under
+    /// `derivation`, via ethers' async Ledger transport.
+    Ledger {
+        /// Which account under `derivation` to use, e.g. `0` for the
+        /// first address.
+        account_index: usize,
+        /// BIP-44-style derivation scheme (Ledger Live, Legacy, etc).
+        derivation: LedgerDerivationPath,
+    },
+}
+
+impl SignerBackend {
+    /// Resolve this backend into a concrete [`EthereumSigner`] bound to
+    /// `chain_id`, opening a connection to the Ledger device if configured.
+    /// Baking in the chain id here (rather than per-call) gets EIP-155
+    /// replay protection applied uniformly to both legacy and typed
+    /// transactions signed through it.
+    pub async fn build(&self, chain_id: u64) -> Result<EthereumSigner, EthereumError> {
+        match self {
+            SignerBackend::PrivateKey(wallet) => Ok(EthereumSigner::PrivateKey(
+                wallet.clone().with_chain_id(chain_id),
+            )),
+            SignerBackend::Ledger {
+                account_index,
+                derivation,
+            } => {
+                let ledger = Ledger::new(derivation.clone(), *account_index)
+                    .await
+                    .map_err(|e| EthereumError::SignerError(e.to_string()))?;
+                Ok(EthereumSigner::Ledger(ledger.with_chain_id(chain_id)))
+            }
+        }
+    }
+
+    /// Build a [`SignerBackend::PrivateKey`] by decrypting `source` rather
+    /// than taking raw key material, so operators can keep the key
+    /// encrypted at rest in `AgentConfig` and rotate it by swapping the
+    /// referenced file.
+    pub fn from_keystore(source: &KeySource) -> Result<Self, EthereumError> {
+        Ok(Self::PrivateKey(source.load()?))
+    }
+}
+
+/// Either an in-process private key or a connected Ledger hardware wallet,
+/// dispatching [`Signer`] to whichever is configured. Lets [`TxSubmitter`]
+/// stay generic only over `M: Middleware`, without also needing to be
+/// generic over the signing backend.
+#[derive(Debug, Clone)]
+pub enum EthereumSigner {
+    /// Signs with an in-process private key.
+    PrivateKey(LocalWallet),
+    /// Signs on a connected Ledger hardware wallet.
+    Ledger(Ledger),
+}
+
+#[async_trait]
+impl Signer for EthereumSigner {
+    type Error = EthereumError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            Self::PrivateKey(wallet) => wallet
+                .sign_message(message)
+                .await
+                .map_err(|e| EthereumError::SignerError(e.to_string())),
+            Self::Ledger(ledger) => ledger
+                .sign_message(message)
+                .await
+                .map_err(|e| EthereumError::SignerError(e.to_string())),
+        }
+    }
+
+    async fn sign_transaction(
+        &self,
+        message: &TypedTransaction,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            Self::PrivateKey(wallet) => wallet
+                .sign_transaction(message)
+                .await
+                .map_err(|e| EthereumError::SignerError(e.to_string())),
+            Self::Ledger(ledger) => ledger
+                .sign_transaction(message)
+                .await
+                .map_err(|e| EthereumError::SignerError(e.to_string())),
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            Self::PrivateKey(wallet) => wallet
+                .sign_typed_data(payload)
+                .await
+                .map_err(|e| EthereumError::SignerError(e.to_string())),
+            Self::Ledger(ledger) => ledger
+                .sign_typed_data(payload)
+                .await
+                .map_err(|e| EthereumError::SignerError(e.to_string())),
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            Self::PrivateKey(wallet) => wallet.address(),
+            Self::Ledger(ledger) => ledger.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            Self::PrivateKey(wallet) => wallet.chain_id(),
+            Self::Ledger(ledger) => ledger.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            Self::PrivateKey(wallet) => Self::PrivateKey(wallet.with_chain_id(chain_id)),
+            Self::Ledger(ledger) => Self::Ledger(ledger.with_chain_id(chain_id)),
+        }
+    }
+}
+
+/// Turn a mined receipt into a [`TxOutcome`], or -- if the transaction
+/// reverted -- an [`EthereumError::Reverted`] carrying the decoded revert
+/// reason, fetched via `debug_traceTransaction` with an `eth_call` replay
+/// fallback for nodes that don't support tracing.
+pub(crate) async fn decode_receipt<M: Middleware + 'static>(
+    provider: &M,
+    mut receipt: TransactionReceipt,
+) -> Result<TxOutcome, EthereumError> {
+    if receipt.status == Some(0.into()) {
+        let reason = revert_reason(provider, &receipt).await;
+        return Err(EthereumError::Reverted {
+            txid: receipt.transaction_hash,
+            reason,
+        });
+    }
+
+    // Some clients (OpenEthereum/Parity, and occasionally older Besu)
+    // don't populate `effectiveGasPrice` on the receipt. Backfill it from
+    // the mined transaction's own `gasPrice` rather than let downstream
+    // outcome decoding see a zeroed fee.
+    if receipt.effective_gas_price.is_none() {
+        if let Ok(Some(tx)) = provider.get_transaction(receipt.transaction_hash).await {
+            receipt.effective_gas_price = Some(tx.gas_price.unwrap_or_default());
+        }
+    }
+
+    utils::try_transaction_receipt_to_tx_outcome(receipt)
+}
+
+/// Best-effort revert reason for a failed `receipt`. Returns `None` if
+/// neither tracing nor replay yields a usable reason.
+async fn revert_reason<M: Middleware + 'static>(
+    provider: &M,
+    receipt: &TransactionReceipt,
+) -> Option<String> {
+    #[derive(serde::Deserialize, Default)]
+    struct CallTrace {
+        error: Option<String>,
+    }
+
+    if let Ok(trace) = provider
+        .provider()
+        .request::<_, CallTrace>(
+            "debug_traceTransaction",
+            (
+                receipt.transaction_hash,
+                serde_json::json!({ "tracer": "callTracer" }),
+            ),
+        )
+        .await
+    {
+        if trace.error.is_some() {
+            return trace.error;
+        }
+    }
+
+    // Node doesn't support tracing (or the trace had no error message):
+    // replay the call at the block it was mined in. Most clients embed the
+    // decoded revert string (or custom error selector) in the RPC error.
+    let tx = provider
+        .get_transaction(receipt.transaction_hash)
+        .await
+        .ok()??;
+    let block = receipt.block_number?;
+
+    let call = TypedTransaction::Legacy(TransactionRequest {
+        from: Some(tx.from),
+        to: tx.to.map(NameOrAddress::Address),
+        gas: Some(tx.gas),
+        gas_price: tx.gas_price,
+        value: Some(tx.value),
+        data: Some(tx.input),
+        nonce: Some(tx.nonce),
+        chain_id: tx.chain_id.map(|id| id.as_u64().into()),
+    });
+
+    provider
+        .call(&call, Some(block.into()))
+        .await
+        .err()
+        .map(|e| e.to_string())
+}
+
+/// A per-method cap on `maxFeePerGas`, paired with the existing
+/// `*GasLimits` structs which only bound gas *limit*. Kept separate from
+/// the generated gas-limit config so that chains which never enable
+/// EIP-1559 don't need to carry fee fields around.
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeCap {
+    /// Ceiling on `maxFeePerGas`, in wei. `None` means uncapped.
+    pub max_fee_per_gas: Option<U256>,
+    /// Ceiling on `maxPriorityFeePerGas`, in wei. `None` means uncapped.
+    pub max_priority_fee_per_gas: Option<U256>,
+}
+
+/// Per-method `maxFeePerGas`/`maxPriorityFeePerGas` ceilings for a Replica,
+/// mirroring the shape of `nomad_xyz_configuration::ReplicaGasLimits`.
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaFeeLimits {
+    pub update: FeeCap,
+    pub prove: FeeCap,
+    pub process: FeeCap,
+    pub prove_and_process: FeeCap,
+}
+
+/// Determines how `TxSubmitter::submit` prices a transaction before sending
+/// it to the chain.
+#[derive(Debug, Clone)]
+pub enum FeeStrategy {
+    /// Use whatever gas price the node/middleware suggests for a legacy
+    /// transaction. Always safe, and the only option for pre-London chains.
+    Legacy,
+    /// Build an [`Eip1559TransactionRequest`], sourcing
+    /// `maxPriorityFeePerGas` from the configured value (or falling back to
+    /// `eth_maxPriorityFeePerGas`) and deriving `maxFeePerGas` from the
+    /// latest base fee, clamped by an optional [`FeeCap`].
+    Eip1559 {
+        /// Priority fee to offer, in wei. If `None`, queried from the node.
+        priority_fee: Option<U256>,
+        /// Optional access list to attach to the typed transaction.
+        access_list: Option<ethers::core::types::transaction::eip2930::AccessList>,
+    },
+}
+
+impl Default for FeeStrategy {
+    fn default() -> Self {
+        Self::Legacy
+    }
+}
+
+/// Geometric fee escalation for transactions that sit unmined for too long,
+/// mirroring ethers' gas-escalator middleware. After `blocks_before_bump`
+/// blocks without a receipt, the pending transaction is resubmitted at the
+/// same nonce with its fee multiplied by `factor`, up to `max_bumps` times.
+#[derive(Debug, Clone)]
+pub struct EscalatorConfig {
+    /// Blocks to wait for the current attempt to land before bumping.
+    pub blocks_before_bump: u64,
+    /// Multiplier applied to the previous attempt's fee on each bump.
+    pub factor: f64,
+    /// Maximum number of bumps before giving up and awaiting the last
+    /// submitted attempt instead of resubmitting again.
+    pub max_bumps: u32,
+    /// Absolute ceiling no bumped fee may exceed, regardless of `factor`.
+    pub fee_ceiling: Option<U256>,
+    /// How often to poll for a receipt while waiting on an attempt.
+    pub poll_interval: Duration,
+}
+
+impl Default for EscalatorConfig {
+    fn default() -> Self {
+        Self {
+            blocks_before_bump: 3,
+            factor: 1.125,
+            max_bumps: 5,
+            fee_ceiling: None,
+            poll_interval: Duration::from_secs(3),
+        }
+    }
+}
+
+impl EscalatorConfig {
+    /// Compute the next fee to resubmit at. Always strictly greater than
+    /// `fee`, *unless* `fee` has already reached (or passed) the
+    /// configured ceiling -- in that case `fee` is returned unchanged
+    /// rather than clamped down to the ceiling, since a resubmission at
+    /// an equal-or-lower fee is rejected by every node as a replacement
+    /// transaction. Callers resubmit only while `bump(fee) > fee`; once
+    /// that stops holding, the ceiling has been reached and there's
+    /// nothing left to do but await the last attempt already in flight.
+    fn bump(&self, fee: U256) -> U256 {
+        let bumped = U256::from((fee.as_u128() as f64 * self.factor) as u128);
+        let bumped = bumped.max(fee + 1);
+        match self.fee_ceiling {
+            Some(ceiling) if ceiling > fee => bumped.min(ceiling),
+            Some(_) => fee,
+            None => bumped,
+        }
+    }
+}
+
+/// Locally-tracked nonce for one signer address, refreshed from chain when
+/// stale or after a nonce-related submission error.
+#[derive(Debug, Clone, Copy)]
+struct TrackedNonce {
+    next: U256,
+    synced_at: Instant,
+}
+
+/// Hands out monotonically increasing nonces for each signer address,
+/// mirroring ethers' nonce-manager middleware. Avoids round-tripping to the
+/// node for every submission, which otherwise serializes or races when many
+/// transactions (e.g. concurrent `prove_and_process` calls) are in flight at
+/// once.
+#[derive(Debug, Clone, Default)]
+pub struct NonceManager {
+    tracked: Arc<Mutex<HashMap<Address, TrackedNonce>>>,
+    /// Resync from chain if the tracked nonce hasn't been touched in this
+    /// long, in case an external actor has also been submitting from `from`.
+    max_idle: Duration,
+}
+
+impl NonceManager {
+    /// Create a nonce manager that resyncs from chain after `max_idle` of
+    /// inactivity for a given address.
+    pub fn new(max_idle: Duration) -> Self {
+        Self {
+            tracked: Default::default(),
+            max_idle,
+        }
+    }
+
+    async fn next<M: Middleware + 'static>(
+        &self,
+        provider: &M,
+        from: Address,
+    ) -> Result<U256, EthereumError> {
+        let mut tracked = self.tracked.lock().await;
+        let now = Instant::now();
+
+        let needs_resync = match tracked.get(&from) {
+            Some(entry) => now.duration_since(entry.synced_at) >= self.max_idle,
+            None => true,
+        };
+
+        if needs_resync {
+            let on_chain = provider
+                .get_transaction_count(from, None)
+                .await
+                .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+            tracked.insert(
+                from,
+                TrackedNonce {
+                    next: on_chain,
+                    synced_at: now,
+                },
+            );
+        }
+
+        let entry = tracked.get_mut(&from).expect("just inserted if missing");
+        let nonce = entry.next;
+        entry.next += U256::one();
+        entry.synced_at = now;
+        Ok(nonce)
+    }
+
+    /// Force the next call to `next` for `from` to resync from chain,
+    /// e.g. after a "nonce too low"/"replacement underpriced" error.
+    pub async fn resync(&self, from: Address) {
+        self.tracked.lock().await.remove(&from);
+    }
+}
+
+/// Submits transactions to a single Ethereum-style chain on behalf of
+/// `EthereumHome`/`EthereumReplica`, applying the configured [`FeeStrategy`]
+/// and surfacing the resulting receipt as a [`TxOutcome`]. Optionally
+/// escalates and resubmits transactions that sit unmined too long, per
+/// [`EscalatorConfig`], and optionally assigns nonces locally via a
+/// [`NonceManager`] instead of relying on the node.
+#[derive(Debug, Clone)]
+pub struct TxSubmitter<M> {
+    provider: Arc<M>,
+    fee_strategy: FeeStrategy,
+    escalator: Option<EscalatorConfig>,
+    nonce_manager: Option<NonceManager>,
+    node_client: NodeClient,
+}
+
+impl<M> TxSubmitter<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a new submitter over `provider`, pricing transactions
+    /// according to `fee_strategy`, with no fee escalation.
+    pub fn new(provider: Arc<M>, fee_strategy: FeeStrategy) -> Self {
+        Self {
+            provider,
+            fee_strategy,
+            escalator: None,
+            nonce_manager: None,
+            node_client: NodeClient::Unknown,
+        }
+    }
+
+    /// Record the execution client [`detect_node_client`] found for this
+    /// submitter's chain, so it can be used to choose a pricing strategy
+    /// and surfaces in this submitter's tracing spans for correlating tx
+    /// failures with a specific backend.
+    pub fn with_node_client(mut self, node_client: NodeClient) -> Self {
+        self.node_client = node_client;
+        self
+    }
+
+    /// Create a new submitter that always uses legacy gas pricing.
+    pub fn legacy(provider: Arc<M>) -> Self {
+        Self::new(provider, FeeStrategy::Legacy)
+    }
+
+    /// Wrap `provider` in a [`SignerMiddleware`] signing via `backend` --
+    /// an in-process private key or a connected Ledger hardware wallet --
+    /// and build a submitter over it. Queries `provider` for the chain id
+    /// so the resulting signer applies correct EIP-155 replay protection.
+    pub async fn with_signer(
+        provider: M,
+        fee_strategy: FeeStrategy,
+        backend: SignerBackend,
+    ) -> Result<TxSubmitter<SignerMiddleware<M, EthereumSigner>>, EthereumError> {
+        let chain_id = provider
+            .get_chainid()
+            .await
+            .map_err(|e| EthereumError::MiddlewareError(e.into()))?
+            .as_u64();
+        let signer = backend.build(chain_id).await?;
+        let node_client = detect_node_client(&provider).await;
+        let provider = Arc::new(SignerMiddleware::new(provider, signer));
+
+        Ok(TxSubmitter::new(provider, fee_strategy).with_node_client(node_client))
+    }
+
+    /// As [`Self::with_signer`], but layers the write path through
+    /// [`GasOracleMiddleware`] and [`NonceManagerMiddleware`] before signing,
+    /// mirroring [`submission_middleware_stack`]'s `provider -> gas-oracle ->
+    /// nonce-manager -> signer` order instead of relying solely on this
+    /// submitter's own [`FeeStrategy`] pricing and [`Self::with_nonce_manager`].
+    /// Lets multiple submitters sharing one signer address (e.g. Home and
+    /// Replica on the same chain) hand out nonces from the same tracked
+    /// `nonce_manager` rather than each racing `get_transaction_count`
+    /// against the node independently.
+    pub async fn with_layered_signer(
+        provider: M,
+        fee_strategy: FeeStrategy,
+        backend: SignerBackend,
+        network: NetworkSpecs,
+        gas_multiplier: f64,
+        nonce_manager: NonceManager,
+    ) -> Result<
+        TxSubmitter<SignerMiddleware<NonceManagerMiddleware<GasOracleMiddleware<M>>, EthereumSigner>>,
+        EthereumError,
+    > {
+        let chain_id = provider
+            .get_chainid()
+            .await
+            .map_err(|e| EthereumError::MiddlewareError(e.into()))?
+            .as_u64();
+        let signer = backend.build(chain_id).await?;
+        let node_client = detect_node_client(&provider).await;
+
+        let layered = submission_middleware_stack(provider, network, gas_multiplier, nonce_manager);
+        let provider = Arc::new(SignerMiddleware::new(layered, signer));
+
+        Ok(TxSubmitter::new(provider, fee_strategy).with_node_client(node_client))
+    }
+
+    /// Enable gas-escalator resubmission for stuck transactions.
+    pub fn with_escalator(mut self, escalator: EscalatorConfig) -> Self {
+        self.escalator = Some(escalator);
+        self
+    }
+
+    /// Enable local nonce tracking instead of relying on the node to assign
+    /// nonces for each submission.
+    pub fn with_nonce_manager(mut self, nonce_manager: NonceManager) -> Self {
+        self.nonce_manager = Some(nonce_manager);
+        self
+    }
+
+    /// Convert `tx` to an EIP-1559 request if the configured strategy calls
+    /// for one, clamping fee fields against `cap`. Falls back to the
+    /// untouched legacy request when the chain/strategy doesn't support
+    /// type-2 transactions.
+    #[instrument(err, skip(self, tx), level = "debug")]
+    async fn price(
+        &self,
+        tx: TypedTransaction,
+        cap: Option<FeeCap>,
+    ) -> Result<TypedTransaction, EthereumError> {
+        let (priority_fee, access_list) = match &self.fee_strategy {
+            FeeStrategy::Legacy => return Ok(tx),
+            FeeStrategy::Eip1559 {
+                priority_fee,
+                access_list,
+            } => (*priority_fee, access_list.clone()),
+        };
+
+        let priority_fee = match priority_fee {
+            Some(fee) => fee,
+            None if self.node_client.supports_fee_history() => {
+                match fee_history_priority_fee(self.provider.as_ref()).await {
+                    Some(fee) => fee,
+                    None => self
+                        .provider
+                        .provider()
+                        .request::<_, U256>("eth_maxPriorityFeePerGas", ())
+                        .await
+                        .map_err(|e| EthereumError::MiddlewareError(e.into()))?,
+                }
+            }
+            None => self
+                .provider
+                .provider()
+                .request::<_, U256>("eth_maxPriorityFeePerGas", ())
+                .await
+                .map_err(|e| EthereumError::MiddlewareError(e.into()))?,
+        };
+
+        let base_fee = self
+            .provider
+            .get_block(ethers::core::types::BlockNumber::Pending)
+            .await
+            .map_err(|e| EthereumError::MiddlewareError(e.into()))?
+            .and_then(|b| b.base_fee_per_gas);
+
+        let base_fee = match base_fee {
+            Some(fee) => fee,
+            // Node didn't return a base fee (pre-London or non-compliant
+            // client) -- fall back to legacy pricing for this tx.
+            None => return Ok(tx),
+        };
+
+        let mut max_fee = base_fee.saturating_mul(2.into()) + priority_fee;
+        let mut priority_fee = priority_fee;
+
+        if let Some(cap) = cap {
+            if let Some(ceiling) = cap.max_fee_per_gas {
+                max_fee = max_fee.min(ceiling);
+            }
+            if let Some(ceiling) = cap.max_priority_fee_per_gas {
+                priority_fee = priority_fee.min(ceiling);
+            }
+        }
+
+        // Clamping each field against its own ceiling independently can
+        // leave max_fee < priority_fee (e.g. a tight max_fee_per_gas cap
+        // with no matching max_priority_fee_per_gas cap) -- every node
+        // rejects that as an invalid EIP-1559 transaction, so bring
+        // max_fee back up to priority_fee rather than submit something
+        // guaranteed to fail.
+        max_fee = max_fee.max(priority_fee);
+
+        let mut eip1559 = Eip1559TransactionRequest {
+            from: tx.from().copied(),
+            to: tx.to().cloned(),
+            gas: tx.gas().copied(),
+            value: tx.value().copied(),
+            data: tx.data().cloned(),
+            nonce: tx.nonce().copied(),
+            access_list: access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: Some(priority_fee),
+            max_fee_per_gas: Some(max_fee),
+            chain_id: tx.chain_id(),
+        };
+        // `TypedTransaction::to` may be unset for contract creation; leave
+        // it out rather than defaulting to the zero address.
+        if eip1559.to.is_none() {
+            eip1559.to = None::<NameOrAddress>;
+        }
+
+        Ok(TypedTransaction::Eip1559(eip1559))
+    }
+
+    /// Submit `tx` to `domain`'s chain at `contract_address`, pricing it
+    /// per the configured [`FeeStrategy`], and await its receipt.
+    #[instrument(err, skip(self, tx), fields(node_client = ?self.node_client))]
+    pub async fn submit(
+        &self,
+        domain: u32,
+        contract_address: Address,
+        tx: TypedTransaction,
+    ) -> Result<TxOutcome, EthereumError> {
+        self.submit_with_fee_cap(domain, contract_address, tx, None)
+            .await
+    }
+
+    /// As [`Self::submit`], but clamps any EIP-1559 fee fields against
+    /// `cap` before sending. Callers pass a method-specific cap (e.g. from
+    /// `ReplicaFeeLimits`) so operators can bound `maxFeePerGas` per call
+    /// the same way `*GasLimits` bounds the gas limit.
+    #[instrument(err, skip(self, tx), fields(node_client = ?self.node_client))]
+    pub async fn submit_with_fee_cap(
+        &self,
+        domain: u32,
+        contract_address: Address,
+        tx: TypedTransaction,
+        cap: Option<FeeCap>,
+    ) -> Result<TxOutcome, EthereumError> {
+        let legacy_tx = tx.clone();
+        let mut tx = match self.price(tx, cap).await {
+            Ok(tx) => tx,
+            Err(e) => {
+                tracing::warn!(
+                    domain,
+                    contract = ?contract_address,
+                    error = %e,
+                    "Falling back to legacy pricing after fee strategy failed"
+                );
+                legacy_tx
+            }
+        };
+
+        if let Some(nonce_manager) = &self.nonce_manager {
+            let from = tx.from().copied().unwrap_or_default();
+            let nonce = nonce_manager.next(self.provider.as_ref(), from).await?;
+            tx.set_nonce(nonce);
+
+            let result = match &self.escalator {
+                Some(escalator) => self.send_with_escalation(tx, escalator.clone()).await,
+                None => self.send_once(tx).await,
+            };
+
+            if let Err(e) = &result {
+                if is_nonce_error(e) {
+                    tracing::warn!(domain, contract = ?contract_address, error = %e, "Resyncing nonce after submission error");
+                    nonce_manager.resync(from).await;
+                }
+            }
+
+            return result;
+        }
+
+        match &self.escalator {
+            Some(escalator) => self.send_with_escalation(tx, escalator.clone()).await,
+            None => self.send_once(tx).await,
+        }
+    }
+
+    /// Send `tx` once and await its receipt, with no escalation.
+    async fn send_once(&self, tx: TypedTransaction) -> Result<TxOutcome, EthereumError> {
+        let pending = self
+            .provider
+            .send_transaction(tx, None)
+            .await
+            .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+
+        let receipt = pending
+            .await
+            .map_err(|e| EthereumError::MiddlewareError(e.into()))?
+            .ok_or(EthereumError::TxNotIncluded)?;
+
+        decode_receipt(self.provider.as_ref(), receipt).await
+    }
+
+    /// Send `tx`, bumping its fee and resubmitting at the same nonce if it
+    /// sits unmined for longer than `escalator.blocks_before_bump` blocks,
+    /// until it lands or `escalator.max_bumps` is reached.
+    async fn send_with_escalation(
+        &self,
+        mut tx: TypedTransaction,
+        escalator: EscalatorConfig,
+    ) -> Result<TxOutcome, EthereumError> {
+        // Escalation resubmits at a fixed nonce, so one must be set even if
+        // no `NonceManager` is configured -- otherwise each resubmission
+        // would get a fresh nonce from the node instead of replacing the
+        // prior attempt.
+        if tx.nonce().is_none() {
+            let from = tx.from().copied().unwrap_or_default();
+            let nonce = self
+                .provider
+                .get_transaction_count(from, None)
+                .await
+                .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+            tx.set_nonce(nonce);
+        }
+
+        for bump in 0..=escalator.max_bumps {
+            let submitted_at = self
+                .provider
+                .get_block_number()
+                .await
+                .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+
+            let pending = self
+                .provider
+                .send_transaction(tx.clone(), None)
+                .await
+                .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+            let tx_hash = *pending;
+
+            let is_last_attempt = bump == escalator.max_bumps;
+            loop {
+                if let Some(receipt) = self
+                    .provider
+                    .get_transaction_receipt(tx_hash)
+                    .await
+                    .map_err(|e| EthereumError::MiddlewareError(e.into()))?
+                {
+                    return decode_receipt(self.provider.as_ref(), receipt).await;
+                }
+
+                if is_last_attempt {
+                    // Out of bumps: wait out the final attempt instead of
+                    // resubmitting again.
+                    let receipt = pending
+                        .await
+                        .map_err(|e| EthereumError::MiddlewareError(e.into()))?
+                        .ok_or(EthereumError::TxNotIncluded)?;
+                    return decode_receipt(self.provider.as_ref(), receipt).await;
+                }
+
+                let current_block = self
+                    .provider
+                    .get_block_number()
+                    .await
+                    .map_err(|e| EthereumError::MiddlewareError(e.into()))?;
+                if current_block.saturating_sub(submitted_at).as_u64()
+                    >= escalator.blocks_before_bump
+                {
+                    break;
+                }
+
+                tokio::time::sleep(escalator.poll_interval).await;
+            }
+
+            let current_fee = match &tx {
+                TypedTransaction::Eip1559(inner) => inner.max_fee_per_gas.unwrap_or_default(),
+                TypedTransaction::Legacy(inner) => inner.gas_price.unwrap_or_default(),
+                TypedTransaction::Eip2930(inner) => inner.tx.gas_price.unwrap_or_default(),
+            };
+            let bumped = escalator.bump(current_fee);
+
+            if bumped <= current_fee {
+                // Reached the fee ceiling: resubmitting would resend an
+                // equal-or-lower fee, which every node rejects as a
+                // replacement transaction, so there's no point trying --
+                // give up escalating and await the attempt already in
+                // flight instead, same as running out of bumps.
+                tracing::warn!(
+                    tx_hash = ?tx_hash,
+                    fee = ?current_fee,
+                    "Reached fee ceiling -- awaiting last attempt instead of bumping further"
+                );
+                let receipt = pending
+                    .await
+                    .map_err(|e| EthereumError::MiddlewareError(e.into()))?
+                    .ok_or(EthereumError::TxNotIncluded)?;
+                return decode_receipt(self.provider.as_ref(), receipt).await;
+            }
+
+            match &mut tx {
+                TypedTransaction::Eip1559(inner) => {
+                    tracing::info!(
+                        tx_hash = ?tx_hash,
+                        bump = bump + 1,
+                        new_max_fee_per_gas = ?bumped,
+                        "Escalating stuck transaction's max fee per gas"
+                    );
+                    inner.max_fee_per_gas = Some(bumped);
+                }
+                TypedTransaction::Legacy(inner) => {
+                    tracing::info!(
+                        tx_hash = ?tx_hash,
+                        bump = bump + 1,
+                        new_gas_price = ?bumped,
+                        "Escalating stuck transaction's gas price"
+                    );
+                    inner.gas_price = Some(bumped);
+                }
+                TypedTransaction::Eip2930(inner) => {
+                    tracing::info!(
+                        tx_hash = ?tx_hash,
+                        bump = bump + 1,
+                        new_gas_price = ?bumped,
+                        "Escalating stuck transaction's gas price"
+                    );
+                    inner.tx.gas_price = Some(bumped);
+                }
+            }
+        }
+
+        unreachable!("loop always returns on or before the final attempt")
+    }
+}
+
+/// Whether `err` looks like a nonce-gap failure ("nonce too low",
+/// "replacement underpriced") that should trigger a `NonceManager` resync
+/// rather than just being surfaced to the caller.
+fn is_nonce_error(err: &EthereumError) -> bool {
+    looks_like_nonce_error(&err.to_string())
+}
+
+/// Whether a stringified submission error looks like a nonce-gap failure.
+/// Shared by [`is_nonce_error`] and [`NonceManagerMiddleware`], which can't
+/// use [`EthereumError`] directly since it wraps an arbitrary `M::Error`.
+fn looks_like_nonce_error(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    msg.contains("nonce") || msg.contains("replacement transaction underpriced")
+}
+
+/// Execution-client family, detected from the `web3_clientVersion` string a
+/// node reports. Behavior varies by client beyond the static
+/// [`NetworkSpecs::supports_1559`] flag -- txpool semantics, `fee_history`
+/// support, and which receipt fields get populated all differ -- so callers
+/// that need to work around a specific client's quirks match on this rather
+/// than re-deriving it from the version string themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeClient {
+    Geth,
+    Erigon,
+    OpenEthereum,
+    Nethermind,
+    Besu,
+    /// Either `web3_clientVersion` didn't match a known prefix, or
+    /// detection wasn't run.
+    #[default]
+    Unknown,
+}
+
+impl NodeClient {
+    /// Classify the client family out of a `web3_clientVersion` response,
+    /// e.g. `Geth/v1.10.26-stable/linux-amd64/go1.19`.
+    fn from_version_string(version: &str) -> Self {
+        let family = version
+            .split('/')
+            .next()
+            .unwrap_or(version)
+            .to_ascii_lowercase();
+
+        if family.contains("geth") {
+            Self::Geth
+        } else if family.contains("erigon") {
+            Self::Erigon
+        } else if family.contains("openethereum") || family.contains("parity") {
+            Self::OpenEthereum
+        } else if family.contains("nethermind") {
+            Self::Nethermind
+        } else if family.contains("besu") {
+            Self::Besu
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether this client's `eth_feeHistory` is trustworthy enough to
+    /// derive a priority fee from, rather than falling back to
+    /// `eth_maxPriorityFeePerGas`. OpenEthereum/Parity never shipped
+    /// EIP-1559 support, and an unidentified client hasn't earned the
+    /// benefit of the doubt.
+    fn supports_fee_history(&self) -> bool {
+        matches!(
+            self,
+            Self::Geth | Self::Erigon | Self::Nethermind | Self::Besu
+        )
+    }
+
+    /// Whether receipts from this client reliably populate
+    /// `effectiveGasPrice`. OpenEthereum/Parity's receipts predate the
+    /// field.
+    pub fn supports_effective_gas_price(&self) -> bool {
+        !matches!(self, Self::OpenEthereum)
+    }
+}
+
+/// Call `web3_clientVersion` on `provider` and classify the response into a
+/// [`NodeClient`]. Meant to run once at startup and be stored alongside the
+/// provider it was detected against; falls back to [`NodeClient::Unknown`]
+/// (no fee-history-based priority fee, no client-specific workarounds) if the node doesn't
+/// answer rather than failing startup over it.
+pub async fn detect_node_client<M: Middleware + 'static>(provider: &M) -> NodeClient {
+    match provider
+        .provider()
+        .request::<_, String>("web3_clientVersion", ())
+        .await
+    {
+        Ok(version) => {
+            let client = NodeClient::from_version_string(&version);
+            tracing::info!(version, ?client, "Detected execution client");
+            client
+        }
+        Err(error) => {
+            tracing::warn!(%error, "Could not detect execution client via web3_clientVersion");
+            NodeClient::Unknown
+        }
+    }
+}
+
+/// Per-chain flags consulted by [`GasOracleMiddleware`] to decide how to
+/// price a transaction.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NetworkSpecs {
+    /// Whether the chain accepts EIP-1559 (type-2) transactions. When
+    /// `false`, [`GasOracleMiddleware`] always prices with legacy
+    /// `gasPrice` instead.
+    pub supports_1559: bool,
+    /// Execution client detected for this network via
+    /// [`detect_node_client`], consulted for client-specific pricing and
+    /// receipt-handling quirks.
+    pub node_client: NodeClient,
+}
+
+/// Error surfaced by [`GasOracleMiddleware`]: always a pass-through from
+/// the wrapped middleware, since pricing itself only ever falls back to
+/// defaults rather than failing.
+#[derive(Debug, thiserror::Error)]
+pub enum GasOracleMiddlewareError<M: Middleware> {
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> ethers::providers::MiddlewareError for GasOracleMiddlewareError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        Self::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            Self::MiddlewareError(inner) => Some(inner),
+        }
+    }
+}
+
+/// Blocks of reward history sampled by [`fee_history_priority_fee`].
+/// Wider than one block so a single block's outlier reward doesn't swing
+/// the derived priority fee.
+const FEE_HISTORY_BLOCK_WINDOW: u64 = 10;
+/// Reward percentile requested from `eth_feeHistory`.
+const FEE_HISTORY_REWARD_PERCENTILE: f64 = 50.0;
+/// Floor on the priority fee derived from fee history, so a node reporting
+/// all-zero rewards doesn't leave a transaction underpriced for inclusion.
+const MIN_PRIORITY_FEE_WEI: u64 = 1_000_000_000;
+/// Ceiling on the priority fee derived from fee history, so a handful of
+/// outlier blocks can't blow out the fee this submitter offers.
+const MAX_PRIORITY_FEE_WEI: u64 = 100_000_000_000;
+
+/// Median priority fee over the last [`FEE_HISTORY_BLOCK_WINDOW`] mined
+/// blocks, via `eth_feeHistory`, clamped to
+/// `[MIN_PRIORITY_FEE_WEI, MAX_PRIORITY_FEE_WEI]`. Returns `None` if the
+/// call fails or the node reports no reward data, leaving the caller to
+/// fall back to `eth_maxPriorityFeePerGas`.
+async fn fee_history_priority_fee<M: Middleware>(provider: &M) -> Option<U256> {
+    #[derive(serde::Deserialize)]
+    struct FeeHistory {
+        reward: Vec<Vec<U256>>,
+    }
+
+    let history: FeeHistory = provider
+        .provider()
+        .request(
+            "eth_feeHistory",
+            (
+                FEE_HISTORY_BLOCK_WINDOW,
+                "pending",
+                [FEE_HISTORY_REWARD_PERCENTILE],
+            ),
+        )
+        .await
+        .ok()?;
+
+    let mut rewards: Vec<U256> = history.reward.into_iter().flatten().collect();
+    if rewards.is_empty() {
+        return None;
+    }
+    rewards.sort();
+    let median = rewards[rewards.len() / 2];
+
+    Some(median.clamp(MIN_PRIORITY_FEE_WEI.into(), MAX_PRIORITY_FEE_WEI.into()))
+}
+
+/// Gas-pricing middleware layer: fills `maxFeePerGas`/
+/// `maxPriorityFeePerGas` on any transaction that doesn't already have one
+/// set (or legacy `gasPrice`, when `network.supports_1559` is `false`),
+/// scaled by `multiplier` over the node's suggested price. A `multiplier`
+/// above `1.0` lets a caller that's replacing or escalating a stuck
+/// transaction ask this layer for a richer price outright, rather than
+/// deriving one itself.
+#[derive(Debug, Clone)]
+pub struct GasOracleMiddleware<M> {
+    inner: M,
+    network: NetworkSpecs,
+    multiplier: f64,
+}
+
+impl<M> GasOracleMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Wrap `inner`, pricing transactions for `network` with `multiplier`
+    /// applied over the node's suggested fee (`1.0` for no adjustment).
+    pub fn new(inner: M, network: NetworkSpecs, multiplier: f64) -> Self {
+        Self {
+            inner,
+            network,
+            multiplier,
+        }
+    }
+
+    fn scale(&self, fee: U256) -> U256 {
+        U256::from((fee.as_u128() as f64 * self.multiplier) as u128)
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for GasOracleMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = GasOracleMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn fill_transaction(
+        &self,
+        tx: &mut TypedTransaction,
+        block: Option<ethers::core::types::BlockId>,
+    ) -> Result<(), Self::Error> {
+        if self.network.supports_1559 {
+            if let TypedTransaction::Eip1559(inner) = tx {
+                if inner.max_fee_per_gas.is_none() {
+                    let priority_fee = match self.network.node_client.supports_fee_history() {
+                        true => fee_history_priority_fee(&self.inner).await.unwrap_or(
+                            self.inner
+                                .provider()
+                                .request::<_, U256>("eth_maxPriorityFeePerGas", ())
+                                .await
+                                .unwrap_or_default(),
+                        ),
+                        false => self
+                            .inner
+                            .provider()
+                            .request::<_, U256>("eth_maxPriorityFeePerGas", ())
+                            .await
+                            .unwrap_or_default(),
+                    };
+
+                    let base_fee = self
+                        .inner
+                        .get_block(ethers::core::types::BlockNumber::Pending)
+                        .await
+                        .map_err(GasOracleMiddlewareError::MiddlewareError)?
+                        .and_then(|b| b.base_fee_per_gas)
+                        .unwrap_or_default();
+
+                    inner.max_priority_fee_per_gas = Some(self.scale(priority_fee));
+                    inner.max_fee_per_gas =
+                        Some(self.scale(base_fee.saturating_mul(2.into()) + priority_fee));
+                }
+            }
+        } else if tx.gas_price().is_none() {
+            let gas_price = self
+                .inner
+                .get_gas_price()
+                .await
+                .map_err(GasOracleMiddlewareError::MiddlewareError)?;
+            tx.set_gas_price(self.scale(gas_price));
+        }
+
+        self.inner
+            .fill_transaction(tx, block)
+            .await
+            .map_err(GasOracleMiddlewareError::MiddlewareError)
+    }
+}
+
+/// Error surfaced by [`NonceManagerMiddleware`]: either a pass-through
+/// failure from the wrapped middleware, or one raised by the
+/// [`NonceManager`] itself while resyncing against chain state.
+#[derive(Debug, thiserror::Error)]
+pub enum NonceManagerMiddlewareError<M: Middleware> {
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+    #[error(transparent)]
+    NonceManagerError(#[from] EthereumError),
+}
+
+impl<M: Middleware> ethers::providers::MiddlewareError for NonceManagerMiddlewareError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        Self::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            Self::MiddlewareError(inner) => Some(inner),
+            Self::NonceManagerError(_) => None,
+        }
+    }
+}
+
+/// Nonce-management middleware layer built on [`NonceManager`]: assigns the
+/// next locally-tracked nonce to any transaction that doesn't already have
+/// one, and resyncs from `get_transaction_count` when submission fails
+/// with what looks like a nonce-gap error.
+#[derive(Debug, Clone)]
+pub struct NonceManagerMiddleware<M> {
+    inner: M,
+    nonce_manager: NonceManager,
+}
+
+impl<M> NonceManagerMiddleware<M>
+where
+    M: Middleware + 'static,
+{
+    /// Wrap `inner`, assigning nonces out of `nonce_manager`.
+    pub fn new(inner: M, nonce_manager: NonceManager) -> Self {
+        Self {
+            inner,
+            nonce_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for NonceManagerMiddleware<M>
+where
+    M: Middleware + 'static,
+{
+    type Error = NonceManagerMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn fill_transaction(
+        &self,
+        tx: &mut TypedTransaction,
+        block: Option<ethers::core::types::BlockId>,
+    ) -> Result<(), Self::Error> {
+        if tx.nonce().is_none() {
+            let from = tx.from().copied().unwrap_or_default();
+            let nonce = self.nonce_manager.next(&self.inner, from).await?;
+            tx.set_nonce(nonce);
+        }
+
+        self.inner
+            .fill_transaction(tx, block)
+            .await
+            .map_err(NonceManagerMiddlewareError::MiddlewareError)
+    }
+
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<ethers::core::types::BlockId>,
+    ) -> Result<ethers::providers::PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let mut tx = tx.into();
+        if tx.nonce().is_none() {
+            let from = tx.from().copied().unwrap_or_default();
+            let nonce = self.nonce_manager.next(&self.inner, from).await?;
+            tx.set_nonce(nonce);
+        }
+
+        let from = tx.from().copied().unwrap_or_default();
+        match self.inner.send_transaction(tx, block).await {
+            Ok(pending) => Ok(pending),
+            Err(error) => {
+                if looks_like_nonce_error(&error.to_string()) {
+                    tracing::warn!(?from, %error, "Resyncing nonce after submission error");
+                    self.nonce_manager.resync(from).await;
+                }
+                Err(NonceManagerMiddlewareError::MiddlewareError(error))
+            }
+        }
+    }
+}
+
+/// Layer `provider` with a gas oracle and a local nonce manager, mirroring
+/// the standard `provider -> gas-oracle -> nonce-manager -> signer` ethers
+/// middleware stack. Intended for write paths -- like
+/// `EthereumConnectionManager`'s `owner_enroll_replica` /
+/// `set_watcher_permission` / `unenroll_replica` -- that submit directly
+/// against a bare provider instead of through [`TxSubmitter`], so they stop
+/// depending entirely on the node for nonce assignment and gas pricing.
+/// [`TxSubmitter::with_layered_signer`] applies this same stack underneath a
+/// submitter's signer, for write paths that do go through [`TxSubmitter`].
+pub fn submission_middleware_stack<M>(
+    provider: M,
+    network: NetworkSpecs,
+    gas_multiplier: f64,
+    nonce_manager: NonceManager,
+) -> NonceManagerMiddleware<GasOracleMiddleware<M>>
+where
+    M: Middleware + 'static,
+{
+    NonceManagerMiddleware::new(
+        GasOracleMiddleware::new(provider, network, gas_multiplier),
+        nonce_manager,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ethers::providers::{MockProvider, Provider};
+
+    fn mock_block_json(base_fee_per_gas: U256) -> serde_json::Value {
+        serde_json::json!({
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "sha3Uncles": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "transactionsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "receiptsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "number": "0x1",
+            "gasUsed": "0x0",
+            "gasLimit": "0x1c9c380",
+            "extraData": "0x",
+            "logsBloom": null,
+            "timestamp": "0x0",
+            "difficulty": "0x0",
+            "totalDifficulty": "0x0",
+            "sealFields": [],
+            "uncles": [],
+            "transactions": [],
+            "size": "0x0",
+            "mixHash": null,
+            "nonce": null,
+            "baseFeePerGas": format!("{:#x}", base_fee_per_gas),
+        })
+    }
+
+    #[test]
+    fn bump_increases_monotonically() {
+        let cfg = EscalatorConfig {
+            factor: 1.5,
+            ..Default::default()
+        };
+        let bumped = cfg.bump(U256::from(1_000));
+        assert!(bumped > U256::from(1_000));
+        assert_eq!(bumped, U256::from(1_500));
+    }
+
+    #[test]
+    fn bump_respects_fee_ceiling() {
+        let cfg = EscalatorConfig {
+            factor: 2.0,
+            fee_ceiling: Some(U256::from(1_200)),
+            ..Default::default()
+        };
+        assert_eq!(cfg.bump(U256::from(1_000)), U256::from(1_200));
+    }
+
+    #[test]
+    fn bump_holds_at_the_ceiling_instead_of_clamping_back_down() {
+        // Once `fee` has already reached the ceiling, the old
+        // `.min(ceiling)` clamp would silently hand back `ceiling` again
+        // -- a same-or-lower fee every node rejects as a replacement
+        // transaction, stalling escalation forever instead of giving up
+        // cleanly.
+        let cfg = EscalatorConfig {
+            factor: 2.0,
+            fee_ceiling: Some(U256::from(1_200)),
+            ..Default::default()
+        };
+        assert_eq!(cfg.bump(U256::from(1_200)), U256::from(1_200));
+        // Also covers a fee that has somehow overshot the ceiling.
+        assert_eq!(cfg.bump(U256::from(1_300)), U256::from(1_300));
+    }
+
+    #[test]
+    fn bump_guarantees_at_least_one_wei_increase() {
+        // factor alone rounds back down to the same integer for a
+        // small enough fee -- the `.max(fee + 1)` floor is what actually
+        // guarantees forward progress here.
+        let cfg = EscalatorConfig {
+            factor: 1.001,
+            ..Default::default()
+        };
+        assert_eq!(cfg.bump(U256::from(1)), U256::from(2));
+    }
+
+    #[tokio::test]
+    async fn nonce_manager_resyncs_after_idle() {
+        let (provider, mock) = Provider::mocked();
+        let from = Address::from_low_u64_be(1);
+        let manager = NonceManager::new(Duration::from_millis(20));
+
+        mock.push(U256::from(5)).unwrap();
+        assert_eq!(manager.next(&provider, from).await.unwrap(), U256::from(5));
+
+        // Still within max_idle: hands out the next nonce locally, with
+        // no further round-trip through the mock provider.
+        assert_eq!(manager.next(&provider, from).await.unwrap(), U256::from(6));
+
+        tokio::time::sleep(Duration::from_millis(25)).await;
+
+        // Past max_idle: resyncs from chain rather than trusting the
+        // locally-tracked value, in case another actor has also been
+        // submitting from this address.
+        mock.push(U256::from(42)).unwrap();
+        assert_eq!(
+            manager.next(&provider, from).await.unwrap(),
+            U256::from(42)
+        );
+    }
+
+    #[tokio::test]
+    async fn price_clamps_max_fee_to_at_least_priority_fee() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(mock_block_json(U256::from(1_000))).unwrap();
+
+        let submitter = TxSubmitter::new(
+            Arc::new(provider),
+            FeeStrategy::Eip1559 {
+                priority_fee: Some(U256::from(500)),
+                access_list: None,
+            },
+        );
+
+        // A tight max_fee_per_gas cap with no matching priority-fee cap is
+        // exactly the case that used to leave max_fee < priority_fee.
+        let cap = FeeCap {
+            max_fee_per_gas: Some(U256::from(100)),
+            max_priority_fee_per_gas: None,
+        };
+
+        let tx: TypedTransaction = TransactionRequest::new().into();
+        let priced = submitter.price(tx, Some(cap)).await.unwrap();
+
+        let max_fee = *priced.max_fee_per_gas().expect("priced as eip1559");
+        let priority_fee = *priced
+            .max_priority_fee_per_gas()
+            .expect("priced as eip1559");
+        assert!(max_fee >= priority_fee);
+        assert_eq!(max_fee, priority_fee);
+    }
+
+    #[tokio::test]
+    async fn price_surfaces_a_pricing_rpc_failure_with_no_fallback_of_its_own() {
+        let (provider, mock) = Provider::mocked();
+        // With no fixed priority fee and an `Unknown` node client (the
+        // default -- `supports_fee_history` is false), `price()`'s next
+        // move is `eth_maxPriorityFeePerGas`. A response that doesn't
+        // decode as a `U256` stands in for a transient RPC failure there.
+        mock.push(serde_json::json!("not-a-hex-number")).unwrap();
+
+        let submitter = TxSubmitter::new(
+            Arc::new(provider),
+            FeeStrategy::Eip1559 {
+                priority_fee: None,
+                access_list: None,
+            },
+        );
+
+        let tx: TypedTransaction = TransactionRequest::new().into();
+        // `price()` itself has no fallback for this -- `submit_with_fee_cap`
+        // is the one place that catches this error and resubmits the
+        // original, un-priced transaction as its advertised "legacy
+        // pricing" fallback.
+        submitter.price(tx, None).await.unwrap_err();
+    }
+}
@@ -1,20 +1,104 @@
 use crate::decodings::{NomadBase, NomadLightMerkleWrapper, NomadState};
 use crate::{report_tx, utils, NomadOnlineClient, SubstrateError, SubstrateSigner};
 use async_trait::async_trait;
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
 use ethers_core::types::{H160, H256, U256};
-use futures::{stream::FuturesOrdered, StreamExt};
+use futures::{stream, StreamExt};
 use nomad_core::{
     accumulator::{Merkle, NomadLightMerkle},
-    Common, CommonIndexer, DoubleUpdate, Home, HomeIndexer, Message, RawCommittedMessage,
-    SignedUpdate, SignedUpdateWithMeta, State, TxOutcome, Update,
+    Common, CommonIndexer, CommonTxSubmission, DoubleUpdate, Home, HomeIndexer, HomeTxSubmission,
+    Message, RawCommittedMessage, SignedUpdate, SignedUpdateWithMeta, State, TxOutcome, Update,
 };
-use std::{convert::TryInto, sync::Arc};
+use std::{convert::TryInto, future::Future, path::PathBuf, sync::Arc, time::Duration};
 use subxt::ext::scale_value::{self, Primitive, Value};
 use subxt::tx::ExtrinsicParams;
 use subxt::Config;
+use tokio::sync::Mutex;
 use tracing::info;
 
+/// Env var checked for a keystore passphrase before
+/// [`SubstrateKeySource::load`] falls back to an interactive prompt.
+/// Shared with the Ethereum signer's `KeySource` so operators set one var
+/// regardless of which chain a key belongs to.
+const KEY_PASSPHRASE_ENV_VAR: &str = "NOMAD_KEY_PASSPHRASE";
+
+/// On-disk key material format understood by [`SubstrateKeySource::load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubstrateKeyFormat {
+    /// A password-encrypted keystore JSON file over the raw seed: scrypt
+    /// KDF, AEAD over the ciphertext.
+    EncryptedJson,
+    /// A PEM-wrapped raw seed, for operators whose own tooling already
+    /// keeps the file encrypted at rest.
+    Pem,
+}
+
+/// A reference to Substrate key material on disk -- a path and format
+/// tag, not the key itself -- as stored in `AgentConfig` in place of a
+/// plaintext seed. Mirrors `nomad_ethereum::KeySource`; the passphrase is
+/// sourced from [`KEY_PASSPHRASE_ENV_VAR`] or an interactive prompt so it
+/// never has to live in config or the process environment.
+#[derive(Debug, Clone)]
+pub struct SubstrateKeySource {
+    /// Path to the keystore/PEM file.
+    pub path: PathBuf,
+    /// Format the file is encoded in.
+    pub format: SubstrateKeyFormat,
+}
+
+impl SubstrateKeySource {
+    /// Reference an encrypted keystore JSON file at `path`.
+    pub fn encrypted_json(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: SubstrateKeyFormat::EncryptedJson,
+        }
+    }
+
+    /// Reference a PEM-wrapped seed file at `path`.
+    pub fn pem(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: SubstrateKeyFormat::Pem,
+        }
+    }
+
+    /// Decrypt and build the [`SubstrateSigner`] this reference points at.
+    /// `SubstrateHome::new(..., signer, ...)` is unchanged by this --
+    /// callers just build the `Arc<SubstrateSigner<T>>` they already pass
+    /// it from a decrypted file instead of raw key material.
+    pub fn load<T: Config>(&self) -> Result<SubstrateSigner<T>> {
+        Ok(SubstrateSigner::from_seed(&self.decrypt()?))
+    }
+
+    /// Decrypt this reference into a raw 32-byte seed.
+    fn decrypt(&self) -> Result<[u8; 32]> {
+        let bytes = match self.format {
+            SubstrateKeyFormat::EncryptedJson => {
+                eth_keystore::decrypt_key(&self.path, Self::passphrase()?)?
+            }
+            SubstrateKeyFormat::Pem => {
+                let contents = std::fs::read_to_string(&self.path)?;
+                pem::parse(contents)?.contents
+            }
+        };
+
+        let len = bytes.len();
+        bytes
+            .try_into()
+            .map_err(|_| eyre!("expected a 32-byte seed, got {} bytes", len))
+    }
+
+    /// `NOMAD_KEY_PASSPHRASE` if set, otherwise prompt for it interactively.
+    fn passphrase() -> Result<String> {
+        if let Ok(passphrase) = std::env::var(KEY_PASSPHRASE_ENV_VAR) {
+            return Ok(passphrase);
+        }
+
+        Ok(rpassword::prompt_password("Keystore passphrase: ")?)
+    }
+}
+
 const HOME_PALLET_NAME: &str = "NomadHome";
 const BASE_STORAGE_NAME: &str = "Base";
 const TREE_STORAGE_NAME: &str = "Tree";
@@ -23,19 +107,57 @@ const ROOT_TO_INDEX_STORAGE_NAME: &str = "RootToIndex";
 const DISPATCH_CALL_NAME: &str = "dispatch";
 const UPDATE_CALL_NAME: &str = "update";
 const IMPROPER_UPDATE_CALL_NAME: &str = "improper_update";
+const DOUBLE_UPDATE_CALL_NAME: &str = "double_update";
 const UPDATE_MAX_INDEX: u32 = 1000;
+const SYSTEM_PALLET_NAME: &str = "System";
+const EXTRINSIC_FAILED_EVENT_NAME: &str = "ExtrinsicFailed";
+/// Default number of blocks queried concurrently within a single indexing
+/// window, absent an explicit [`SubstrateHomeIndexer::with_max_in_flight`]
+/// override.
+const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+/// Retries attempted for a single block's query, on top of the initial
+/// attempt, before a transient RPC failure aborts the whole window.
+const BLOCK_FETCH_RETRIES: u32 = 3;
+const BLOCK_FETCH_RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+/// How many finalized blocks `finalized_tx_outcome` walks backward from
+/// the current finalized head, per call, looking for a submitted
+/// extrinsic. Bounds a single `status()` poll's cost; a submission older
+/// than this is expected to have already been found by an earlier poll.
+const FINALIZED_SCAN_DEPTH: u32 = 256;
 
 /// Substrate home indexer
 #[derive(Clone)]
-pub struct SubstrateHomeIndexer<T: Config>(NomadOnlineClient<T>);
+pub struct SubstrateHomeIndexer<T: Config> {
+    client: NomadOnlineClient<T>,
+    /// Blocks per indexing window, from `NetworkSpecs::index_page_size`.
+    /// Bounds how many blocks worth of results are buffered in memory at
+    /// once during historical backfill.
+    index_page_size: u32,
+    /// Blocks queried concurrently within a window.
+    max_in_flight: usize,
+}
 
 impl<T> SubstrateHomeIndexer<T>
 where
     T: Config,
 {
-    /// Instantiate a new SubstrateHomeIndexer object
-    pub fn new(client: NomadOnlineClient<T>) -> Self {
-        Self(client)
+    /// Instantiate a new SubstrateHomeIndexer object, windowing historical
+    /// syncs into `index_page_size`-block chunks (typically
+    /// `NetworkSpecs::index_page_size` for the chain being indexed).
+    pub fn new(client: NomadOnlineClient<T>, index_page_size: u32) -> Self {
+        Self {
+            client,
+            // A zero page size would never advance the indexing window.
+            index_page_size: index_page_size.max(1),
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+        }
+    }
+
+    /// Override how many blocks are queried concurrently within a single
+    /// window. Defaults to [`DEFAULT_MAX_IN_FLIGHT`].
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
     }
 }
 
@@ -45,7 +167,7 @@ where
 {
     type Target = NomadOnlineClient<T>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.client
     }
 }
 
@@ -54,7 +176,80 @@ where
     T: Config,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "SubstrateHomeIndexer",)
+        write!(
+            f,
+            "SubstrateHomeIndexer {{ index_page_size: {}, max_in_flight: {} }}",
+            self.index_page_size, self.max_in_flight,
+        )
+    }
+}
+
+/// Fetch per-block results over `[from, to)` in `index_page_size`-block
+/// windows, querying at most `max_in_flight` blocks concurrently within
+/// each window (rather than fanning every block out at once) and retrying
+/// a transient per-block failure with backoff before giving up on the
+/// whole range. Preserves block order in the flattened output, the same
+/// order the replaced `FuturesOrdered` fan-out produced.
+async fn fetch_windowed<F, Fut, Item>(
+    from: u32,
+    to: u32,
+    index_page_size: u32,
+    max_in_flight: usize,
+    fetch_block: F,
+) -> Result<Vec<Item>, SubstrateError>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<Vec<Item>, SubstrateError>>,
+{
+    let mut out = Vec::new();
+    let mut window_start = from;
+
+    while window_start < to {
+        let window_end = (window_start + index_page_size).min(to);
+
+        let window_results = stream::iter(window_start..window_end)
+            .map(|block_number| fetch_block_with_retry(block_number, &fetch_block))
+            .buffered(max_in_flight)
+            .collect::<Vec<_>>()
+            .await;
+
+        for result in window_results {
+            out.extend(result?);
+        }
+
+        window_start = window_end;
+    }
+
+    Ok(out)
+}
+
+/// Retry a single block's `fetch_block` call with a linearly growing backoff, so a
+/// single dropped connection or node hiccup doesn't abort an entire
+/// indexing window.
+async fn fetch_block_with_retry<F, Fut, Item>(
+    block_number: u32,
+    fetch_block: &F,
+) -> Result<Vec<Item>, SubstrateError>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<Vec<Item>, SubstrateError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match fetch_block(block_number).await {
+            Ok(items) => return Ok(items),
+            Err(error) if attempt < BLOCK_FETCH_RETRIES => {
+                attempt += 1;
+                tracing::warn!(
+                    block_number,
+                    attempt,
+                    %error,
+                    "Retrying block fetch after transient error"
+                );
+                tokio::time::sleep(BLOCK_FETCH_RETRY_BASE_DELAY * attempt).await;
+            }
+            Err(error) => return Err(error),
+        }
     }
 }
 
@@ -68,7 +263,7 @@ where
 
     #[tracing::instrument(err, skip(self))]
     async fn get_block_number(&self) -> Result<u32, Self::Error> {
-        self.0.get_block_number().await
+        self.client.get_block_number().await
     }
 
     #[tracing::instrument(err, skip(self))]
@@ -77,21 +272,14 @@ where
         from: u32,
         to: u32,
     ) -> Result<Vec<SignedUpdateWithMeta>, Self::Error> {
-        let mut futs = FuturesOrdered::new();
-        for block_number in from..to {
-            futs.push(self.0.fetch_sorted_updates_for_block(block_number))
-        }
-
-        // Flatten all Future<Output = Result<Vec<SignedUpdateWithMeta>>> into
-        // single Vec<SignedUpdateWithMeta>
-        Ok(futs
-            .collect::<Vec<_>>()
-            .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten()
-            .collect())
+        fetch_windowed(
+            from,
+            to,
+            self.index_page_size,
+            self.max_in_flight,
+            |block_number| self.client.fetch_sorted_updates_for_block(block_number),
+        )
+        .await
     }
 }
 
@@ -107,21 +295,14 @@ where
         from: u32,
         to: u32,
     ) -> Result<Vec<RawCommittedMessage>, <Self as CommonIndexer>::Error> {
-        let mut futs = FuturesOrdered::new();
-        for block_number in from..to {
-            futs.push(self.0.fetch_sorted_messages_for_block(block_number))
-        }
-
-        // Flatten all Future<Output = Result<Vec<RawCommittedMessage>>> into
-        // single Vec<RawCommittedMessage>
-        Ok(futs
-            .collect::<Vec<_>>()
-            .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten()
-            .collect())
+        fetch_windowed(
+            from,
+            to,
+            self.index_page_size,
+            self.max_in_flight,
+            |block_number| self.client.fetch_sorted_messages_for_block(block_number),
+        )
+        .await
     }
 }
 
@@ -132,6 +313,17 @@ pub struct SubstrateHome<T: Config> {
     signer: Arc<SubstrateSigner<T>>,
     domain: u32,
     name: String,
+    /// Per-txid finalized head that `finalized_tx_outcome` last scanned
+    /// back from, shared across every `status()` call on this handle.
+    /// Lets a poll for a given txid stop at the blocks it already
+    /// checked for *that* txid instead of rescanning the full
+    /// [`FINALIZED_SCAN_DEPTH`] window every time, since only the blocks
+    /// finalized since then can contain an extrinsic not yet found. Keyed
+    /// per txid rather than one handle-wide floor -- several txids can be
+    /// polled concurrently on the same handle, each at its own pace, and
+    /// a shared floor would let scanning one advance past a block another,
+    /// still-unresolved txid actually landed in.
+    last_scanned_finalized_heads: Arc<Mutex<std::collections::HashMap<H256, T::Hash>>>,
 }
 
 impl<T> SubstrateHome<T>
@@ -151,6 +343,7 @@ where
             signer,
             domain,
             name: name.to_owned(),
+            last_scanned_finalized_heads: Arc::new(Mutex::new(std::collections::HashMap::new())),
         }
     }
 
@@ -168,6 +361,104 @@ where
         let merkle_wrapper: NomadLightMerkleWrapper = scale_value::serde::from_value(tree_value)?;
         Ok(merkle_wrapper.into())
     }
+
+    /// Scan the most recently finalized blocks for `txid`, returning its
+    /// outcome if the extrinsic landed in one of them.
+    ///
+    /// `status()` is meant to be polled repeatedly until a submitted
+    /// extrinsic's outcome is known, so this doesn't just inspect the
+    /// single next finalized header to arrive after the call -- by the
+    /// time polling starts, the block the extrinsic actually landed in
+    /// has almost always already finalized and passed, and waiting on
+    /// "the next one" would never see it. Instead this walks backward
+    /// from the current finalized head, the same shape as the Ethereum
+    /// side's block-range event search, just bounded per-call instead of
+    /// windowed across an explicit `[from, to)` range: at most
+    /// [`FINALIZED_SCAN_DEPTH`] blocks, and no further back than the head
+    /// this same `txid` was already scanned past on a previous call (see
+    /// [`Self::last_scanned_finalized_heads`]) -- so a caller polling
+    /// every few blocks re-checks only what's newly finalized since then
+    /// rather than the whole window every time.
+    ///
+    /// `TxOutcome` on this chain carries only `txid` (it predates a
+    /// block-number field), so a reverted extrinsic is reported via a
+    /// warning rather than a distinct error variant -- the watcher cares
+    /// that the fraud report was seen on-chain, not how it resolved.
+    ///
+    /// Untested: this crate has no mock Substrate node harness (and
+    /// `NomadOnlineClient`, the type this method is built around, isn't
+    /// checked into this tree), so there's nothing to submit an extrinsic
+    /// against and poll in a test here.
+    async fn finalized_tx_outcome(&self, txid: H256) -> Result<Option<TxOutcome>, SubstrateError>
+    where
+        <T as Config>::Hash: Into<H256>,
+    {
+        let mut finalized_headers = self.rpc().subscribe_finalized_block_headers().await?;
+        let header = match finalized_headers.next().await.transpose()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+
+        // Snapshot this txid's floor and release the lock immediately --
+        // it isn't needed again until the scan below has finished, and
+        // holding it the whole time would serialize every other txid's
+        // concurrent poll on this handle behind this one's scan.
+        let previous_floor = {
+            let scanned = self.last_scanned_finalized_heads.lock().await;
+            scanned.get(&txid).copied()
+        };
+
+        let mut block_hash = header.hash();
+        let mut outcome = None;
+
+        for _ in 0..FINALIZED_SCAN_DEPTH {
+            let block = self.blocks().at(block_hash).await?;
+            let extrinsics = block.extrinsics().await?;
+
+            let index = extrinsics.iter().enumerate().find_map(|(index, details)| {
+                let details = details.ok()?;
+                (details.hash().into() == txid).then_some(index as u32)
+            });
+
+            if let Some(index) = index {
+                let failed = block.events().await?.iter().any(|event| {
+                    let Ok(event) = event else {
+                        return false;
+                    };
+                    event.phase() == subxt::events::Phase::ApplyExtrinsic(index)
+                        && event.pallet_name() == SYSTEM_PALLET_NAME
+                        && event.variant_name() == EXTRINSIC_FAILED_EVENT_NAME
+                });
+
+                if failed {
+                    tracing::warn!(?txid, "Extrinsic finalized but reverted on-chain");
+                }
+
+                outcome = Some(TxOutcome { txid });
+                break;
+            }
+
+            // Already scanned everything from here back on a previous
+            // call -- only the blocks newly finalized since then could
+            // possibly still contain an extrinsic not yet found.
+            if previous_floor.is_some_and(|floor| Into::<H256>::into(floor) == block_hash.into()) {
+                break;
+            }
+
+            let parent_hash = block.header().parent_hash;
+            if Into::<H256>::into(parent_hash) == H256::zero() {
+                // Walked back to genesis -- nothing earlier to scan.
+                break;
+            }
+            block_hash = parent_hash;
+        }
+
+        self.last_scanned_finalized_heads
+            .lock()
+            .await
+            .insert(txid, header.hash());
+        Ok(outcome)
+    }
 }
 
 impl<T> std::ops::Deref for SubstrateHome<T>
@@ -224,11 +515,6 @@ where
         &self.name
     }
 
-    #[tracing::instrument(err, skip(self))]
-    async fn status(&self, _txid: H256) -> Result<Option<TxOutcome>, Self::Error> {
-        unimplemented!("Have not implemented _status_ for substrate home")
-    }
-
     #[tracing::instrument(err, skip(self))]
     async fn updater(&self) -> Result<H256, Self::Error> {
         let base = self.base().await?;
@@ -250,9 +536,27 @@ where
         let base = self.base().await?;
         Ok(base.committed_root.into())
     }
+}
+
+#[async_trait]
+impl<T> CommonTxSubmission for SubstrateHome<T>
+where
+    T: Config + Send + Sync,
+    <<T as Config>::ExtrinsicParams as ExtrinsicParams<
+        <T as Config>::Index,
+        <T as Config>::Hash,
+    >>::OtherParams: std::default::Default + Send + Sync,
+    <T as Config>::Extrinsic: Send + Sync,
+    <T as Config>::Hash: Into<H256>,
+    <T as Config>::BlockNumber: TryInto<u32>,
+{
+    #[tracing::instrument(err, skip(self))]
+    async fn status(&self, txid: H256) -> Result<Option<TxOutcome>, <Self as Common>::Error> {
+        self.finalized_tx_outcome(txid).await
+    }
 
     #[tracing::instrument(err, skip(self, update), fields(update = %update))]
-    async fn update(&self, update: &SignedUpdate) -> Result<TxOutcome, Self::Error> {
+    async fn update(&self, update: &SignedUpdate) -> Result<TxOutcome, <Self as Common>::Error> {
         let signed_update_value = utils::format_signed_update_value(update);
         let max_index = Value::u128(UPDATE_MAX_INDEX as u128);
         let tx_payload = subxt::dynamic::tx(
@@ -265,11 +569,21 @@ where
         report_tx!(UPDATE_CALL_NAME, self.api, self.signer, tx_payload)
     }
 
-    #[tracing::instrument(err, skip(self))]
-    async fn double_update(&self, _double: &DoubleUpdate) -> Result<TxOutcome, Self::Error> {
-        Ok(TxOutcome {
-            txid: Default::default(),
-        })
+    #[tracing::instrument(err, skip(self, double), fields(double = %double))]
+    async fn double_update(
+        &self,
+        double: &DoubleUpdate,
+    ) -> Result<TxOutcome, <Self as Common>::Error> {
+        let first_value = utils::format_signed_update_value(&double.0);
+        let second_value = utils::format_signed_update_value(&double.1);
+        let tx_payload = subxt::dynamic::tx(
+            HOME_PALLET_NAME,
+            DOUBLE_UPDATE_CALL_NAME,
+            vec![first_value, second_value],
+        );
+
+        info!(double = ?double, "Dispatching double update fraud proof to chain.");
+        report_tx!(DOUBLE_UPDATE_CALL_NAME, self.api, self.signer, tx_payload)
     }
 }
 
@@ -303,6 +617,55 @@ where
         Ok(scale_value::serde::from_value(nonce_value)?)
     }
 
+    async fn queue_length(&self) -> Result<U256, <Self as Common>::Error> {
+        unimplemented!("Queue deprecated for Substrate implementations")
+    }
+
+    async fn queue_contains(&self, root: H256) -> Result<bool, <Self as Common>::Error> {
+        let index_address = subxt::dynamic::storage(
+            HOME_PALLET_NAME,
+            ROOT_TO_INDEX_STORAGE_NAME,
+            vec![Value::from_bytes(&root)],
+        );
+        let index_value = self.storage_fetch(&index_address).await?;
+        Ok(index_value.is_some())
+    }
+
+    #[tracing::instrument(err, skip(self))]
+    async fn produce_update(&self) -> Result<Option<Update>, <Self as Common>::Error> {
+        let committed_root: H256 = self.base().await?.committed_root.into();
+        let new_root = self.tree().await?.root();
+
+        // If tree has no messages, DO NOT produce update with initial root
+        // (will cause failed home)
+        if new_root == NomadLightMerkle::initial_root() {
+            return Ok(None);
+        }
+
+        Ok(if committed_root == new_root {
+            None
+        } else {
+            Some(Update {
+                home_domain: self.domain,
+                previous_root: committed_root,
+                new_root,
+            })
+        })
+    }
+}
+
+#[async_trait]
+impl<T> HomeTxSubmission for SubstrateHome<T>
+where
+    T: Config + Send + Sync,
+    <<T as Config>::ExtrinsicParams as ExtrinsicParams<
+        <T as Config>::Index,
+        <T as Config>::Hash,
+    >>::OtherParams: std::default::Default + Send + Sync,
+    <T as Config>::Extrinsic: Send + Sync,
+    <T as Config>::Hash: Into<H256>,
+    <T as Config>::BlockNumber: TryInto<u32>,
+{
     #[tracing::instrument(err, skip(self))]
     async fn dispatch(&self, message: &Message) -> Result<TxOutcome, <Self as Common>::Error> {
         let Message {
@@ -325,20 +688,6 @@ where
         report_tx!(DISPATCH_CALL_NAME, self.api, self.signer, tx_payload)
     }
 
-    async fn queue_length(&self) -> Result<U256, <Self as Common>::Error> {
-        unimplemented!("Queue deprecated for Substrate implementations")
-    }
-
-    async fn queue_contains(&self, root: H256) -> Result<bool, <Self as Common>::Error> {
-        let index_address = subxt::dynamic::storage(
-            HOME_PALLET_NAME,
-            ROOT_TO_INDEX_STORAGE_NAME,
-            vec![Value::from_bytes(&root)],
-        );
-        let index_value = self.storage_fetch(&index_address).await?;
-        Ok(index_value.is_some())
-    }
-
     #[tracing::instrument(err, skip(self), fields(hex_signature = %format!("0x{}", hex::encode(update.signature.to_vec()))))]
     async fn improper_update(
         &self,
@@ -354,26 +703,4 @@ where
         info!(update = ?update, "Dispatching improper update call to chain.");
         report_tx!(IMPROPER_UPDATE_CALL_NAME, self.api, self.signer, tx_payload)
     }
-
-    #[tracing::instrument(err, skip(self))]
-    async fn produce_update(&self) -> Result<Option<Update>, <Self as Common>::Error> {
-        let committed_root: H256 = self.base().await?.committed_root.into();
-        let new_root = self.tree().await?.root();
-
-        // If tree has no messages, DO NOT produce update with initial root
-        // (will cause failed home)
-        if new_root == NomadLightMerkle::initial_root() {
-            return Ok(None);
-        }
-
-        Ok(if committed_root == new_root {
-            None
-        } else {
-            Some(Update {
-                home_domain: self.domain,
-                previous_root: committed_root,
-                new_root,
-            })
-        })
-    }
 }
@@ -43,31 +43,151 @@ impl std::fmt::Display for RpcStyle {
     }
 }
 
+/// Relative trust given to one provider in a [`Connection::Quorum`] when
+/// tallying matching responses. Equal weights behave like a simple vote
+/// count; giving one provider a higher weight lets it outweigh several
+/// lower-trust providers.
+pub type ProviderWeight = u64;
+
+/// One backend RPC endpoint participating in a [`Connection::Quorum`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightedProvider {
+    /// Fully qualified URI to connect to
+    pub rpc: String,
+    /// Relative trust given to this provider when tallying responses.
+    #[serde(default = "WeightedProvider::default_weight")]
+    pub weight: ProviderWeight,
+}
+
+impl WeightedProvider {
+    fn default_weight() -> ProviderWeight {
+        1
+    }
+}
+
+impl From<String> for WeightedProvider {
+    fn from(rpc: String) -> Self {
+        Self {
+            rpc,
+            weight: Self::default_weight(),
+        }
+    }
+}
+
+/// Agreement required across a [`Connection::Quorum`]'s providers before a
+/// response is accepted, rather than silently trusting whichever provider
+/// answers first.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuorumPolicy {
+    /// A strict majority of the summed provider weight must agree.
+    Majority,
+    /// Every provider must agree.
+    All,
+    /// At least `minimum` summed weight must agree.
+    Weighted {
+        /// Minimum summed weight required to accept a response.
+        minimum: ProviderWeight,
+    },
+}
+
+impl Default for QuorumPolicy {
+    fn default() -> Self {
+        Self::Majority
+    }
+}
+
+/// Retry-with-backoff policy applied on top of a [`Connection::Http`]'s
+/// read provider, so a long-running indexer can ride out a hosted RPC
+/// provider's rate limiting instead of restarting its whole indexing loop.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryConfig {
+    /// Maximum number of retries before giving up and surfacing the error.
+    #[serde(default = "RetryConfig::default_max_retries")]
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubles (with jitter) on each
+    /// subsequent attempt, unless the error carries its own `Retry-After`.
+    #[serde(default = "RetryConfig::default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+}
+
+impl RetryConfig {
+    fn default_max_retries() -> u32 {
+        5
+    }
+
+    fn default_initial_backoff_ms() -> u64 {
+        250
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: Self::default_max_retries(),
+            initial_backoff_ms: Self::default_initial_backoff_ms(),
+        }
+    }
+}
+
 /// Chain connection configuration
 #[derive(Debug, Clone, PartialEq)]
 pub enum Connection {
     /// HTTP connection details
-    Http(
+    Http {
         /// Fully qualified URI to connect to
-        String,
-    ),
+        rpc: String,
+        /// Retry-with-backoff policy for transient errors on this
+        /// connection. `None` submits each request as-is.
+        retry: Option<RetryConfig>,
+    },
     /// Websocket connection details
     Ws(
         /// Fully qualified URI to connect to
         String,
     ),
+    /// Quorum connection details. Fans every read out across `providers`
+    /// and only accepts a response once `quorum` is satisfied, surfacing
+    /// an error on disagreement rather than picking one provider's answer.
+    Quorum {
+        /// Backend endpoints to fan requests out to
+        providers: Vec<WeightedProvider>,
+        /// Agreement policy required to accept a response
+        quorum: QuorumPolicy,
+    },
 }
 
 impl Connection {
     fn from_string(s: String) -> eyre::Result<Self> {
-        if s.starts_with("http://") || s.starts_with("https://") {
-            Ok(Self::Http(s))
-        } else if s.starts_with("wss://") || s.starts_with("ws://") {
+        if Self::is_http(&s) {
+            Ok(Self::Http {
+                rpc: s,
+                retry: None,
+            })
+        } else if Self::is_ws(&s) {
             Ok(Self::Ws(s))
         } else {
             eyre::bail!("Expected http or websocket URI")
         }
     }
+
+    fn is_http(s: &str) -> bool {
+        s.starts_with("http://") || s.starts_with("https://")
+    }
+
+    fn is_ws(s: &str) -> bool {
+        s.starts_with("wss://") || s.starts_with("ws://")
+    }
+
+    fn validate_uri(s: &str) -> eyre::Result<()> {
+        if Self::is_http(s) || Self::is_ws(s) {
+            Ok(())
+        } else {
+            eyre::bail!("Expected http or websocket URI, got `{}`", s)
+        }
+    }
 }
 
 impl FromStr for Connection {
@@ -80,7 +200,10 @@ impl FromStr for Connection {
 
 impl Default for Connection {
     fn default() -> Self {
-        Self::Http(Default::default())
+        Self::Http {
+            rpc: Default::default(),
+            retry: None,
+        }
     }
 }
 
@@ -89,8 +212,88 @@ impl<'de> serde::Deserialize<'de> for Connection {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        Self::from_string(s).map_err(serde::de::Error::custom)
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ProviderRepr {
+            Plain(String),
+            Weighted(WeightedProvider),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct HttpRepr {
+            rpc: String,
+            max_retries: Option<u32>,
+            initial_backoff_ms: Option<u64>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ConnectionRepr {
+            Single(String),
+            Quorum {
+                providers: Vec<ProviderRepr>,
+                #[serde(default)]
+                quorum: QuorumPolicy,
+            },
+            Providers(Vec<ProviderRepr>),
+            Http(HttpRepr),
+        }
+
+        match ConnectionRepr::deserialize(deserializer)? {
+            ConnectionRepr::Single(s) => Self::from_string(s).map_err(serde::de::Error::custom),
+            ConnectionRepr::Http(HttpRepr {
+                rpc,
+                max_retries,
+                initial_backoff_ms,
+            }) => {
+                if !Self::is_http(&rpc) {
+                    return Err(serde::de::Error::custom(format!(
+                        "Expected http URI, got `{}`",
+                        rpc
+                    )));
+                }
+                let retry = if max_retries.is_some() || initial_backoff_ms.is_some() {
+                    Some(RetryConfig {
+                        max_retries: max_retries.unwrap_or_else(RetryConfig::default_max_retries),
+                        initial_backoff_ms: initial_backoff_ms
+                            .unwrap_or_else(RetryConfig::default_initial_backoff_ms),
+                    })
+                } else {
+                    None
+                };
+                Ok(Self::Http { rpc, retry })
+            }
+            ConnectionRepr::Quorum { providers, quorum } => {
+                let providers = providers
+                    .into_iter()
+                    .map(|p| match p {
+                        ProviderRepr::Plain(rpc) => WeightedProvider::from(rpc),
+                        ProviderRepr::Weighted(w) => w,
+                    })
+                    .collect::<Vec<_>>();
+                for provider in &providers {
+                    Self::validate_uri(&provider.rpc).map_err(serde::de::Error::custom)?;
+                }
+                Ok(Self::Quorum { providers, quorum })
+            }
+            ConnectionRepr::Providers(providers) => {
+                let providers = providers
+                    .into_iter()
+                    .map(|p| match p {
+                        ProviderRepr::Plain(rpc) => WeightedProvider::from(rpc),
+                        ProviderRepr::Weighted(w) => w,
+                    })
+                    .collect::<Vec<_>>();
+                for provider in &providers {
+                    Self::validate_uri(&provider.rpc).map_err(serde::de::Error::custom)?;
+                }
+                Ok(Self::Quorum {
+                    providers,
+                    quorum: QuorumPolicy::default(),
+                })
+            }
+        }
     }
 }
 
@@ -168,7 +371,7 @@ impl TxSubmitterConf {
 mod test {
     use serde_json::json;
 
-    use super::Connection;
+    use super::{Connection, ProviderWeight, QuorumPolicy, RetryConfig, WeightedProvider};
 
     #[test]
     fn it_desers_rpc_configs() {
@@ -178,13 +381,22 @@ mod test {
         let connection: Connection = serde_json::from_value(value).unwrap();
         assert_eq!(
             connection,
-            Connection::Http("https://google.com".to_owned())
+            Connection::Http {
+                rpc: "https://google.com".to_owned(),
+                retry: None,
+            }
         );
         let value = json! {
             "http://google.com"
         };
         let connection: Connection = serde_json::from_value(value).unwrap();
-        assert_eq!(connection, Connection::Http("http://google.com".to_owned()));
+        assert_eq!(
+            connection,
+            Connection::Http {
+                rpc: "http://google.com".to_owned(),
+                retry: None,
+            }
+        );
         let value = json! {
             "wss://google.com"
         };
@@ -196,4 +408,116 @@ mod test {
         let connection: Connection = serde_json::from_value(value).unwrap();
         assert_eq!(connection, Connection::Ws("ws://google.com".to_owned()));
     }
+
+    #[test]
+    fn it_desers_bare_provider_arrays_as_majority_quorum() {
+        let value = json! {
+            ["https://a.xyz", "https://b.xyz"]
+        };
+        let connection: Connection = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            connection,
+            Connection::Quorum {
+                providers: vec![
+                    WeightedProvider {
+                        rpc: "https://a.xyz".to_owned(),
+                        weight: 1
+                    },
+                    WeightedProvider {
+                        rpc: "https://b.xyz".to_owned(),
+                        weight: 1
+                    },
+                ],
+                quorum: QuorumPolicy::Majority,
+            }
+        );
+    }
+
+    #[test]
+    fn it_desers_quorum_connections_with_weights_and_policy() {
+        let value = json! {
+            {
+                "providers": [
+                    "https://a.xyz",
+                    { "rpc": "https://b.xyz", "weight": 3 },
+                ],
+                "quorum": { "weighted": { "minimum": 4 } },
+            }
+        };
+        let connection: Connection = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            connection,
+            Connection::Quorum {
+                providers: vec![
+                    WeightedProvider {
+                        rpc: "https://a.xyz".to_owned(),
+                        weight: 1
+                    },
+                    WeightedProvider {
+                        rpc: "https://b.xyz".to_owned(),
+                        weight: 3
+                    },
+                ],
+                quorum: QuorumPolicy::Weighted { minimum: 4 },
+            }
+        );
+    }
+
+    #[test]
+    fn it_rejects_quorum_providers_with_bad_schemes() {
+        let value = json! {
+            ["not-a-uri"]
+        };
+        assert!(serde_json::from_value::<Connection>(value).is_err());
+    }
+
+    #[test]
+    fn default_weight_is_one() {
+        let weight: ProviderWeight = WeightedProvider::default_weight();
+        assert_eq!(weight, 1);
+    }
+
+    #[test]
+    fn it_desers_http_connections_with_retry_config() {
+        let value = json! {
+            { "rpc": "https://google.com", "maxRetries": 8, "initialBackoffMs": 500 }
+        };
+        let connection: Connection = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            connection,
+            Connection::Http {
+                rpc: "https://google.com".to_owned(),
+                retry: Some(RetryConfig {
+                    max_retries: 8,
+                    initial_backoff_ms: 500,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn it_defaults_retry_fields_independently() {
+        let value = json! {
+            { "rpc": "https://google.com", "maxRetries": 3 }
+        };
+        let connection: Connection = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            connection,
+            Connection::Http {
+                rpc: "https://google.com".to_owned(),
+                retry: Some(RetryConfig {
+                    max_retries: 3,
+                    initial_backoff_ms: RetryConfig::default_initial_backoff_ms(),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn it_rejects_non_http_uri_in_object_form() {
+        let value = json! {
+            { "rpc": "wss://google.com", "maxRetries": 3 }
+        };
+        assert!(serde_json::from_value::<Connection>(value).is_err());
+    }
 }
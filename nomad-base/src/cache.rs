@@ -0,0 +1,451 @@
+//! Read-through cache over [`crate::NomadDB`]'s hottest lookups.
+//!
+//! Leaves, committed messages, and proofs are immutable once written, so
+//! each cache is populated write-through on the corresponding `store_*`
+//! call and otherwise never invalidated by a normal write -- a hit never
+//! needs to be checked against the DB. `LATEST_ROOT`/`LATEST_LEAF_INDEX`
+//! are mutable pointers and are intentionally not cached here.
+//!
+//! A reorg is the one case where an already-cached leaf/message/proof can
+//! become wrong: everything from the reorg's pivot leaf index onward gets
+//! rewritten in the DB, so [`Caches::invalidate_from`] drops every cached
+//! entry at or past that index rather than risk a fraud check reading a
+//! stale proof.
+
+use ethers::core::types::H256;
+use lru::LruCache;
+use nomad_core::{accumulator::NomadProof, RawCommittedMessage};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Bound on one cache category: at most `entries` items, and at most
+/// `max_bytes` of cumulative size, whichever is hit first. Either field set
+/// to `0` disables that category's cache entirely.
+#[derive(Debug, Clone, Copy)]
+pub struct CategoryConfig {
+    /// Maximum number of entries to retain.
+    pub entries: usize,
+    /// Maximum cumulative byte size of retained entries.
+    pub max_bytes: usize,
+}
+
+impl CategoryConfig {
+    fn enabled(&self) -> bool {
+        self.entries > 0 && self.max_bytes > 0
+    }
+}
+
+/// Per-category cache bounds.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheConfig {
+    /// Bound on the `leaf_index` -> leaf hash cache.
+    pub leaves: CategoryConfig,
+    /// Bound on the leaf hash -> raw committed message cache.
+    pub messages: CategoryConfig,
+    /// Bound on the `leaf_index` -> proof cache.
+    pub proofs: CategoryConfig,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            leaves: CategoryConfig {
+                entries: 1024,
+                max_bytes: 1024 * 64, // H256 entries are tiny; bytes won't bind first.
+            },
+            messages: CategoryConfig {
+                entries: 1024,
+                max_bytes: 64 * 1024 * 1024,
+            },
+            proofs: CategoryConfig {
+                entries: 1024,
+                max_bytes: 16 * 1024 * 1024,
+            },
+        }
+    }
+}
+
+/// Hit/miss/eviction counters for one cache category, suitable for
+/// exposing as Prometheus gauges by whatever agent owns `CoreMetrics`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of lookups served from cache.
+    pub hits: u64,
+    /// Number of lookups that missed and fell through to the DB.
+    pub misses: u64,
+    /// Number of entries evicted to stay within `entries`/`max_bytes`.
+    pub evictions: u64,
+    /// Number of entries dropped by [`Caches::invalidate_from`] after a
+    /// reorg.
+    pub invalidations: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl Counters {
+    fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A size-and-count-bounded LRU cache: on top of `lru::LruCache`'s
+/// entry-count bound, this also tracks the cumulative byte size of what's
+/// stored and evicts the least-recently-used entry whenever either bound
+/// would otherwise be exceeded.
+struct BoundedCache<K, V> {
+    lru: LruCache<K, (V, usize)>,
+    bytes: usize,
+    max_bytes: usize,
+    counters: Counters,
+}
+
+impl<K: std::hash::Hash + Eq, V: Clone> BoundedCache<K, V> {
+    fn new(config: CategoryConfig) -> Option<Self> {
+        config.enabled().then(|| Self {
+            lru: LruCache::new(config.entries),
+            bytes: 0,
+            max_bytes: config.max_bytes,
+            counters: Counters::default(),
+        })
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        match self.lru.get(key) {
+            Some((value, _)) => {
+                self.counters.hits.fetch_add(1, Ordering::Relaxed);
+                Some(value.clone())
+            }
+            None => {
+                self.counters.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn put(&mut self, key: K, value: V, size: usize) {
+        // `LruCache` enforces its own entry-count bound by silently
+        // evicting the LRU entry on a capacity-exceeding insert of a new
+        // key; peek at it first so `bytes`/`evictions` stay accurate for
+        // that eviction too, not just the byte-ceiling ones below.
+        if self.lru.len() == self.lru.cap() && !self.lru.contains(&key) {
+            if let Some((_, (_, evicted_size))) = self.lru.peek_lru() {
+                self.bytes -= evicted_size;
+                self.counters.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        if let Some((_, old_size)) = self.lru.put(key, (value, size)) {
+            self.bytes -= old_size;
+        }
+        self.bytes += size;
+
+        while self.bytes > self.max_bytes && self.lru.len() > 1 {
+            if let Some((_, (_, evicted_size))) = self.lru.pop_lru() {
+                self.bytes -= evicted_size;
+                self.counters.evictions.fetch_add(1, Ordering::Relaxed);
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Remove every entry for which `matches` returns `true`, e.g. every
+    /// leaf/message/proof at or past a reorg's pivot index.
+    fn invalidate(&mut self, matches: impl Fn(&K, &V) -> bool) {
+        let stale: Vec<K> = self
+            .lru
+            .iter()
+            .filter(|(k, (v, _))| matches(k, v))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in stale {
+            if let Some((_, size)) = self.lru.pop(&key) {
+                self.bytes -= size;
+                self.counters.invalidations.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.counters.snapshot()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Caches {
+    leaves: Mutex<Option<BoundedCache<u32, H256>>>,
+    messages: Mutex<Option<BoundedCache<H256, RawCommittedMessage>>>,
+    proofs: Mutex<Option<BoundedCache<u32, NomadProof>>>,
+}
+
+impl std::fmt::Debug for BoundedCache<u32, H256> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedCache").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for BoundedCache<H256, RawCommittedMessage> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedCache").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for BoundedCache<u32, NomadProof> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedCache").finish_non_exhaustive()
+    }
+}
+
+/// Approximate heap size of a [`RawCommittedMessage`], for byte-size
+/// accounting -- dominated by the variable-length `message` body.
+fn message_size(message: &RawCommittedMessage) -> usize {
+    std::mem::size_of::<RawCommittedMessage>() + message.message.len()
+}
+
+/// Approximate heap size of a [`NomadProof`], for byte-size accounting --
+/// dominated by the merkle path.
+fn proof_size(proof: &NomadProof) -> usize {
+    std::mem::size_of::<NomadProof>() + proof.path.len() * std::mem::size_of::<H256>()
+}
+
+impl Caches {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        Self {
+            leaves: Mutex::new(BoundedCache::new(config.leaves)),
+            messages: Mutex::new(BoundedCache::new(config.messages)),
+            proofs: Mutex::new(BoundedCache::new(config.proofs)),
+        }
+    }
+
+    pub(crate) fn get_leaf(&self, leaf_index: u32) -> Option<H256> {
+        self.leaves
+            .lock()
+            .expect("cache lock poisoned")
+            .as_mut()?
+            .get(&leaf_index)
+    }
+
+    pub(crate) fn put_leaf(&self, leaf_index: u32, leaf: H256) {
+        if let Some(cache) = self.leaves.lock().expect("cache lock poisoned").as_mut() {
+            cache.put(leaf_index, leaf, std::mem::size_of::<H256>());
+        }
+    }
+
+    pub(crate) fn get_message(&self, leaf: H256) -> Option<RawCommittedMessage> {
+        self.messages
+            .lock()
+            .expect("cache lock poisoned")
+            .as_mut()?
+            .get(&leaf)
+    }
+
+    pub(crate) fn put_message(&self, leaf: H256, message: RawCommittedMessage) {
+        if let Some(cache) = self.messages.lock().expect("cache lock poisoned").as_mut() {
+            let size = message_size(&message);
+            cache.put(leaf, message, size);
+        }
+    }
+
+    pub(crate) fn get_proof(&self, leaf_index: u32) -> Option<NomadProof> {
+        self.proofs
+            .lock()
+            .expect("cache lock poisoned")
+            .as_mut()?
+            .get(&leaf_index)
+    }
+
+    pub(crate) fn put_proof(&self, leaf_index: u32, proof: NomadProof) {
+        if let Some(cache) = self.proofs.lock().expect("cache lock poisoned").as_mut() {
+            let size = proof_size(&proof);
+            cache.put(leaf_index, proof, size);
+        }
+    }
+
+    /// Drop every cached leaf/message/proof at or past `from_leaf_index`,
+    /// e.g. because a reorg rewrote the DB from that index onward. Without
+    /// this, a watcher could keep serving a proof or message the chain no
+    /// longer agrees with during fraud detection.
+    pub(crate) fn invalidate_from(&self, from_leaf_index: u32) {
+        if let Some(cache) = self.leaves.lock().expect("cache lock poisoned").as_mut() {
+            cache.invalidate(|index, _| *index >= from_leaf_index);
+        }
+        if let Some(cache) = self.messages.lock().expect("cache lock poisoned").as_mut() {
+            cache.invalidate(|_, message| message.leaf_index >= from_leaf_index);
+        }
+        if let Some(cache) = self.proofs.lock().expect("cache lock poisoned").as_mut() {
+            cache.invalidate(|index, _| *index >= from_leaf_index);
+        }
+    }
+
+    /// Snapshot hit/miss/eviction/invalidation counters for the leaf,
+    /// message, and proof caches, in that order.
+    pub(crate) fn stats(&self) -> (CacheStats, CacheStats, CacheStats) {
+        (
+            self.leaves
+                .lock()
+                .expect("cache lock poisoned")
+                .as_ref()
+                .map(BoundedCache::stats)
+                .unwrap_or_default(),
+            self.messages
+                .lock()
+                .expect("cache lock poisoned")
+                .as_ref()
+                .map(BoundedCache::stats)
+                .unwrap_or_default(),
+            self.proofs
+                .lock()
+                .expect("cache lock poisoned")
+                .as_ref()
+                .map(BoundedCache::stats)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nomad_core::NomadMessage;
+
+    fn message(leaf_index: u32, body_len: usize) -> RawCommittedMessage {
+        let m = NomadMessage {
+            origin: 1,
+            sender: H256::zero(),
+            nonce: leaf_index,
+            destination: 2,
+            recipient: H256::zero(),
+            body: vec![0u8; body_len],
+        };
+        use nomad_core::Encode;
+        RawCommittedMessage {
+            leaf_index,
+            committed_root: H256::zero(),
+            message: m.to_vec(),
+        }
+    }
+
+    #[test]
+    fn evicts_lru_entry_once_entry_count_exceeded() {
+        let caches = Caches::new(CacheConfig {
+            leaves: CategoryConfig {
+                entries: 2,
+                max_bytes: 1024,
+            },
+            messages: CategoryConfig {
+                entries: 0,
+                max_bytes: 0,
+            },
+            proofs: CategoryConfig {
+                entries: 0,
+                max_bytes: 0,
+            },
+        });
+
+        caches.put_leaf(1, H256::from_low_u64_be(1));
+        caches.put_leaf(2, H256::from_low_u64_be(2));
+        // Touch leaf 1 so leaf 2 becomes the least-recently-used entry.
+        assert!(caches.get_leaf(1).is_some());
+        caches.put_leaf(3, H256::from_low_u64_be(3));
+
+        assert!(caches.get_leaf(1).is_some());
+        assert!(caches.get_leaf(2).is_none());
+        assert!(caches.get_leaf(3).is_some());
+
+        let (leaves, _, _) = caches.stats();
+        assert_eq!(leaves.evictions, 1);
+    }
+
+    #[test]
+    fn evicts_lru_entry_once_byte_ceiling_exceeded() {
+        let caches = Caches::new(CacheConfig {
+            leaves: CategoryConfig {
+                entries: 0,
+                max_bytes: 0,
+            },
+            messages: CategoryConfig {
+                entries: 1024,
+                max_bytes: 200,
+            },
+            proofs: CategoryConfig {
+                entries: 0,
+                max_bytes: 0,
+            },
+        });
+
+        let small = message(1, 10);
+        let small_leaf = small.leaf();
+        caches.put_message(small_leaf, small);
+
+        let big = message(2, 1000);
+        let big_leaf = big.leaf();
+        caches.put_message(big_leaf, big);
+
+        assert!(caches.get_message(small_leaf).is_none());
+        assert!(caches.get_message(big_leaf).is_some());
+
+        let (_, messages, _) = caches.stats();
+        assert_eq!(messages.evictions, 1);
+    }
+
+    #[test]
+    fn tracks_hits_and_misses() {
+        let caches = Caches::new(CacheConfig::default());
+        assert!(caches.get_leaf(1).is_none());
+        caches.put_leaf(1, H256::from_low_u64_be(1));
+        assert!(caches.get_leaf(1).is_some());
+
+        let (leaves, _, _) = caches.stats();
+        assert_eq!(leaves.misses, 1);
+        assert_eq!(leaves.hits, 1);
+    }
+
+    #[test]
+    fn invalidate_from_drops_entries_at_or_past_pivot() {
+        let caches = Caches::new(CacheConfig::default());
+        caches.put_leaf(1, H256::from_low_u64_be(1));
+        caches.put_leaf(2, H256::from_low_u64_be(2));
+        caches.put_leaf(3, H256::from_low_u64_be(3));
+
+        caches.invalidate_from(2);
+
+        assert!(caches.get_leaf(1).is_some());
+        assert!(caches.get_leaf(2).is_none());
+        assert!(caches.get_leaf(3).is_none());
+
+        let (leaves, _, _) = caches.stats();
+        assert_eq!(leaves.invalidations, 2);
+    }
+
+    #[test]
+    fn a_category_with_zero_bound_disables_caching() {
+        let caches = Caches::new(CacheConfig {
+            leaves: CategoryConfig {
+                entries: 0,
+                max_bytes: 0,
+            },
+            messages: CategoryConfig {
+                entries: 1024,
+                max_bytes: 1024 * 1024,
+            },
+            proofs: CategoryConfig {
+                entries: 1024,
+                max_bytes: 1024 * 1024,
+            },
+        });
+
+        caches.put_leaf(1, H256::from_low_u64_be(1));
+        assert!(caches.get_leaf(1).is_none());
+    }
+}
@@ -1,23 +1,28 @@
 use color_eyre::{
-    eyre::{ensure, WrapErr},
+    eyre::{ensure, eyre, WrapErr},
     Result,
 };
 use ethers::core::types::H256;
+use ethers::utils::keccak256;
 use nomad_core::db::{DbError, TypedDB, DB};
 use nomad_core::{
-    accumulator::NomadProof, utils, CommittedMessage, Decode, NomadMessage, RawCommittedMessage,
-    SignedUpdate, SignedUpdateWithMeta, UpdateMeta,
+    accumulator::NomadProof, utils, CommittedMessage, Decode, Encode, NomadMessage,
+    RawCommittedMessage, SignedUpdate, SignedUpdateWithMeta, UpdateMeta,
 };
 use nomad_xyz_configuration::contracts::CoreContracts;
 use nomad_xyz_configuration::NomadConfig;
-use tokio::time::sleep;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
 use std::future::Future;
-use std::time::Duration;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use tokio::sync::broadcast;
 
 use nomad_core::db::iterator::PrefixIterator;
 
+use crate::cache::{CacheConfig, Caches};
+
 const LEAF_IDX: &str = "leaf_index_";
 const LEAF: &str = "leaf_";
 const PREV_ROOT: &str = "update_prev_root_";
@@ -32,16 +37,166 @@ const PROVER_LATEST_COMMITTED: &str = "prover_latest_committed_";
 const PROCESSOR_ATTEMPTED: &str = "processor_attempted_";
 
 const CORE_INTEGRITY: &str = "core_ingerity_";
+const CORE_INTEGRITY_RECORD: &str = "core_integrity_record_";
+const CORE_INTEGRITY_HISTORY: &str = "core_integrity_history_";
+
+const CHECKPOINT_ROOT: &str = "checkpoint_root_";
+const CHECKPOINT_LEAF_INDEX: &str = "checkpoint_leaf_index_";
+
+const SNAPSHOT_TAG_CHECKPOINT: u8 = 0;
+const SNAPSHOT_TAG_MESSAGE: u8 = 1;
+const SNAPSHOT_TAG_PROOF: u8 = 2;
+const SNAPSHOT_TAG_UPDATE: u8 = 3;
+const SNAPSHOT_TAG_UPDATE_META: u8 = 4;
+
+/// Write one length-framed snapshot record: a tag byte identifying the
+/// record kind, the payload's 4-byte big-endian length, then the payload
+/// itself -- each of `fields` with its own 4-byte big-endian length
+/// prefix, so [`read_snapshot_fields`] can split a
+/// record's payload back into its original fields without a schema.
+fn write_snapshot_record(writer: &mut impl Write, tag: u8, fields: &[Vec<u8>]) -> Result<(), DbError> {
+    let mut payload = Vec::new();
+    for field in fields {
+        payload.extend_from_slice(&(field.len() as u32).to_be_bytes());
+        payload.extend_from_slice(field);
+    }
+    writer.write_all(&[tag])?;
+    writer.write_all(&(payload.len() as u32).to_be_bytes())?;
+    writer.write_all(&payload)?;
+    Ok(())
+}
+
+/// Split a snapshot record's payload back into the fields
+/// [`write_snapshot_record`] framed it from.
+fn read_snapshot_fields(payload: &[u8]) -> Vec<Vec<u8>> {
+    let mut fields = Vec::new();
+    let mut cursor = payload;
+    while cursor.len() >= 4 {
+        let len = u32::from_be_bytes(cursor[..4].try_into().expect("checked above")) as usize;
+        cursor = &cursor[4..];
+        if cursor.len() < len {
+            break;
+        }
+        fields.push(cursor[..len].to_vec());
+        cursor = &cursor[len..];
+    }
+    fields
+}
+
+/// Per-core integrity bookkeeping: a content hash over the full core
+/// definition (so the DB doesn't have to keep a second full JSON copy
+/// just to detect a change), the core's deploy height, and a monotonic
+/// version bumped each time [`NomadDB::force_reconcile`] accepts a new
+/// definition.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct CoreIntegrityRecord {
+    content_hash: H256,
+    deploy_height: u64,
+    version: u64,
+}
+
+/// Which fields of a core definition, at any depth, changed between the
+/// persisted record and a newly observed one, surfaced when
+/// [`NomadDB::check_core_integrity`] detects a mismatch.
+#[derive(Debug, Clone, Default)]
+pub struct CoreIntegrityDiff {
+    /// Dotted JSON paths (e.g. `home.proxy`, `replicas.goerli.proxy`) that
+    /// differ between the two definitions.
+    pub changed_fields: Vec<String>,
+}
+
+impl std::fmt::Display for CoreIntegrityDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "changed fields: {}", self.changed_fields.join(", "))
+    }
+}
+
+fn core_content_hash(core: &CoreContracts) -> Result<H256> {
+    let serialized = serde_json::to_vec(core)?;
+    Ok(H256::from(keccak256(serialized)))
+}
+
+fn core_deploy_height(core: &CoreContracts) -> Result<u64> {
+    let value = serde_json::to_value(core)?;
+    value
+        .get("deployHeight")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| eyre!("core definition missing deployHeight"))
+}
+
+/// Recursively collect the dotted JSON paths at which `old` and `new`
+/// differ, e.g. `home.proxy` or `replicas.goerli.proxy`.
+fn diff_core_json(old: &serde_json::Value, new: &serde_json::Value, path: &str, changed: &mut Vec<String>) {
+    match (old, new) {
+        (serde_json::Value::Object(o), serde_json::Value::Object(n)) => {
+            let mut keys: Vec<&String> = o.keys().chain(n.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match (o.get(key), n.get(key)) {
+                    (Some(ov), Some(nv)) => diff_core_json(ov, nv, &child_path, changed),
+                    _ => changed.push(child_path),
+                }
+            }
+        }
+        _ => {
+            if old != new {
+                changed.push(path.to_owned());
+            }
+        }
+    }
+}
+
+/// Backlog of unreceived notifications a [`NomadDB::subscribe_leaves`]/
+/// [`NomadDB::subscribe_updates`] subscriber can fall behind by before
+/// `broadcast::Receiver::recv` starts reporting `Lagged`. Generous relative
+/// to how bursty a resync can get, since a lagged receiver just falls back
+/// to a direct DB read rather than losing the notification entirely.
+const NOTIFY_CHANNEL_CAPACITY: usize = 256;
+
+/// Per-category notification channels, fired after a successful write so
+/// waiters (`wait_for_leaf`, `wait_for_proof`, and any `subscribe_*`
+/// consumer) react immediately instead of polling the DB.
+struct Notifiers {
+    leaves: broadcast::Sender<u32>,
+    updates: broadcast::Sender<H256>,
+    proofs: broadcast::Sender<u32>,
+}
+
+impl Default for Notifiers {
+    fn default() -> Self {
+        Self {
+            leaves: broadcast::channel(NOTIFY_CHANNEL_CAPACITY).0,
+            updates: broadcast::channel(NOTIFY_CHANNEL_CAPACITY).0,
+            proofs: broadcast::channel(NOTIFY_CHANNEL_CAPACITY).0,
+        }
+    }
+}
+
+impl std::fmt::Debug for Notifiers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Notifiers").finish_non_exhaustive()
+    }
+}
 
 /// DB handle for storing data tied to a specific home.
 ///
 /// Key structure: ```<entity>_<additional_prefix(es)>_<key>```
 #[derive(Debug, Clone)]
-pub struct NomadDB(TypedDB);
+pub struct NomadDB(TypedDB, Arc<Notifiers>, Arc<Caches>);
 
 impl From<TypedDB> for NomadDB {
     fn from(db: TypedDB) -> Self {
-        NomadDB(db)
+        NomadDB(
+            db,
+            Arc::new(Notifiers::default()),
+            Arc::new(Caches::new(CacheConfig::default())),
+        )
     }
 }
 
@@ -68,7 +223,50 @@ impl AsRef<DB> for NomadDB {
 impl NomadDB {
     /// Instantiated new `NomadDB`
     pub fn new(entity: impl AsRef<str>, db: DB) -> Self {
-        Self(TypedDB::new(entity.as_ref().to_owned(), db))
+        Self::with_cache_config(entity, db, CacheConfig::default())
+    }
+
+    /// As [`Self::new`], with explicit per-category cache capacities
+    /// instead of [`CacheConfig::default`]. Pass a `CacheConfig` with a
+    /// field set to `0` to disable that category's cache entirely.
+    pub fn with_cache_config(entity: impl AsRef<str>, db: DB, cache_config: CacheConfig) -> Self {
+        Self(
+            TypedDB::new(entity.as_ref().to_owned(), db),
+            Arc::new(Notifiers::default()),
+            Arc::new(Caches::new(cache_config)),
+        )
+    }
+
+    /// Subscribe to newly-stored leaves, e.g. to react to `store_leaf`
+    /// without polling. Missed notifications (subscriber fell behind by
+    /// more than [`NOTIFY_CHANNEL_CAPACITY`]) surface as
+    /// `broadcast::error::RecvError::Lagged`; callers that only care about
+    /// "something changed, go re-check the DB" can treat that the same as a
+    /// delivered notification.
+    pub fn subscribe_leaves(&self) -> broadcast::Receiver<u32> {
+        self.1.leaves.subscribe()
+    }
+
+    /// Subscribe to newly-stored updates' new roots, e.g. to react to
+    /// `store_latest_update` without polling. See
+    /// [`Self::subscribe_leaves`] for lagged-receiver behavior.
+    pub fn subscribe_updates(&self) -> broadcast::Receiver<H256> {
+        self.1.updates.subscribe()
+    }
+
+    /// Drop every cached leaf/message/proof at or past `from_leaf_index`.
+    /// Call this once a reorg is detected to rewrite the DB from that
+    /// index onward, so a cache hit can never hand back a leaf/message/
+    /// proof the chain no longer agrees with during fraud detection.
+    pub fn invalidate_cache_from(&self, from_leaf_index: u32) {
+        self.2.invalidate_from(from_leaf_index)
+    }
+
+    /// Snapshot hit/miss/eviction/invalidation counters for the leaf,
+    /// message, and proof caches, in that order, for exposing as
+    /// Prometheus gauges.
+    pub fn cache_stats(&self) -> (crate::cache::CacheStats, crate::cache::CacheStats, crate::cache::CacheStats) {
+        self.2.stats()
     }
 
     /// Check if db is empty
@@ -119,6 +317,7 @@ impl NomadDB {
         );
         self.store_leaf(message.leaf_index, destination_and_nonce, leaf)?;
         self.store_keyed_encodable(MESSAGE, &leaf, message)?;
+        self.2.put_message(leaf, message.clone());
         Ok(())
     }
 
@@ -169,17 +368,36 @@ impl NomadDB {
             "storing leaf hash keyed by index and dest+nonce"
         );
         self.store_keyed_encodable(LEAF, &destination_and_nonce, &leaf)?;
-        self.store_keyed_encodable(LEAF, &leaf_index, &leaf)
+        self.store_keyed_encodable(LEAF, &leaf_index, &leaf)?;
+        self.2.put_leaf(leaf_index, leaf);
+        let _ = self.1.leaves.send(leaf_index);
+        Ok(())
     }
 
     /// Retrieve a raw committed message by its leaf hash
     pub fn message_by_leaf(&self, leaf: H256) -> Result<Option<RawCommittedMessage>, DbError> {
-        self.retrieve_keyed_decodable(MESSAGE, &leaf)
+        if let Some(message) = self.2.get_message(leaf) {
+            return Ok(Some(message));
+        }
+
+        let message: Option<RawCommittedMessage> = self.retrieve_keyed_decodable(MESSAGE, &leaf)?;
+        if let Some(message) = &message {
+            self.2.put_message(leaf, message.clone());
+        }
+        Ok(message)
     }
 
     /// Retrieve the leaf hash keyed by leaf index
     pub fn leaf_by_leaf_index(&self, leaf_index: u32) -> Result<Option<H256>, DbError> {
-        self.retrieve_keyed_decodable(LEAF, &leaf_index)
+        if let Some(leaf) = self.2.get_leaf(leaf_index) {
+            return Ok(Some(leaf));
+        }
+
+        let leaf: Option<H256> = self.retrieve_keyed_decodable(LEAF, &leaf_index)?;
+        if let Some(leaf) = leaf {
+            self.2.put_leaf(leaf_index, leaf);
+        }
+        Ok(leaf)
     }
 
     /// Retrieve the leaf hash keyed by destination and nonce
@@ -292,7 +510,9 @@ impl NomadDB {
             None => self.store_latest_root(update.update.new_root)?,
         }
 
-        self.store_update(update)
+        self.store_update(update)?;
+        let _ = self.1.updates.send(update.update.new_root);
+        Ok(())
     }
 
     /// Store an update.
@@ -328,8 +548,11 @@ impl NomadDB {
     }
 
     /// Iterate over all leaves
-    pub fn leaf_iterator(&self) -> PrefixIterator<H256> {
-        PrefixIterator::new(self.0.as_ref().prefix_iterator(LEAF_IDX), LEAF_IDX.as_ref())
+    pub fn leaf_iterator(&self) -> Result<PrefixIterator<H256>, DbError> {
+        Ok(PrefixIterator::new(
+            self.0.as_ref().prefix_iterator(LEAF_IDX)?,
+            LEAF_IDX.as_ref(),
+        ))
     }
 
     /// Store a proof by its leaf index
@@ -338,24 +561,77 @@ impl NomadDB {
     /// - `leaf_index` --> `proof`
     pub fn store_proof(&self, leaf_index: u32, proof: &NomadProof) -> Result<(), DbError> {
         debug!(leaf_index, "storing proof in DB");
-        self.store_keyed_encodable(PROOF, &leaf_index, proof)
+        self.store_keyed_encodable(PROOF, &leaf_index, proof)?;
+        self.2.put_proof(leaf_index, proof.clone());
+        let _ = self.1.proofs.send(leaf_index);
+        Ok(())
     }
 
     /// Retrieve a proof by its leaf index
     pub fn proof_by_leaf_index(&self, leaf_index: u32) -> Result<Option<NomadProof>, DbError> {
-        self.retrieve_keyed_decodable(PROOF, &leaf_index)
+        if let Some(proof) = self.2.get_proof(leaf_index) {
+            return Ok(Some(proof));
+        }
+
+        let proof: Option<NomadProof> = self.retrieve_keyed_decodable(PROOF, &leaf_index)?;
+        if let Some(proof) = &proof {
+            self.2.put_proof(leaf_index, proof.clone());
+        }
+        Ok(proof)
     }
 
-    // TODO(james): this is a quick-fix for the prover_sync and I don't like it
-    /// poll db ever 100 milliseconds waiting for a leaf.
+    /// Wait for the leaf at `leaf_index` to be stored, returning it as soon
+    /// as `store_leaf` notifies this of a matching write rather than
+    /// polling the DB.
     pub fn wait_for_leaf(&self, leaf_index: u32) -> impl Future<Output = Result<H256, DbError>> {
         let slf = self.clone();
         async move {
+            // One initial read, before subscribing, covers the race where
+            // the leaf (and its notification) landed before this call.
+            if let Some(leaf) = slf.leaf_by_leaf_index(leaf_index)? {
+                return Ok(leaf);
+            }
+
+            let mut leaves = slf.1.leaves.subscribe();
             loop {
-                if let Some(leaf) = slf.leaf_by_leaf_index(leaf_index)? {
-                    return Ok(leaf);
+                match leaves.recv().await {
+                    Ok(stored_index) if stored_index != leaf_index => continue,
+                    // Either this is our index, we lagged and may have
+                    // missed it, or every sender is gone (can't happen
+                    // while `slf` holds the `Arc` that owns them) -- in
+                    // every case, re-check the DB rather than assume.
+                    _ => {
+                        if let Some(leaf) = slf.leaf_by_leaf_index(leaf_index)? {
+                            return Ok(leaf);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// As [`Self::wait_for_leaf`], but for the proof stored at `leaf_index`
+    /// via `store_proof`.
+    pub fn wait_for_proof(
+        &self,
+        leaf_index: u32,
+    ) -> impl Future<Output = Result<NomadProof, DbError>> {
+        let slf = self.clone();
+        async move {
+            if let Some(proof) = slf.proof_by_leaf_index(leaf_index)? {
+                return Ok(proof);
+            }
+
+            let mut proofs = slf.1.proofs.subscribe();
+            loop {
+                match proofs.recv().await {
+                    Ok(stored_index) if stored_index != leaf_index => continue,
+                    _ => {
+                        if let Some(proof) = slf.proof_by_leaf_index(leaf_index)? {
+                            return Ok(proof);
+                        }
+                    }
                 }
-                sleep(Duration::from_millis(100)).await
             }
         }
     }
@@ -419,14 +695,111 @@ impl NomadDB {
         Ok(None)
     }
 
+    /// Retrieve the persisted integrity bookkeeping for `name`, if any.
+    fn retrieve_core_integrity_record(&self, name: &str) -> Result<Option<CoreIntegrityRecord>> {
+        if let Some(json) =
+            self.retrieve_keyed_decodable::<_, _, String>(CORE_INTEGRITY_RECORD, &name.to_owned())?
+        {
+            return Ok(Some(serde_json::from_str(&json)?));
+        }
+        Ok(None)
+    }
+
+    fn store_core_integrity_record(&self, name: &str, record: &CoreIntegrityRecord) -> Result<()> {
+        let serialized = serde_json::to_string(record)?;
+        Ok(self.store_keyed_encodable(CORE_INTEGRITY_RECORD, &name.to_owned(), &serialized)?)
+    }
+
+    /// Append `core` to `name`'s accepted-core history at `version`, so an
+    /// audit can see when and how a core's contract addresses changed
+    /// over the agent's lifetime.
+    fn append_core_integrity_history(&self, name: &str, version: u64, core: &CoreContracts) -> Result<()> {
+        let key = format!("{}:{}", name, version);
+        let serialized = serde_json::to_string(core)?;
+        Ok(self.store_keyed_encodable(CORE_INTEGRITY_HISTORY, &key, &serialized)?)
+    }
+
+    /// Retrieve `name`'s accepted-core history entry at `version`, if any.
+    pub fn retrieve_core_integrity_history(
+        &self,
+        name: &str,
+        version: u64,
+    ) -> Result<Option<CoreContracts>> {
+        let key = format!("{}:{}", name, version);
+        if let Some(json) =
+            self.retrieve_keyed_decodable::<_, _, String>(CORE_INTEGRITY_HISTORY, &key)?
+        {
+            return Ok(Some(serde_json::from_str(&json)?));
+        }
+        Ok(None)
+    }
+
     /// Check a core's integrity against the DB. If there is no persisted
-    /// object for that core, store it for later integrity checks
+    /// record for that core, this is the first sight of it and it is
+    /// stored (with an initial accepted-core history entry at version 1)
+    /// for later checks. On a later mismatch, returns an error naming
+    /// which fields changed, as dotted paths (see [`CoreIntegrityDiff`]) instead
+    /// of an opaque "integrity check failed"; accepting a legitimate
+    /// change requires an explicit [`Self::force_reconcile`] call.
     pub fn check_core_integrity(&self, name: &str, core: &CoreContracts) -> Result<()> {
-        if let Some(integrity) = self.retrieve_core(name)? {
-            ensure!(integrity == *core, "integrity check failed");
-        } else {
-            self.store_core(name, core)?;
+        let content_hash = core_content_hash(core)?;
+        let deploy_height = core_deploy_height(core)?;
+
+        match self.retrieve_core_integrity_record(name)? {
+            Some(record) if record.content_hash == content_hash => Ok(()),
+            Some(_) => {
+                let mut changed_fields = Vec::new();
+                if let Some(persisted) = self.retrieve_core(name)? {
+                    let old = serde_json::to_value(persisted)?;
+                    let new = serde_json::to_value(core)?;
+                    diff_core_json(&old, &new, "", &mut changed_fields);
+                }
+                Err(eyre!(
+                    "integrity check failed for core {}: {}; call force_reconcile to accept this as a legitimate upgrade",
+                    name,
+                    CoreIntegrityDiff { changed_fields }
+                ))
+            }
+            None => {
+                self.store_core(name, core)?;
+                self.store_core_integrity_record(
+                    name,
+                    &CoreIntegrityRecord {
+                        content_hash,
+                        deploy_height,
+                        version: 1,
+                    },
+                )?;
+                self.append_core_integrity_history(name, 1, core)?;
+                Ok(())
+            }
         }
+    }
+
+    /// Explicitly accept `core` as `name`'s new definition -- e.g. after
+    /// an operator has verified a legitimate upgrade (a new replica added
+    /// to an existing core) rather than a silent address change.
+    /// [`Self::check_core_integrity`] will otherwise keep rejecting
+    /// `core` as a mismatch. Bumps the monotonic version and appends to
+    /// the accepted-core history.
+    pub fn force_reconcile(&self, name: &str, core: &CoreContracts) -> Result<()> {
+        let content_hash = core_content_hash(core)?;
+        let deploy_height = core_deploy_height(core)?;
+        let version = self
+            .retrieve_core_integrity_record(name)?
+            .map(|record| record.version + 1)
+            .unwrap_or(1);
+
+        self.store_core(name, core)?;
+        self.store_core_integrity_record(
+            name,
+            &CoreIntegrityRecord {
+                content_hash,
+                deploy_height,
+                version,
+            },
+        )?;
+        self.append_core_integrity_history(name, version, core)?;
         Ok(())
     }
 
@@ -439,6 +812,199 @@ impl NomadDB {
         }
         Ok(())
     }
+
+    /// Record `root`/`leaf_index` as a trusted checkpoint: a committed
+    /// root the operator already trusts, below which [`Self::prune_below`]
+    /// is allowed to discard history and from which
+    /// [`Self::export_snapshot`] exports a portable bootstrap snapshot.
+    pub fn set_checkpoint(&self, root: H256, leaf_index: u32) -> Result<(), DbError> {
+        debug!(leaf_index, root = ?root, "setting trusted checkpoint");
+        self.store_encodable("", CHECKPOINT_ROOT, &root)?;
+        self.store_encodable("", CHECKPOINT_LEAF_INDEX, &leaf_index)
+    }
+
+    /// Retrieve the trusted checkpoint set by [`Self::set_checkpoint`], if
+    /// any.
+    pub fn retrieve_checkpoint(&self) -> Result<Option<(H256, u32)>, DbError> {
+        let root: Option<H256> = self.retrieve_decodable("", CHECKPOINT_ROOT)?;
+        let leaf_index: Option<u32> = self.retrieve_decodable("", CHECKPOINT_LEAF_INDEX)?;
+        Ok(root.zip(leaf_index))
+    }
+
+    /// Delete the leaf, its message, and its proof for `leaf_index`, if
+    /// present.
+    fn prune_leaf(&self, leaf_index: u32) -> Result<(), DbError> {
+        if let Some(leaf) = self.leaf_by_leaf_index(leaf_index)? {
+            if let Some(message) = self.message_by_leaf(leaf)? {
+                if let Ok(parsed) = NomadMessage::read_from(&mut message.message.as_slice()) {
+                    self.delete_keyed_encodable(LEAF, &parsed.destination_and_nonce())?;
+                }
+                self.delete_keyed_encodable(MESSAGE, &leaf)?;
+            }
+            self.delete_keyed_encodable(LEAF, &leaf_index)?;
+        }
+        self.delete_keyed_encodable(PROOF, &leaf_index)
+    }
+
+    /// Delete every update strictly before the one that arrives at
+    /// `checkpoint_root`. The transition into the checkpoint (and its
+    /// metadata) is kept as the checkpoint's own provenance; everything
+    /// further back is safe to discard.
+    fn prune_updates_before(&self, checkpoint_root: H256) -> Result<(), DbError> {
+        let mut new_root = match self.retrieve_keyed_decodable::<_, H256, H256>(PREV_ROOT, &checkpoint_root)? {
+            Some(previous_root) => previous_root,
+            None => return Ok(()), // checkpoint is genesis; nothing earlier exists
+        };
+
+        loop {
+            let previous_root: Option<H256> = self.retrieve_keyed_decodable(PREV_ROOT, &new_root)?;
+            self.delete_keyed_encodable(UPDATE_META, &new_root)?;
+            self.delete_keyed_encodable(PREV_ROOT, &new_root)?;
+            match previous_root {
+                Some(previous_root) => {
+                    self.delete_keyed_encodable(UPDATE, &previous_root)?;
+                    new_root = previous_root;
+                }
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delete history strictly below the trusted checkpoint set via
+    /// [`Self::set_checkpoint`], up to (but never past) the checkpoint's
+    /// own leaf index. Preserves the `LATEST_*`,
+    /// `PROVER_LATEST_COMMITTED`, and `CORE_INTEGRITY` pointers, and the
+    /// checkpoint itself, so the accumulator and integrity checks keep
+    /// working from the checkpoint forward. A leaf whose message the
+    /// processor has not yet attempted is left in place, since its proof
+    /// may still be required to process it.
+    pub fn prune_below(&self, leaf_index: u32) -> Result<()> {
+        let (checkpoint_root, checkpoint_leaf_index) = self
+            .retrieve_checkpoint()?
+            .ok_or_else(|| eyre!("cannot prune without a trusted checkpoint"))?;
+        ensure!(
+            leaf_index <= checkpoint_leaf_index,
+            "refusing to prune past the trusted checkpoint"
+        );
+
+        for index in 0..leaf_index {
+            if let Some(message) = self.message_by_leaf_index(index)? {
+                let committed: CommittedMessage = message.try_into()?;
+                if !self.previously_attempted(&committed)? {
+                    debug!(
+                        leaf_index = index,
+                        "leaving unprocessed leaf in place while pruning"
+                    );
+                    continue;
+                }
+            }
+            self.prune_leaf(index)?;
+        }
+
+        self.prune_updates_before(checkpoint_root)?;
+        Ok(())
+    }
+
+    /// Serialize every live entry at/above the trusted checkpoint set via
+    /// [`Self::set_checkpoint`] -- the checkpoint itself, every stored
+    /// leaf/message/proof from the checkpoint's leaf index through
+    /// [`Self::retrieve_latest_leaf_index`], and every update from the
+    /// checkpoint root forward -- into a portable stream that
+    /// [`Self::import_snapshot`] can bulk-load into a fresh DB.
+    pub fn export_snapshot(&self, writer: &mut impl Write) -> Result<()> {
+        let (checkpoint_root, checkpoint_leaf_index) = self
+            .retrieve_checkpoint()?
+            .ok_or_else(|| eyre!("cannot export a snapshot without a trusted checkpoint"))?;
+
+        write_snapshot_record(
+            writer,
+            SNAPSHOT_TAG_CHECKPOINT,
+            &[checkpoint_root.to_vec(), checkpoint_leaf_index.to_vec()],
+        )?;
+
+        let latest_leaf_index = self
+            .retrieve_latest_leaf_index()?
+            .unwrap_or(checkpoint_leaf_index);
+        for index in checkpoint_leaf_index..=latest_leaf_index {
+            if let Some(message) = self.message_by_leaf_index(index)? {
+                write_snapshot_record(writer, SNAPSHOT_TAG_MESSAGE, &[message.to_vec()])?;
+            }
+            if let Some(proof) = self.proof_by_leaf_index(index)? {
+                write_snapshot_record(writer, SNAPSHOT_TAG_PROOF, &[index.to_vec(), proof.to_vec()])?;
+            }
+        }
+
+        let mut previous_root = checkpoint_root;
+        while let Some(update) = self.update_by_previous_root(previous_root)? {
+            write_snapshot_record(writer, SNAPSHOT_TAG_UPDATE, &[update.to_vec()])?;
+            if let Some(meta) = self.retrieve_update_metadata(update.update.new_root)? {
+                write_snapshot_record(
+                    writer,
+                    SNAPSHOT_TAG_UPDATE_META,
+                    &[update.update.new_root.to_vec(), meta.to_vec()],
+                )?;
+            }
+            previous_root = update.update.new_root;
+        }
+
+        Ok(())
+    }
+
+    /// Bulk-load a snapshot produced by [`Self::export_snapshot`], letting
+    /// a new agent bootstrap from a trusted checkpoint instead of
+    /// replaying history from genesis.
+    pub fn import_snapshot(&self, reader: &mut impl Read) -> Result<()> {
+        loop {
+            let mut tag = [0u8; 1];
+            match reader.read_exact(&mut tag) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(DbError::from(e).into()),
+            }
+
+            let mut len_bytes = [0u8; 4];
+            reader.read_exact(&mut len_bytes)?;
+            let mut payload = vec![0u8; u32::from_be_bytes(len_bytes) as usize];
+            reader.read_exact(&mut payload)?;
+            let fields = read_snapshot_fields(&payload);
+
+            match tag[0] {
+                SNAPSHOT_TAG_CHECKPOINT => {
+                    ensure!(fields.len() == 2, "malformed checkpoint snapshot record");
+                    let root = H256::read_from(&mut fields[0].as_slice())?;
+                    let leaf_index = u32::read_from(&mut fields[1].as_slice())?;
+                    self.set_checkpoint(root, leaf_index)?;
+                }
+                SNAPSHOT_TAG_MESSAGE => {
+                    ensure!(fields.len() == 1, "malformed message snapshot record");
+                    let message = RawCommittedMessage::read_from(&mut fields[0].as_slice())?;
+                    self.store_raw_committed_message(&message)?;
+                }
+                SNAPSHOT_TAG_PROOF => {
+                    ensure!(fields.len() == 2, "malformed proof snapshot record");
+                    let leaf_index = u32::read_from(&mut fields[0].as_slice())?;
+                    let proof = NomadProof::read_from(&mut fields[1].as_slice())?;
+                    self.store_proof(leaf_index, &proof)?;
+                }
+                SNAPSHOT_TAG_UPDATE => {
+                    ensure!(fields.len() == 1, "malformed update snapshot record");
+                    let update = SignedUpdate::read_from(&mut fields[0].as_slice())?;
+                    self.store_update(&update)?;
+                }
+                SNAPSHOT_TAG_UPDATE_META => {
+                    ensure!(fields.len() == 2, "malformed update metadata snapshot record");
+                    let new_root = H256::read_from(&mut fields[0].as_slice())?;
+                    let meta = UpdateMeta::read_from(&mut fields[1].as_slice())?;
+                    self.store_keyed_encodable(UPDATE_META, &new_root, &meta)?;
+                }
+                other => return Err(eyre!("unknown snapshot record tag: {other}")),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
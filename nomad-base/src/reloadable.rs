@@ -0,0 +1,126 @@
+//! An atomically-swappable, validated config snapshot.
+//!
+//! `AgentCore` freezes its settings at construction today, so retuning a
+//! running watcher (enabling a newly-deployed replica, adjusting
+//! `IndexSettings`/`PageSettings` pacing) means a restart. [`Reloadable`]
+//! is the primitive a hot-reload path would sit behind: readers always see
+//! one complete, already-validated snapshot (never a value torn mid-update
+//! across fields), and a candidate reload is checked before it's installed
+//! so a bad config can't take down whatever's running on the last good
+//! one.
+//!
+//! Note: `AgentCore` itself, and the file-path-plus-env-overlay config
+//! loader that would feed a new snapshot in here, aren't present in this
+//! snapshot of the workspace -- wiring `Reloadable` in as `AgentCore`'s
+//! settings field, and watching the source config for changes, is the
+//! follow-up once those files exist in this tree.
+
+use std::sync::{Arc, RwLock};
+
+/// Holds the current snapshot of a `T`, swappable for a new one without
+/// readers ever observing a partially-applied update. Cheap to read: a
+/// clone of the `Arc`, not of `T` itself.
+pub struct Reloadable<T> {
+    current: RwLock<Arc<T>>,
+}
+
+impl<T> Reloadable<T> {
+    /// Start out holding `initial`.
+    pub fn new(initial: T) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(initial)),
+        }
+    }
+
+    /// The snapshot currently in effect.
+    pub fn current(&self) -> Arc<T> {
+        self.current.read().expect("reloadable lock poisoned").clone()
+    }
+
+    /// Validate `candidate` and, if it passes, install it as the new
+    /// current snapshot; otherwise leave the previous good snapshot
+    /// running untouched and return the validation error.
+    pub fn try_reload<E>(
+        &self,
+        candidate: T,
+        validate: impl FnOnce(&T) -> Result<(), E>,
+    ) -> Result<(), E> {
+        validate(&candidate)?;
+        *self.current.write().expect("reloadable lock poisoned") = Arc::new(candidate);
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Reloadable<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reloadable")
+            .field("current", &self.current())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn current_reflects_a_successful_reload() {
+        let reloadable = Reloadable::new(1);
+        assert_eq!(*reloadable.current(), 1);
+
+        reloadable
+            .try_reload(2, |_: &i32| Ok::<(), String>(()))
+            .unwrap();
+        assert_eq!(*reloadable.current(), 2);
+    }
+
+    #[test]
+    fn a_rejected_reload_keeps_the_previous_snapshot_running() {
+        let reloadable = Reloadable::new(1);
+
+        let err = reloadable
+            .try_reload(-1, |candidate: &i32| {
+                if *candidate < 0 {
+                    Err("candidate must be non-negative".to_owned())
+                } else {
+                    Ok(())
+                }
+            })
+            .unwrap_err();
+
+        assert_eq!(err, "candidate must be non-negative");
+        assert_eq!(*reloadable.current(), 1);
+    }
+
+    #[test]
+    fn readers_never_observe_a_torn_update() {
+        use std::sync::Arc;
+        use std::thread;
+
+        #[derive(Debug)]
+        struct Pair {
+            a: i32,
+            b: i32,
+        }
+
+        let reloadable = Arc::new(Reloadable::new(Pair { a: 0, b: 0 }));
+
+        let writer = {
+            let reloadable = reloadable.clone();
+            thread::spawn(move || {
+                for i in 1..100 {
+                    reloadable
+                        .try_reload(Pair { a: i, b: i }, |_: &Pair| Ok::<(), ()>(()))
+                        .unwrap();
+                }
+            })
+        };
+
+        for _ in 0..100 {
+            let snapshot = reloadable.current();
+            assert_eq!(snapshot.a, snapshot.b);
+        }
+
+        writer.join().unwrap();
+    }
+}
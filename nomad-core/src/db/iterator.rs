@@ -0,0 +1,50 @@
+//! Decoding wrapper over a raw [`super::KvStore::prefix_iterator`] scan.
+
+use crate::Decode;
+use std::marker::PhantomData;
+
+/// Decodes each value yielded by a prefix scan into `T`, skipping entries
+/// that fail to decode rather than aborting the whole scan -- a backend
+/// swap or an in-flight schema change should degrade to "missing entry",
+/// not a panic partway through iteration.
+pub struct PrefixIterator<'a, T> {
+    inner: Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a>,
+    prefix: Box<[u8]>,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> PrefixIterator<'a, T> {
+    /// Wrap `inner` -- the raw `(key, value)` pairs from a
+    /// [`super::DB::prefix_iterator`] call -- decoding each value as `T`.
+    ///
+    /// `prefix` is enforced here too, not just trusted from the backend:
+    /// a backend (e.g. `rocksdb`, absent a configured prefix extractor)
+    /// can hand back a plain forward scan that runs past the prefix
+    /// boundary, and this is the one place every backend funnels through.
+    pub fn new(inner: Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a>, prefix: &[u8]) -> Self {
+        Self {
+            inner,
+            prefix: prefix.into(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Decode> Iterator for PrefixIterator<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for (key, value) in self.inner.by_ref() {
+            // Backends yield keys in order, so the matching run is
+            // contiguous -- the first mismatch means the scan has run
+            // past the prefix and the rest belongs to the next one.
+            if !key.starts_with(self.prefix.as_ref()) {
+                return None;
+            }
+            if let Ok(decoded) = T::read_from(&mut value.as_ref()) {
+                return Some(decoded);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,116 @@
+use super::{BatchOp, DbError, KvStore};
+use heed::types::ByteSlice;
+use heed::{Database, Env, EnvOpenOptions};
+use std::path::Path;
+
+/// An LMDB-backed [`KvStore`] via the [`heed`] bindings -- a
+/// memory-mapped, copy-on-write alternative to RocksDB's LSM tree, for
+/// read-heavy agents (Relayer, Processor) on memory-constrained nodes where
+/// avoiding RocksDB's background compaction matters more than raw write
+/// throughput.
+#[derive(Debug)]
+pub struct LmdbStore {
+    env: Env,
+    db: Database<ByteSlice, ByteSlice>,
+}
+
+impl LmdbStore {
+    /// Open (or create) an LMDB environment at `path`, with a single
+    /// unnamed database.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DbError> {
+        std::fs::create_dir_all(&path).map_err(|e| DbError::EngineError(e.to_string()))?;
+
+        let env = EnvOpenOptions::new()
+            .open(path)
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+
+        let mut txn = env
+            .write_txn()
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        let db = env
+            .create_database(&mut txn, None)
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        txn.commit().map_err(|e| DbError::EngineError(e.to_string()))?;
+
+        Ok(Self { env, db })
+    }
+}
+
+impl KvStore for LmdbStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, DbError> {
+        let txn = self
+            .env
+            .read_txn()
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        Ok(self
+            .db
+            .get(&txn, key)
+            .map_err(|e| DbError::EngineError(e.to_string()))?
+            .map(|value| value.to_vec()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), DbError> {
+        let mut txn = self
+            .env
+            .write_txn()
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        self.db
+            .put(&mut txn, key, value)
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        txn.commit().map_err(|e| DbError::EngineError(e.to_string()))
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), DbError> {
+        let mut txn = self
+            .env
+            .write_txn()
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        self.db
+            .delete(&mut txn, key)
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        txn.commit().map_err(|e| DbError::EngineError(e.to_string()))
+    }
+
+    fn prefix_iterator(
+        &self,
+        prefix: &[u8],
+    ) -> Result<Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + '_>, DbError> {
+        // Collect eagerly within one read transaction rather than returning
+        // a borrowing iterator: `KvStore::prefix_iterator` has no txn of
+        // its own to hand back to the caller, and per-entity key spaces are
+        // small enough for this not to matter.
+        let txn = self
+            .env
+            .read_txn()
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        let entries: Vec<_> = self
+            .db
+            .prefix_iter(&txn, prefix)
+            .map_err(|e| DbError::EngineError(e.to_string()))?
+            .map_while(Result::ok)
+            .map(|(key, value)| (Box::from(key), Box::from(value)))
+            .collect();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn write_batch(&self, batch: Vec<BatchOp>) -> Result<(), DbError> {
+        let mut txn = self
+            .env
+            .write_txn()
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        for op in batch {
+            match op {
+                BatchOp::Put(key, value) => self
+                    .db
+                    .put(&mut txn, &key, &value)
+                    .map_err(|e| DbError::EngineError(e.to_string()))?,
+                BatchOp::Delete(key) => {
+                    self.db
+                        .delete(&mut txn, &key)
+                        .map_err(|e| DbError::EngineError(e.to_string()))?;
+                }
+            }
+        }
+        txn.commit().map_err(|e| DbError::EngineError(e.to_string()))
+    }
+}
@@ -0,0 +1,215 @@
+//! Pluggable key/value storage behind [`TypedDB`]'s typed, entity-prefixed
+//! storage.
+//!
+//! [`DB`] no longer hardwires a single embedded engine: it holds any
+//! [`KvStore`] implementation behind an `Arc`, so operators pick a backend
+//! (RocksDB, LMDB, or -- behind the `sqlite` feature -- SQLite) to suit
+//! their hardware, and [`TypedDB`]/`NomadDB`'s `store_*`/`retrieve_*`
+//! methods and entity key prefixes (`LEAF`, `PROOF`, `UPDATE`, ...) never
+//! have to change to support a new one.
+
+mod lmdb;
+mod rocksdb;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub mod iterator;
+
+pub use lmdb::LmdbStore;
+pub use rocksdb::RocksDbStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+use crate::{Decode, Encode};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Errors surfaced by a [`KvStore`] backend or while decoding a value
+/// retrieved through [`TypedDB`].
+#[derive(Debug, Error)]
+pub enum DbError {
+    /// The backing storage engine rejected a read, write, or iteration.
+    #[error("storage engine error: {0}")]
+    EngineError(String),
+    /// A stored value didn't decode into the type the caller requested.
+    #[error("error decoding stored value: {0}")]
+    EncodingError(#[from] std::io::Error),
+}
+
+/// A single write applied atomically as part of a [`KvStore::write_batch`]
+/// call.
+#[derive(Debug, Clone)]
+pub enum BatchOp {
+    /// Store `value` at the given key, overwriting any existing value.
+    Put(Vec<u8>, Vec<u8>),
+    /// Remove the value stored at the given key, if any.
+    Delete(Vec<u8>),
+}
+
+/// The storage operations a backend must support to sit behind [`DB`].
+/// [`TypedDB`]'s key-prefixing and (de)serialization are built entirely on
+/// top of this trait, so a new backend never has to know about `LEAF`,
+/// `PROOF`, `UPDATE`, or any other entity prefix -- it only ever sees raw
+/// bytes.
+pub trait KvStore: std::fmt::Debug + Send + Sync {
+    /// Fetch the raw value stored at `key`, if any.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, DbError>;
+    /// Store `value` at `key`, overwriting any existing value.
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), DbError>;
+    /// Remove the value stored at `key`, if any.
+    fn delete(&self, key: &[u8]) -> Result<(), DbError>;
+    /// Iterate over every `(key, value)` pair whose key starts with
+    /// `prefix`, in key order. Fails if the backend can't even start the
+    /// scan (e.g. opening a read transaction); an error encountered
+    /// partway through iteration instead just ends the iterator early
+    /// rather than panicking, since a `Result`-producing `Item` would
+    /// make every caller handle per-entry failures this scan has never
+    /// been able to recover from anyway.
+    fn prefix_iterator(
+        &self,
+        prefix: &[u8],
+    ) -> Result<Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + '_>, DbError>;
+    /// Apply every operation in `batch` atomically.
+    fn write_batch(&self, batch: Vec<BatchOp>) -> Result<(), DbError>;
+}
+
+/// A cheaply-cloneable handle to a pluggable key/value store. Every clone
+/// shares the same backend through the inner `Arc`.
+#[derive(Clone)]
+pub struct DB(Arc<dyn KvStore>);
+
+impl std::fmt::Debug for DB {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl DB {
+    /// Select `store` as this handle's storage backend.
+    pub fn new(store: impl KvStore + 'static) -> Self {
+        Self(Arc::new(store))
+    }
+
+    /// Fetch the raw value stored at `key`, if any.
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>, DbError> {
+        self.0.get(key.as_ref())
+    }
+
+    /// Store `value` at `key`, overwriting any existing value.
+    pub fn put(&self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) -> Result<(), DbError> {
+        self.0.put(key.as_ref(), value.as_ref())
+    }
+
+    /// Remove the value stored at `key`, if any.
+    pub fn delete(&self, key: impl AsRef<[u8]>) -> Result<(), DbError> {
+        self.0.delete(key.as_ref())
+    }
+
+    /// Iterate over every `(key, value)` pair whose key starts with
+    /// `prefix`, in key order.
+    pub fn prefix_iterator(
+        &self,
+        prefix: impl AsRef<[u8]>,
+    ) -> Result<Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + '_>, DbError> {
+        self.0.prefix_iterator(prefix.as_ref())
+    }
+
+    /// Apply every operation in `batch` atomically.
+    pub fn write_batch(&self, batch: Vec<BatchOp>) -> Result<(), DbError> {
+        self.0.write_batch(batch)
+    }
+}
+
+/// A [`DB`] scoped under an `entity` (e.g. a home or replica's name),
+/// providing typed, prefixed storage on top of it: every `store_*`/
+/// `retrieve_*` call additionally scopes its key under `entity` and a
+/// caller-supplied entity prefix (`LEAF`, `PROOF`, `UPDATE`, ...), so
+/// multiple entities can safely share one backing [`DB`].
+#[derive(Debug, Clone)]
+pub struct TypedDB {
+    entity: String,
+    db: DB,
+}
+
+impl AsRef<DB> for TypedDB {
+    fn as_ref(&self) -> &DB {
+        &self.db
+    }
+}
+
+impl TypedDB {
+    /// Scope `db` under `entity`.
+    pub fn new(entity: String, db: DB) -> Self {
+        Self { entity, db }
+    }
+
+    fn full_key(&self, prefix: &str, key: &[u8]) -> Vec<u8> {
+        let mut full = Vec::with_capacity(self.entity.len() + prefix.len() + key.len());
+        full.extend_from_slice(self.entity.as_bytes());
+        full.extend_from_slice(prefix.as_bytes());
+        full.extend_from_slice(key);
+        full
+    }
+
+    fn retrieve<V: Decode>(&self, prefix: &str, raw_key: &[u8]) -> Result<Option<V>, DbError> {
+        match self.db.get(self.full_key(prefix, raw_key))? {
+            Some(bytes) => Ok(Some(V::read_from(&mut bytes.as_slice())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Store `value` under `key`, scoped under this `TypedDB`'s entity and
+    /// `prefix`.
+    pub fn store_encodable(
+        &self,
+        prefix: impl AsRef<str>,
+        key: impl Encode,
+        value: &impl Encode,
+    ) -> Result<(), DbError> {
+        self.db
+            .put(self.full_key(prefix.as_ref(), &key.to_vec()), value.to_vec())
+    }
+
+    /// Retrieve and decode the value stored under `key`/`prefix`.
+    pub fn retrieve_decodable<V: Decode>(
+        &self,
+        prefix: impl AsRef<str>,
+        key: impl Encode,
+    ) -> Result<Option<V>, DbError> {
+        self.retrieve(prefix.as_ref(), &key.to_vec())
+    }
+
+    /// As [`Self::store_encodable`], for a key borrowed rather than taken by
+    /// value.
+    pub fn store_keyed_encodable(
+        &self,
+        prefix: impl AsRef<str>,
+        key: &impl Encode,
+        value: &impl Encode,
+    ) -> Result<(), DbError> {
+        self.db
+            .put(self.full_key(prefix.as_ref(), &key.to_vec()), value.to_vec())
+    }
+
+    /// As [`Self::retrieve_decodable`], for a key borrowed rather than
+    /// taken by value.
+    pub fn retrieve_keyed_decodable<T: AsRef<str>, K: Encode, V: Decode>(
+        &self,
+        prefix: T,
+        key: &K,
+    ) -> Result<Option<V>, DbError> {
+        self.retrieve(prefix.as_ref(), &key.to_vec())
+    }
+
+    /// Remove the value stored under `key`, scoped under this `TypedDB`'s
+    /// entity and `prefix`, if any.
+    pub fn delete_encodable(&self, prefix: impl AsRef<str>, key: impl Encode) -> Result<(), DbError> {
+        self.db.delete(self.full_key(prefix.as_ref(), &key.to_vec()))
+    }
+
+    /// As [`Self::delete_encodable`], for a key borrowed rather than taken
+    /// by value.
+    pub fn delete_keyed_encodable(&self, prefix: impl AsRef<str>, key: &impl Encode) -> Result<(), DbError> {
+        self.db.delete(self.full_key(prefix.as_ref(), &key.to_vec()))
+    }
+}
@@ -0,0 +1,128 @@
+use super::{BatchOp, DbError, KvStore};
+use std::path::Path;
+
+/// The original RocksDB-backed [`KvStore`] -- unchanged on-disk format from
+/// before [`super::DB`] became generic over a backend. The default choice
+/// for write-heavy agents (Watcher, Updater) that benefit from RocksDB's
+/// LSM-tree write amplification characteristics.
+#[derive(Debug)]
+pub struct RocksDbStore {
+    inner: rocksdb::DB,
+}
+
+impl RocksDbStore {
+    /// Open (or create) a RocksDB database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DbError> {
+        let inner =
+            rocksdb::DB::open_default(path).map_err(|e| DbError::EngineError(e.to_string()))?;
+        Ok(Self { inner })
+    }
+}
+
+impl KvStore for RocksDbStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, DbError> {
+        self.inner
+            .get(key)
+            .map_err(|e| DbError::EngineError(e.to_string()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), DbError> {
+        self.inner
+            .put(key, value)
+            .map_err(|e| DbError::EngineError(e.to_string()))
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), DbError> {
+        self.inner
+            .delete(key)
+            .map_err(|e| DbError::EngineError(e.to_string()))
+    }
+
+    fn prefix_iterator(
+        &self,
+        prefix: &[u8],
+    ) -> Result<Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + '_>, DbError> {
+        // rocksdb's own prefix_iterator can't fail up front -- it only
+        // ever errors per-entry, once iteration is under way -- so this
+        // always returns `Ok`. A mid-scan error ends the iterator rather
+        // than panicking.
+        //
+        // rocksdb's `prefix_iterator` is just a forward scan starting at
+        // `prefix` -- without a configured column-family prefix extractor
+        // (which `open` doesn't set up) it never stops at the prefix
+        // boundary on its own, so `take_while` has to do that here,
+        // exactly like the client-side filter in `sqlite.rs`.
+        let owned_prefix = prefix.to_vec();
+        Ok(Box::new(
+            self.inner
+                .prefix_iterator(prefix)
+                .map_while(Result::ok)
+                .take_while(move |(k, _)| k.starts_with(owned_prefix.as_slice())),
+        ))
+    }
+
+    fn write_batch(&self, batch: Vec<BatchOp>) -> Result<(), DbError> {
+        let mut wb = rocksdb::WriteBatch::default();
+        for op in batch {
+            match op {
+                BatchOp::Put(key, value) => wb.put(key, value),
+                BatchOp::Delete(key) => wb.delete(key),
+            }
+        }
+        self.inner
+            .write(wb)
+            .map_err(|e| DbError::EngineError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Open a `RocksDbStore` at a fresh, process-unique temp path -- this
+    /// crate has no `tempfile` dependency, so uniqueness is rolled by hand.
+    fn open_test_store() -> (RocksDbStore, std::path::PathBuf) {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "nomad-rocksdb-prefix-iterator-test-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        let store = RocksDbStore::open(&path).unwrap();
+        (store, path)
+    }
+
+    #[test]
+    fn prefix_iterator_does_not_cross_into_the_next_prefix() {
+        let (store, path) = open_test_store();
+
+        store.put(b"alpha_1", b"a1").unwrap();
+        store.put(b"alpha_2", b"a2").unwrap();
+        store.put(b"alpha_3", b"a3").unwrap();
+        // `alphb_` sorts immediately after every `alpha_*` key -- a scan
+        // that didn't stop at the prefix boundary would run straight into
+        // it, which is exactly the bug this guards against.
+        store.put(b"alphb_1", b"b1").unwrap();
+
+        let found: Vec<Box<[u8]>> = store
+            .prefix_iterator(b"alpha_")
+            .unwrap()
+            .map(|(k, _)| k)
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                Box::from(&b"alpha_1"[..]),
+                Box::from(&b"alpha_2"[..]),
+                Box::from(&b"alpha_3"[..]),
+            ]
+        );
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(path);
+    }
+}
@@ -0,0 +1,106 @@
+use super::{BatchOp, DbError, KvStore};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// An optional SQLite-backed [`KvStore`], behind the `sqlite` feature, for
+/// operators who'd rather run one familiar embedded file format across
+/// their whole stack than add RocksDB or LMDB as a new dependency just for
+/// this agent.
+#[derive(Debug)]
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Open (or create) a SQLite database at `path`, creating the
+    /// single key/value table this store expects.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DbError> {
+        let conn = Connection::open(path).map_err(|e| DbError::EngineError(e.to_string()))?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS kv (key BLOB PRIMARY KEY, value BLOB NOT NULL)",
+            [],
+        )
+        .map_err(|e| DbError::EngineError(e.to_string()))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+}
+
+impl KvStore for SqliteStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, DbError> {
+        let conn = self.conn.lock().expect("sqlite connection poisoned");
+        conn.query_row("SELECT value FROM kv WHERE key = ?1", params![key], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(|e| DbError::EngineError(e.to_string()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), DbError> {
+        let conn = self.conn.lock().expect("sqlite connection poisoned");
+        conn.execute(
+            "INSERT INTO kv (key, value) VALUES (?1, ?2) \
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )
+        .map_err(|e| DbError::EngineError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), DbError> {
+        let conn = self.conn.lock().expect("sqlite connection poisoned");
+        conn.execute("DELETE FROM kv WHERE key = ?1", params![key])
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn prefix_iterator(
+        &self,
+        prefix: &[u8],
+    ) -> Result<Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + '_>, DbError> {
+        let conn = self.conn.lock().expect("sqlite connection poisoned");
+        // SQLite has no native "starts with" byte-range scan the way
+        // RocksDB/LMDB's ordered key space gives them; filter client-side
+        // since per-entity key spaces are small enough for this to be fine.
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM kv ORDER BY key")
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        let prefix = prefix.to_vec();
+        let entries: Vec<_> = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })
+            .map_err(|e| DbError::EngineError(e.to_string()))?
+            .map_while(Result::ok)
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, value)| (key.into_boxed_slice(), value.into_boxed_slice()))
+            .collect();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn write_batch(&self, batch: Vec<BatchOp>) -> Result<(), DbError> {
+        let mut conn = self.conn.lock().expect("sqlite connection poisoned");
+        let txn = conn
+            .transaction()
+            .map_err(|e| DbError::EngineError(e.to_string()))?;
+        for op in batch {
+            match op {
+                BatchOp::Put(key, value) => {
+                    txn.execute(
+                        "INSERT INTO kv (key, value) VALUES (?1, ?2) \
+                         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                        params![key, value],
+                    )
+                    .map_err(|e| DbError::EngineError(e.to_string()))?;
+                }
+                BatchOp::Delete(key) => {
+                    txn.execute("DELETE FROM kv WHERE key = ?1", params![key])
+                        .map_err(|e| DbError::EngineError(e.to_string()))?;
+                }
+            }
+        }
+        txn.commit().map_err(|e| DbError::EngineError(e.to_string()))
+    }
+}
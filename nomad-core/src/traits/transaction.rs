@@ -1,6 +1,7 @@
 use crate::{ChainCommunicationError, PersistedTransaction, TxOutcome};
 use async_trait::async_trait;
 use color_eyre::Result;
+use ethers_core::types::H256;
 use tokio::task::JoinHandle;
 
 /// Interface for chain-agnostic to chain-specifc transaction translators
@@ -23,3 +24,40 @@ pub trait TxSubmitTask: Send + Sync + std::fmt::Debug {
         None
     }
 }
+
+/// A chain-agnostic description of the logical action a [`Completion`]
+/// confirms, rather than the concrete transaction that (attempted to)
+/// perform it. Letting a `Completion` re-derive its claim from contract
+/// state means gas escalation, nonce replacement, or relaying can change
+/// which txid actually lands without invalidating the handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Claim {
+    /// The message with this leaf hash reached `MessageStatus::Processed`.
+    MessageProcessed(H256),
+    /// This root became one of the replica's `acceptable_root`s.
+    AcceptableRoot(H256),
+    /// Fall back to the receipt for this txid, e.g. for calls (like
+    /// `update`) that have no cheaper state-based claim to poll.
+    Receipt(H256),
+}
+
+/// A handle returned by a transaction-submitting call that can be polled
+/// for whether the underlying [`Claim`] has been satisfied, independent of
+/// which transaction (if any) the caller originally submitted.
+///
+/// Modeled on Serai's `Eventuality`/`confirm_completion` split: `submit`
+/// hands back a `Completion` instead of blocking until one specific txid is
+/// mined, so an agent can keep polling even after the submitter resubmits
+/// under a different hash.
+#[async_trait]
+pub trait Completion: Send + Sync + std::fmt::Debug {
+    /// The chain-specific error type surfaced while confirming.
+    type Error;
+
+    /// The claim this handle is polling for.
+    fn claim(&self) -> &Claim;
+
+    /// Check whether `claim()` currently holds, returning the outcome of
+    /// the transaction that satisfied it if one is available.
+    async fn confirm(&self) -> Result<Option<TxOutcome>, Self::Error>;
+}
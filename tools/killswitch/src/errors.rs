@@ -0,0 +1,51 @@
+use std::fmt;
+
+/// Errors produced while fetching and parsing killswitch secrets.
+#[derive(Debug)]
+pub(crate) enum Error {
+    /// The HTTP request itself failed (connection refused, timed out,
+    /// DNS failure, etc).
+    ReqwestError(reqwest::Error),
+    /// The remote secrets endpoint responded with a non-success status.
+    /// `body` is truncated so a stray HTML error page doesn't flood the
+    /// logs.
+    BadStatus { status: u16, body: String },
+    /// The response body didn't deserialize as the expected `Secrets`
+    /// shape.
+    YamlBadDeser(serde_yaml::Error),
+    /// Reading the local `CONFIG_PATH` fallback failed.
+    LocalFallback {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+impl Error {
+    /// Whether this failure is worth retrying -- a network hiccup or a
+    /// transient 5xx, as opposed to a malformed response or a permanent
+    /// 4xx that won't succeed on a second try.
+    pub(crate) fn is_transient(&self) -> bool {
+        match self {
+            Error::ReqwestError(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            Error::BadStatus { status, .. } => (500..600).contains(status),
+            Error::YamlBadDeser(_) | Error::LocalFallback { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ReqwestError(e) => write!(f, "secrets request failed: {}", e),
+            Error::BadStatus { status, body } => {
+                write!(f, "secrets endpoint returned {}: {}", status, body)
+            }
+            Error::YamlBadDeser(e) => write!(f, "secrets did not deserialize: {}", e),
+            Error::LocalFallback { path, source } => {
+                write!(f, "local secrets fallback at {} failed: {}", path, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
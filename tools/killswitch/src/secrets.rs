@@ -2,6 +2,13 @@ use crate::{errors::Error, Environment, Result};
 use reqwest;
 use serde_yaml;
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Attempts allowed against the remote secrets endpoint before falling
+/// back to `CONFIG_PATH` on disk.
+const MAX_ATTEMPTS: u32 = 3;
+/// Delay before the first retry; doubled on each subsequent one.
+const BASE_DELAY: Duration = Duration::from_millis(250);
 
 /// A model for our remote secrets file
 #[derive(Debug, serde::Deserialize)]
@@ -20,15 +27,70 @@ pub(crate) struct Secrets {
 }
 
 impl Secrets {
-    /// Create a `Secrets` by fetching yaml from a remote URL
+    /// Create a `Secrets` by fetching yaml from a remote URL, retrying
+    /// transient failures (network errors, 5xx) with exponential backoff,
+    /// and falling back to `CONFIG_PATH` on disk if the remote host is
+    /// still unreachable once retries are exhausted.
     pub(crate) async fn fetch(url: &str) -> Result<Self> {
-        let bytes = reqwest::get(url)
-            .await
-            .map_err(Error::ReqwestError)?
-            .bytes()
+        match Self::fetch_remote_with_retries(url).await {
+            Ok(secrets) => Ok(secrets),
+            Err(e) if e.is_transient() => {
+                tracing::warn!(error = %e, "secrets fetch exhausted retries; falling back to CONFIG_PATH");
+                Self::fetch_local_fallback(e).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn fetch_remote_with_retries(url: &str) -> Result<Self> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match Self::fetch_remote(url).await {
+                Ok(secrets) => return Ok(secrets),
+                Err(e) if attempt < MAX_ATTEMPTS && e.is_transient() => {
+                    let delay = BASE_DELAY * 2u32.pow(attempt - 1);
+                    tracing::warn!(attempt, ?delay, error = %e, "secrets fetch failed; retrying");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn fetch_remote(url: &str) -> Result<Self> {
+        let response = reqwest::get(url).await.map_err(Error::ReqwestError)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let snippet: String = body.chars().take(200).collect();
+            return Err(Error::BadStatus {
+                status: status.as_u16(),
+                body: snippet,
+            });
+        }
+
+        let bytes = response.bytes().await.map_err(Error::ReqwestError)?;
+        serde_yaml::from_slice::<Self>(&bytes[..]).map_err(Error::YamlBadDeser)
+    }
+
+    /// Read and deserialize `Secrets` from `CONFIG_PATH` on disk. Returns
+    /// `remote_error` unchanged if `CONFIG_PATH` isn't set, so the error
+    /// the caller sees still describes the original remote failure.
+    async fn fetch_local_fallback(remote_error: Error) -> Result<Self> {
+        let path = match std::env::var("CONFIG_PATH") {
+            Ok(path) => path,
+            Err(_) => return Err(remote_error),
+        };
+
+        let contents = tokio::fs::read(&path)
             .await
-            .map_err(Error::ReqwestError)?;
-        Ok(serde_yaml::from_slice::<Self>(&bytes[..]).map_err(Error::YamlBadDeser)?)
+            .map_err(|source| Error::LocalFallback {
+                path: path.clone(),
+                source,
+            })?;
+        serde_yaml::from_slice::<Self>(&contents).map_err(Error::YamlBadDeser)
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::metrics::metrics::Metrics;
-use crate::server::backoff::RestartBackoff;
+use crate::server::backoff::{BackoffConfig, RestartBackoff};
 use crate::server::errors::ServerRejection;
 use crate::server::params::{Network, RestartableAgent};
 
@@ -101,14 +101,25 @@ pub struct K8S {
 }
 
 impl K8S {
+    /// Construct a `K8S` lifeguard using the default restart-backoff
+    /// schedule (decorrelated exponential jitter -- see
+    /// [`BackoffConfig::default`]). Use [`K8S::with_backoff_config`] to
+    /// tune the schedule, e.g. to fall back to the old fixed-linear
+    /// behavior.
     pub async fn new(metrics: Arc<Metrics>) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_backoff_config(metrics, BackoffConfig::default()).await
+    }
+
+    /// As [`K8S::new`], with an explicit restart-backoff schedule so
+    /// operators can tune how aggressively a crash-looping pod is
+    /// retried (or switch back to the plain linear strategy) without a
+    /// code change.
+    pub async fn with_backoff_config(
+        metrics: Arc<Metrics>,
+        backoff_config: BackoffConfig,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         let client = Client::try_default().await?;
-        let backoff = RestartBackoff::new(
-            5,
-            Some(Duration::seconds(30)),
-            Some(Duration::days(1)),
-            metrics.clone(),
-        );
+        let backoff = RestartBackoff::with_config(backoff_config, metrics.clone());
         Ok(K8S {
             client,
             backoff,
@@ -178,6 +189,12 @@ impl K8S {
         Ok(())
     }
 
+    /// Dump every tracked pod's current backoff state, for the admin
+    /// `/backoff` endpoint in `crate::server::routes`.
+    pub async fn backoff_snapshot(&self) -> Vec<crate::server::backoff::BackoffSnapshot> {
+        self.backoff.snapshot().await
+    }
+
     /// Method that is used to get a pod status
     #[instrument]
     pub async fn status(&self, pod: &LifeguardPod) -> Result<PodStatus, K8sError> {
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::k8s::LifeguardPod;
+use crate::metrics::metrics::Metrics;
+
+/// How [`RestartBackoff`] grows a pod's retry delay across repeated
+/// restart attempts.
+#[derive(Debug, Clone, Copy)]
+pub enum BackoffStrategy {
+    /// Delay grows by a fixed `base_delay` step per attempt, capped at
+    /// `max_delay`. Simple, but every pod that starts failing at the same
+    /// block retries in lockstep with every other one.
+    Linear,
+    /// Decorrelated jitter: each attempt's delay is drawn uniformly from
+    /// `[base_delay, prev_delay * multiplier]` and capped at `max_delay`,
+    /// so pods that start crash-looping at the same instant fan out
+    /// across their retries instead of hammering the cluster together.
+    ExponentialJitter { multiplier: u32 },
+}
+
+/// Tunables for [`RestartBackoff`], exposed through `K8S::with_backoff_config` so
+/// operators can pick a retry schedule (and avoid a restart storm) without
+/// a code change.
+#[derive(Debug, Clone, Copy)]
+pub struct BackoffConfig {
+    /// Attempts allowed before the delay is pinned at `max_delay` rather
+    /// than continuing to grow.
+    pub attempt_limit: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub strategy: BackoffStrategy,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            attempt_limit: 5,
+            base_delay: Duration::seconds(30),
+            max_delay: Duration::days(1),
+            strategy: BackoffStrategy::ExponentialJitter { multiplier: 3 },
+        }
+    }
+}
+
+struct PodState {
+    attempts: u32,
+    prev_delay: Duration,
+    next_allowed: DateTime<Utc>,
+}
+
+/// One pod's currently-tracked backoff state, for the admin `/backoff`
+/// dump -- enough for an operator to see why a restart is being refused.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackoffSnapshot {
+    pub pod: String,
+    pub attempts: u32,
+    pub next_allowed: DateTime<Utc>,
+}
+
+/// Tracks restart attempts per pod and decides, on each attempt, whether
+/// it's too soon to retry.
+pub struct RestartBackoff {
+    config: BackoffConfig,
+    state: Mutex<HashMap<String, PodState>>,
+    metrics: Arc<Metrics>,
+}
+
+impl std::fmt::Debug for RestartBackoff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestartBackoff")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl RestartBackoff {
+    /// Construct a backoff tracker using the linear strategy, matching
+    /// this type's original (pre-jitter) behavior.
+    pub fn new(
+        attempt_limit: u32,
+        base_delay: Option<Duration>,
+        max_delay: Option<Duration>,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self::with_config(
+            BackoffConfig {
+                attempt_limit,
+                base_delay: base_delay.unwrap_or_else(|| Duration::seconds(30)),
+                max_delay: max_delay.unwrap_or_else(|| Duration::days(1)),
+                strategy: BackoffStrategy::Linear,
+            },
+            metrics,
+        )
+    }
+
+    /// Construct a backoff tracker under an explicit [`BackoffConfig`],
+    /// e.g. to opt into [`BackoffStrategy::ExponentialJitter`].
+    pub fn with_config(config: BackoffConfig, metrics: Arc<Metrics>) -> Self {
+        Self {
+            config,
+            state: Mutex::new(HashMap::new()),
+            metrics,
+        }
+    }
+
+    /// Record a restart attempt for `pod`. Returns `Some(next_attempt)` if
+    /// this attempt is too soon and should be refused (the caller should
+    /// surface `next_attempt` as a `TooEarly` error); returns `None` once
+    /// the attempt is allowed to proceed, having scheduled the delay
+    /// before the next one is allowed.
+    pub async fn inc(&self, pod: &LifeguardPod) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        let mut state = self.state.lock().await;
+        let entry = state.entry(pod.to_string()).or_insert_with(|| PodState {
+            attempts: 0,
+            prev_delay: self.config.base_delay,
+            next_allowed: now,
+        });
+
+        // The pod has stayed up well past its last scheduled retry --
+        // treat it as recovered rather than still crash-looping.
+        if now > entry.next_allowed + self.config.max_delay.max(self.config.base_delay) {
+            entry.attempts = 0;
+            entry.prev_delay = self.config.base_delay;
+        }
+
+        if now < entry.next_allowed {
+            self.metrics.backoffs_inc(
+                "restart_backoff",
+                &pod.network.to_string(),
+                &pod.agent.to_string(),
+            );
+            return Some(entry.next_allowed);
+        }
+
+        entry.attempts += 1;
+        let delay = if entry.attempts > self.config.attempt_limit {
+            self.config.max_delay
+        } else {
+            match self.config.strategy {
+                BackoffStrategy::Linear => {
+                    std::cmp::min(self.config.max_delay, self.config.base_delay * entry.attempts as i32)
+                }
+                BackoffStrategy::ExponentialJitter { multiplier } => {
+                    let ceiling = std::cmp::min(self.config.max_delay, entry.prev_delay * multiplier as i32);
+                    let lo_ms = self.config.base_delay.num_milliseconds().max(1);
+                    let hi_ms = ceiling.num_milliseconds().max(lo_ms);
+                    let jittered_ms = rand::thread_rng().gen_range(lo_ms..=hi_ms);
+                    Duration::milliseconds(jittered_ms)
+                }
+            }
+        };
+
+        entry.prev_delay = delay;
+        entry.next_allowed = now + delay;
+        None
+    }
+
+    /// Dump every pod's currently-tracked backoff state, for the admin
+    /// `/backoff` endpoint in [`crate::server::routes`].
+    pub async fn snapshot(&self) -> Vec<BackoffSnapshot> {
+        let state = self.state.lock().await;
+        state
+            .iter()
+            .map(|(pod, s)| BackoffSnapshot {
+                pod: pod.clone(),
+                attempts: s.attempts,
+                next_allowed: s.next_allowed,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::server::params::RestartableAgent;
+
+    fn pod() -> LifeguardPod {
+        LifeguardPod::new("testnet".to_owned(), RestartableAgent::Updater)
+    }
+
+    #[tokio::test]
+    async fn linear_strategy_allows_the_first_attempt() {
+        let backoff = RestartBackoff::new(5, None, None, Arc::new(Metrics::new().unwrap()));
+        assert!(backoff.inc(&pod()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn a_second_attempt_before_the_delay_elapses_is_refused() {
+        let backoff = RestartBackoff::new(5, None, None, Arc::new(Metrics::new().unwrap()));
+        assert!(backoff.inc(&pod()).await.is_none());
+        assert!(backoff.inc(&pod()).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn exponential_jitter_delay_stays_within_its_bounds() {
+        let config = BackoffConfig {
+            attempt_limit: 10,
+            base_delay: Duration::seconds(1),
+            max_delay: Duration::seconds(100),
+            strategy: BackoffStrategy::ExponentialJitter { multiplier: 3 },
+        };
+        let backoff = RestartBackoff::with_config(config, Arc::new(Metrics::new().unwrap()));
+        let p = pod();
+
+        assert!(backoff.inc(&p).await.is_none());
+        let next_allowed = {
+            let state = backoff.state.lock().await;
+            state.get(&p.to_string()).unwrap().next_allowed
+        };
+        let delay = next_allowed - Utc::now();
+        assert!(delay >= Duration::zero());
+        assert!(delay <= Duration::seconds(1) * 3 + Duration::seconds(1));
+    }
+
+    #[tokio::test]
+    async fn attempts_past_the_limit_are_pinned_at_max_delay() {
+        let config = BackoffConfig {
+            attempt_limit: 1,
+            base_delay: Duration::milliseconds(1),
+            max_delay: Duration::seconds(1000),
+            strategy: BackoffStrategy::Linear,
+        };
+        let backoff = RestartBackoff::with_config(config, Arc::new(Metrics::new().unwrap()));
+        let p = pod();
+
+        assert!(backoff.inc(&p).await.is_none());
+        // Wait out the first (tiny) delay so the second attempt is allowed.
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        assert!(backoff.inc(&p).await.is_none());
+
+        let prev_delay = {
+            let state = backoff.state.lock().await;
+            state.get(&p.to_string()).unwrap().prev_delay
+        };
+        assert_eq!(prev_delay, Duration::seconds(1000));
+    }
+}
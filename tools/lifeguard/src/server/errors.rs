@@ -0,0 +1,60 @@
+//! Maps lifeguard's internal errors -- chiefly [`crate::k8s::K8sError`] --
+//! onto HTTP responses for the admin router in [`crate::server::routes`].
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// An error surfaced to an admin HTTP client. `impl From<K8sError>` in
+/// `crate::k8s` is the main place these get constructed.
+#[derive(Debug)]
+pub enum ServerRejection {
+    /// A restart was refused by a backoff gate; `next_attempt` is when the
+    /// caller should retry. Mapped to `425 Too Early`, the status this
+    /// situation actually describes.
+    TooEarly(DateTime<Utc>),
+    /// The request itself didn't make sense, e.g. an unknown agent kind
+    /// in the path.
+    BadRequest(String),
+    /// Anything else that went wrong talking to K8s.
+    InternalError(String),
+    /// The `x-lifeguard-admin-token` header was missing or didn't match
+    /// the configured shared secret.
+    Unauthorized,
+}
+
+#[derive(Serialize)]
+struct TooEarlyBody {
+    next_attempt: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for ServerRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::TooEarly(next_attempt) => {
+                (StatusCode::TOO_EARLY, Json(TooEarlyBody { next_attempt })).into_response()
+            }
+            Self::BadRequest(message) => {
+                (StatusCode::BAD_REQUEST, Json(ErrorBody { error: message })).into_response()
+            }
+            Self::InternalError(message) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorBody { error: message }))
+                    .into_response()
+            }
+            Self::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorBody {
+                    error: "missing or invalid admin token".to_owned(),
+                }),
+            )
+                .into_response(),
+        }
+    }
+}
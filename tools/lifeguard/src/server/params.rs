@@ -0,0 +1,53 @@
+//! Path-parameter types shared between [`crate::k8s::K8S`] and the admin
+//! HTTP router in [`crate::server::routes`]: which network, and which
+//! agent kind, a request is about.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Which chain/environment a pod belongs to, e.g. `"kovan"` or `"rinkeby"`.
+/// Kept as a bare `String` rather than an enum since the set of networks
+/// is config-driven, not fixed at compile time.
+pub type Network = String;
+
+/// Which Nomad agent binary a pod runs -- the set of agents `lifeguard`
+/// knows how to restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestartableAgent {
+    Updater,
+    Relayer,
+    Processor,
+    Watcher,
+    Kathy,
+}
+
+impl fmt::Display for RestartableAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Updater => "updater",
+            Self::Relayer => "relayer",
+            Self::Processor => "processor",
+            Self::Watcher => "watcher",
+            Self::Kathy => "kathy",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for RestartableAgent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "updater" => Ok(Self::Updater),
+            "relayer" => Ok(Self::Relayer),
+            "processor" => Ok(Self::Processor),
+            "watcher" => Ok(Self::Watcher),
+            "kathy" => Ok(Self::Kathy),
+            other => Err(format!("unknown agent kind: {}", other)),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Admin HTTP surface over [`crate::k8s::K8S`]: list pods, check one
+//! pod's status, and trigger a (backoff-respecting) restart -- the
+//! programmatic equivalent of the `status`/`try_delete_pod` methods `K8S`
+//! already exposes to Rust callers, for an operator hitting this from
+//! outside the binary instead of needing direct RPC into the process.
+//!
+//! Every route, including the `restart` one that deletes a pod, is gated
+//! behind the [`ADMIN_TOKEN_HEADER`] shared-secret header checked in
+//! [`require_admin_token`] -- there's no per-operator identity here, just
+//! a single token set at deploy time, so this is only appropriate to
+//! expose cluster-internally (e.g. behind a `ClusterIP` service), never
+//! on a public-facing listener.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Request, State};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Serialize;
+
+use crate::k8s::{LifeguardPod, PodStatus, K8S};
+use crate::server::backoff::BackoffSnapshot;
+use crate::server::errors::ServerRejection;
+use crate::server::params::{Network, RestartableAgent};
+
+/// Header carrying the shared admin secret on every request, checked by
+/// [`require_admin_token`].
+pub const ADMIN_TOKEN_HEADER: &str = "x-lifeguard-admin-token";
+
+/// Shared state for the admin router: the `K8S` client, the set of pods
+/// this deployment is responsible for (`K8S` has no "list all pods in
+/// the cluster" call, so `GET /pods` enumerates this set instead), and
+/// the shared secret [`require_admin_token`] checks on every request.
+#[derive(Clone)]
+pub struct AdminState {
+    k8s: Arc<K8S>,
+    pods: Arc<Vec<(Network, RestartableAgent)>>,
+    admin_token: Arc<str>,
+}
+
+impl AdminState {
+    pub fn new(k8s: Arc<K8S>, pods: Vec<(Network, RestartableAgent)>, admin_token: String) -> Self {
+        Self {
+            k8s,
+            pods: Arc::new(pods),
+            admin_token: admin_token.into(),
+        }
+    }
+}
+
+/// Reject any request whose `x-lifeguard-admin-token` header doesn't
+/// match `state.admin_token`, before it reaches a handler -- the only
+/// thing standing between this router and anyone who can reach the
+/// listening port.
+async fn require_admin_token(
+    State(state): State<AdminState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ServerRejection> {
+    let supplied = request
+        .headers()
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    match supplied {
+        Some(token) if token == state.admin_token.as_ref() => Ok(next.run(request).await),
+        _ => Err(ServerRejection::Unauthorized),
+    }
+}
+
+/// Build the admin router: `GET /pods`, `GET /pods/:network/:agent/status`,
+/// `POST /pods/:network/:agent/restart`, `GET /backoff` -- all behind the
+/// [`require_admin_token`] shared-secret gate.
+pub fn router(state: AdminState) -> Router {
+    Router::new()
+        .route("/pods", get(list_pods))
+        .route("/pods/:network/:agent/status", get(pod_status))
+        .route("/pods/:network/:agent/restart", post(restart_pod))
+        .route("/backoff", get(backoff))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_admin_token,
+        ))
+        .with_state(state)
+}
+
+fn parse_agent(agent: &str) -> Result<RestartableAgent, ServerRejection> {
+    agent
+        .parse()
+        .map_err(|e| ServerRejection::BadRequest(format!("{}", e)))
+}
+
+#[derive(Serialize)]
+struct PodEntry {
+    network: Network,
+    agent: RestartableAgent,
+    status: PodStatus,
+}
+
+async fn list_pods(
+    State(state): State<AdminState>,
+) -> Result<Json<Vec<PodEntry>>, ServerRejection> {
+    let mut entries = Vec::with_capacity(state.pods.len());
+    for (network, agent) in state.pods.iter() {
+        let pod = LifeguardPod::new(network.clone(), *agent);
+        let status = state.k8s.status(&pod).await?;
+        entries.push(PodEntry {
+            network: network.clone(),
+            agent: *agent,
+            status,
+        });
+    }
+    Ok(Json(entries))
+}
+
+async fn pod_status(
+    State(state): State<AdminState>,
+    Path((network, agent)): Path<(Network, String)>,
+) -> Result<Json<PodStatus>, ServerRejection> {
+    let agent = parse_agent(&agent)?;
+    let pod = LifeguardPod::new(network, agent);
+    let status = state.k8s.status(&pod).await?;
+    Ok(Json(status))
+}
+
+#[derive(Serialize)]
+struct RestartAccepted {
+    restarted: bool,
+}
+
+async fn restart_pod(
+    State(state): State<AdminState>,
+    Path((network, agent)): Path<(Network, String)>,
+) -> Result<Json<RestartAccepted>, ServerRejection> {
+    let agent = parse_agent(&agent)?;
+    let pod = LifeguardPod::new(network, agent);
+    state.k8s.try_delete_pod(&pod).await?;
+    Ok(Json(RestartAccepted { restarted: true }))
+}
+
+async fn backoff(State(state): State<AdminState>) -> Json<Vec<BackoffSnapshot>> {
+    Json(state.k8s.backoff_snapshot().await)
+}